@@ -0,0 +1,50 @@
+use {
+    battlezips_v2::{
+        prover::{board_keys, prove_board, BOARD_K},
+        utils::{
+            board::Board,
+            deck::Deck,
+            profile::{HeapStats, PeakAllocator},
+        },
+    },
+    halo2_proofs::{pasta::EqAffine, poly::commitment::Params},
+};
+
+#[global_allocator]
+static ALLOCATOR: PeakAllocator = PeakAllocator::new();
+
+/**
+ * Print the heap usage of one proving stage
+ *
+ * @param stage - name of the stage
+ * @param stats - heap usage recorded for the stage
+ */
+fn report(stage: &str, stats: HeapStats) {
+    println!(
+        "{:<8} peak {:>8.2} MiB | allocated {:>9.2} MiB | retained {:>8.2} MiB",
+        stage,
+        stats.peak as f64 / (1 << 20) as f64,
+        stats.total as f64 / (1 << 20) as f64,
+        stats.retained as f64 / (1 << 20) as f64,
+    );
+}
+
+/**
+ * Report peak heap usage while proving a full board
+ * @dev run with `cargo run --release --example profile --features profiling`
+ */
+fn main() {
+    let board = Board::from(&Deck::from([
+        Some((3, 3, true)),
+        Some((5, 4, false)),
+        Some((0, 1, false)),
+        Some((0, 5, true)),
+        Some((6, 1, false)),
+    ]));
+    let (params, stats) = ALLOCATOR.profile(|| Params::<EqAffine>::new(BOARD_K));
+    report("params", stats);
+    let (pk, stats) = ALLOCATOR.profile(|| board_keys(&params).unwrap());
+    report("keygen", stats);
+    let (_, stats) = ALLOCATOR.profile(|| prove_board(&params, &pk, &board).unwrap());
+    report("prove", stats);
+}
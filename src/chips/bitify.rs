@@ -51,6 +51,18 @@ impl<F: FieldExt, const B: usize> Num2BitsChip<F, B> {
         }
     }
 
+    /// Create a new chip, witnessing the bits of the assigned value's little endian representation.
+    pub fn from_assigned(value: AssignedCell<F, F>) -> Self {
+        let mut bits = [(); B].map(|_| Value::unknown());
+        for (i, bit) in bits.iter_mut().enumerate() {
+            *bit = value.value().map(|v| {
+                let repr = v.to_repr();
+                F::from((repr.as_ref()[i / 8] >> (i % 8)) & 1 == 1)
+            });
+        }
+        Self { value, bits }
+    }
+
     /// Make the circuit config.
     pub fn configure(
         meta: &mut ConstraintSystem<F>,
@@ -102,11 +114,14 @@ impl<F: FieldExt, const B: usize> Num2BitsChip<F, B> {
     }
 
     /// Synthesize the circuit.
+    #[cfg_attr(feature = "trace", tracing::instrument(level = "debug", skip_all, fields(bits = B)))]
     pub fn synthesize(
         &self,
         config: BitifyConfig,
         mut layouter: impl Layouter<F>,
     ) -> Result<[AssignedCell<F, F>; B], Error> {
+        #[cfg(feature = "trace")]
+        tracing::debug!(region = "num2bits", offset = 0, value = ?self.value.value());
         layouter.assign_region(
             || "num2bits",
             |mut region: Region<'_, F>| {
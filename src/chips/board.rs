@@ -2,14 +2,22 @@ use {
     crate::{
         chips::{
             bitify::{BitifyConfig, Bits2NumChip, Num2BitsChip},
+            footprint::{FootprintChip, FootprintConfig},
+            no_touch::{NoTouchChip, NoTouchConfig},
+            poseidon::{commit_salted, hash_single},
             placement::{PlacementChip, PlacementConfig, AssignedBits},
             transpose::{TransposeChip, TransposeConfig},
         },
-        utils::{binary::BinaryValue, board::BOARD_SIZE},
+        utils::{
+            binary::BinaryValue,
+            board::BOARD_SIZE,
+            deck::Deck,
+            ship::{ShipType, WitnessOption},
+        },
     },
     halo2_gadgets::poseidon::{
-        primitives::{ConstantLength, Spec},
-        Hash, Pow5Chip, Pow5Config,
+        primitives::Spec,
+        Pow5Chip, Pow5Config,
     },
     halo2_proofs::{
         arithmetic::FieldExt,
@@ -26,6 +34,62 @@ use {
 pub type Commitments<F> = [AssignedCell<F, F>; 10];
 pub type Placements<F> = [AssignedBits<F>; 10];
 
+/**
+ * Per-game salt mixed into a board commitment (see chips::poseidon::commit_salted)
+ *
+ * @param secret - the player's private secret, reused across games
+ * @param game_id - public identifier of the game the commitment is bound to
+ * @param row - the public instance row to export game_id to
+ */
+#[derive(Clone, Copy, Debug)]
+pub struct BoardSalt<F: FieldExt> {
+    pub secret: F,
+    pub game_id: F,
+    pub row: usize,
+}
+
+/**
+ * Commit to an assigned board state, under a per-game salt if one is given
+ * @dev the digest path shared by every chip proving a statement about a committed board, so a
+ *      board committed with a salt can be checked by shot, win and tally proofs of the same game
+ *
+ * @param poseidon - the configured poseidon chip
+ * @param advice - an equality-enabled advice column to load the padding or salt inputs into
+ * @param instance - the instance column to export the game id to
+ * @param preimage - assigned cell storing the board state
+ * @param salt - private secret and public game id to salt with, or None for poseidon(board)
+ * @return - assigned cell storing the board commitment
+ */
+pub fn commit_board_state<S: Spec<F, 3, 2>, F: FieldExt>(
+    poseidon: Pow5Config<F, 3, 2>,
+    layouter: &mut impl Layouter<F>,
+    advice: Column<Advice>,
+    instance: Column<Instance>,
+    preimage: AssignedCell<F, F>,
+    salt: Option<BoardSalt<F>>,
+) -> Result<AssignedCell<F, F>, Error> {
+    let salt = match salt {
+        Some(salt) => salt,
+        None => return hash_single::<S, F>(poseidon, layouter, advice, preimage),
+    };
+    let [secret, game_id] = layouter.assign_region(
+        || "load board salt inputs",
+        |mut region: Region<F>| {
+            let secret = region.assign_advice(
+                || "player secret",
+                advice,
+                0,
+                || Value::known(salt.secret),
+            )?;
+            let game_id =
+                region.assign_advice(|| "game id", advice, 1, || Value::known(salt.game_id))?;
+            Ok([secret, game_id])
+        },
+    )?;
+    layouter.constrain_instance(game_id.cell(), instance, salt.row)?;
+    commit_salted::<S, F>(poseidon, layouter, preimage, secret, game_id)
+}
+
 /**
  * Return a label for commitments in debugging
  *
@@ -48,6 +112,16 @@ pub fn commitment_label(i: usize) -> String {
     })
 }
 
+/**
+ * Return the layouter namespace a ship's placement regions are synthesized in
+ *
+ * @param ship_type - the ship the placement chip checks
+ * @return - the namespace name (ex: "Cruiser placement")
+ */
+pub fn placement_namespace(ship_type: ShipType) -> String {
+    format!("{} placement", ship_type.name())
+}
+
 // bundles all placement configs together
 #[derive(Clone, Copy, Debug)]
 pub struct PlacementConfigs<F: FieldExt> {
@@ -67,11 +141,13 @@ pub struct BoardConfig<F: FieldExt> {
     pub bits2num: BitifyConfig,
     pub placement: PlacementConfigs<F>,
     pub transpose: TransposeConfig<F>,
+    pub no_touch: Option<NoTouchConfig<F>>, // only set if configured with the no touch rule
+    pub footprint: Option<FootprintConfig<F>>, // only set if configured to place ships by coordinates
     pub poseidon: Pow5Config<F, 3, 2>,
     pub advice: [Column<Advice>; 11],
     pub fixed: [Column<Fixed>; 6],
     pub instance: Column<Instance>,
-    pub selectors: [Selector; 1],
+    pub selectors: [Selector; 3],
     _marker: PhantomData<F>,
 }
 
@@ -170,16 +246,32 @@ pub trait BoardInstructions<S: Spec<F, 3, 2>, F: FieldExt> {
     ) -> Result<AssignedCell<F, F>, Error>;
 
     /**
-     * Constrained computation of poseidon hash of transposed board state
+     * Constrained computation of the commitment to the transposed board state
      *
      * @param preimage - assigned cell storing the transposed board state to hash
-     * @return - if successful, assigned cell storing the poseidon hash of the board state
+     * @param salt - per-game salt to commit to the board under, or None for the unsalted digest
+     * @return - if successful, assigned cell storing the commitment to the board state
      */
     fn hash_board(
         &self,
         layouter: &mut impl Layouter<F>,
         preimage: AssignedCell<F, F>,
+        salt: Option<BoardSalt<F>>,
     ) -> Result<AssignedCell<F, F>, Error>;
+
+    /**
+     * Constrain the popcount of the transposed board state to equal the fleet's tonnage
+     * @dev row 0 is padding so the running sum's Rotation::prev() starts from 0
+     * @dev sums at most 100 boolean bits so the running sum cannot wrap the field
+     *
+     * @param transposed - reference to assigned cells storing bits that represent serialized board state
+     * @return - Ok if synthesis executes successfully
+     */
+    fn constrain_tonnage(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        transposed: AssignedBits<F>,
+    ) -> Result<(), Error>;
 }
 
 impl<S: Spec<F, 3, 2>, F: FieldExt> BoardChip<S, F> {
@@ -216,11 +308,12 @@ impl<S: Spec<F, 3, 2>, F: FieldExt> BoardChip<S, F> {
         meta.enable_equality(instance);
 
         // define selectors
+        // [commitment orientation, tonnage running sum, tonnage constraint]
         let mut selectors = Vec::<Selector>::new();
-        for _ in 0..1 {
+        for _ in 0..3 {
             selectors.push(meta.selector());
         }
-        let selectors: [Selector; 1] = selectors.try_into().unwrap();
+        let selectors: [Selector; 3] = selectors.try_into().unwrap();
 
         // define num2bits chips
         let mut num2bits = Vec::<BitifyConfig>::new();
@@ -302,12 +395,31 @@ impl<S: Spec<F, 3, 2>, F: FieldExt> BoardChip<S, F> {
             )
         });
 
+        meta.create_gate("board tonnage running sum", |meta| {
+            // count the number of occupied cells in the transposed board state
+            let bit = meta.query_advice(advice[0], Rotation::cur());
+            let prev = meta.query_advice(advice[1], Rotation::prev());
+            let sum = meta.query_advice(advice[1], Rotation::cur());
+            let selector = meta.query_selector(selectors[1]);
+            Constraints::with_selector(selector, [("Running Sum: Tonnage", bit + prev - sum)])
+        });
+
+        meta.create_gate("board tonnage constraint", |meta| {
+            // constrain the total occupied cells to equal the tonnage of a full fleet
+            let tonnage = Expression::Constant(F::from(Deck::expected_tonnage() as u64));
+            let sum = meta.query_advice(advice[1], Rotation::cur());
+            let selector = meta.query_selector(selectors[2]);
+            Constraints::with_selector(selector, [("Board occupies fleet tonnage", sum - tonnage)])
+        });
+
         // return config
         BoardConfig {
             num2bits,
             bits2num,
             placement,
             transpose,
+            no_touch: None,
+            footprint: None,
             poseidon,
             advice,
             fixed,
@@ -317,17 +429,147 @@ impl<S: Spec<F, 3, 2>, F: FieldExt> BoardChip<S, F> {
         }
     }
 
+    /**
+     * Configure the board chip with the variant rule that ships may not touch, even diagonally
+     * @dev the no touch chip reuses the transpose chip's 10 placement columns
+     */
+    pub fn configure_no_touch(meta: &mut ConstraintSystem<F>) -> BoardConfig<F> {
+        let mut config = Self::configure(meta);
+        let no_touch = NoTouchChip::<F>::configure(meta, config.transpose.permuted_bits);
+        config.no_touch = Some(no_touch);
+        config
+    }
+
+    /**
+     * Configure the board chip to derive ship commitments from ship coordinates in-circuit
+     * @dev the footprint chip reuses the 11 board advice columns and adds a fixed column for the
+     *      ship length masks. use synthesize_deck to prove a board configured this way
+     */
+    pub fn configure_footprint(meta: &mut ConstraintSystem<F>) -> BoardConfig<F> {
+        let mut config = Self::configure(meta);
+        let mask = meta.fixed_column();
+        let footprint = FootprintChip::<F>::configure(meta, config.advice, mask);
+        config.footprint = Some(footprint);
+        config
+    }
+
     /**
      * Synthesize a proof of a valid board
      *
      * @param ship_commitments - 10x private ship commitments indicating a horizontal or vertical placement
      * @param board - board state as a BinaryValue
+     * @param row - the public instance row to export the board commitment to
      */
     pub fn synthesize(
         &self,
         mut layouter: impl Layouter<F>,
         ship_commitments: [BinaryValue; 10],
         board: BinaryValue,
+        row: usize,
+    ) -> Result<(), Error> {
+        // load ship commitments into advice
+        let assigned_commitments = self.load_commitments(&mut layouter, ship_commitments)?;
+        self.synthesize_commitments(
+            &mut layouter,
+            ship_commitments,
+            assigned_commitments,
+            board,
+            None,
+            row,
+        )
+    }
+
+    /**
+     * Synthesize a proof of a valid board committed to under a per-game salt
+     * @dev the commitment is poseidon(board, poseidon(secret, game_id)) instead of poseidon(board),
+     *      so the same layout commits differently every game. game_id is exported so a verifier
+     *      checks the proof is bound to the current game
+     *
+     * @param ship_commitments - 10x private ship commitments indicating a horizontal or vertical placement
+     * @param board - board state as a BinaryValue
+     * @param salt - private secret and public game id the commitment is salted with
+     * @param row - the public instance row to export the board commitment to
+     */
+    pub fn synthesize_salted(
+        &self,
+        mut layouter: impl Layouter<F>,
+        ship_commitments: [BinaryValue; 10],
+        board: BinaryValue,
+        salt: BoardSalt<F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        let assigned_commitments = self.load_commitments(&mut layouter, ship_commitments)?;
+        self.synthesize_commitments(
+            &mut layouter,
+            ship_commitments,
+            assigned_commitments,
+            board,
+            Some(salt),
+            row,
+        )
+    }
+
+    /**
+     * Synthesize a proof of a valid board from ship coordinates
+     * @dev each ship's H, V commitments are derived in-circuit by the footprint chip, so the
+     *      combined board bits are produced by the circuit from (x, y, z, S) alone
+     *      requires a config from configure_footprint
+     *
+     * @param deck - private placements for each of the 5 ships
+     * @param board - board state as a BinaryValue
+     * @param row - the public instance row to export the board commitment to
+     */
+    pub fn synthesize_deck(
+        &self,
+        mut layouter: impl Layouter<F>,
+        deck: Deck,
+        board: BinaryValue,
+        row: usize,
+    ) -> Result<(), Error> {
+        let chip = FootprintChip::<F>::new(self.config.footprint.ok_or(Error::Synthesis)?);
+        let ship_types = [
+            ShipType::Carrier,
+            ShipType::Battleship,
+            ShipType::Cruiser,
+            ShipType::Submarine,
+            ShipType::Destroyer,
+        ];
+        let mut ship_commitments = Vec::<BinaryValue>::new();
+        let mut assigned_commitments = Vec::<AssignedCell<F, F>>::new();
+        for (ship_type, ship) in ship_types.into_iter().zip(deck.iterator()) {
+            let commitments = ship.map_or([BinaryValue::empty(); 2], |ship| {
+                ship.witness(WitnessOption::Default)
+            });
+            ship_commitments.extend(commitments);
+            assigned_commitments.extend(chip.synthesize(&mut layouter, ship_type, ship)?);
+        }
+        self.synthesize_commitments(
+            &mut layouter,
+            ship_commitments.try_into().unwrap(),
+            assigned_commitments.try_into().unwrap(),
+            board,
+            None,
+            row,
+        )
+    }
+
+    /**
+     * Constrain assigned ship commitments to form a valid board and export its commitment
+     *
+     * @param ship_commitments - 10x private ship commitments indicating a horizontal or vertical placement
+     * @param assigned_commitments - assigned cells storing the ship commitments
+     * @param board - board state as a BinaryValue
+     * @param salt - per-game salt to commit to the board under, or None for the unsalted digest
+     * @param row - the public instance row to export the board commitment to
+     */
+    fn synthesize_commitments(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        ship_commitments: [BinaryValue; 10],
+        assigned_commitments: Commitments<F>,
+        board: BinaryValue,
+        salt: Option<BoardSalt<F>>,
+        row: usize,
     ) -> Result<(), Error> {
         // compute combined ship commitments
         let mut ships = Vec::<BinaryValue>::new();
@@ -335,23 +577,26 @@ impl<S: Spec<F, 3, 2>, F: FieldExt> BoardChip<S, F> {
             ships.push(ship_commitments[i * 2].zip(ship_commitments[i * 2 + 1]));
         }
         let ships: [BinaryValue; 5] = ships.try_into().unwrap();
-        // load ship commitments into advice
-        let assigned_commitments = self.load_commitments(&mut layouter, ship_commitments)?;
         // decompose commitments into 100 bits each
         let placements =
-            self.decompose_commitments(&mut layouter, ship_commitments, assigned_commitments)?;
+            self.decompose_commitments(layouter, ship_commitments, assigned_commitments)?;
         // run individual ship placement rule checks
-        self.synth_placements(&mut layouter, ships, placements.clone())?;
+        self.synth_placements(layouter, ships, placements.clone())?;
         // check that ships can all be placed together to form a valid board
-        let transposed_bits =
-            self.transpose_placements(&mut layouter, board, placements.clone())?;
+        let transposed_bits = self.transpose_placements(layouter, board, placements.clone())?;
+        // optionally check that no two ships occupy neighboring cells
+        if let Some(no_touch) = self.config.no_touch {
+            NoTouchChip::<F>::new(no_touch).synthesize(layouter, placements)?;
+        }
         // recompose the 100 bit board state into a single value
-        let transposed = self.recompose_board(&mut layouter, board, transposed_bits)?;
+        let transposed = self.recompose_board(layouter, board, transposed_bits.clone())?;
         // hash the board state into public commitment
         // @todo: add signing here to prevent known ciphertext attack
-        let commitment = self.hash_board(&mut layouter, transposed.clone())?;
+        let commitment = self.hash_board(layouter, transposed.clone(), salt)?;
         // export constained board commitment to public instance column
-        layouter.constrain_instance(commitment.cell(), self.config.instance, 0)?;
+        layouter.constrain_instance(commitment.cell(), self.config.instance, row)?;
+        // check that the board occupies exactly as many cells as the fleet
+        self.constrain_tonnage(layouter, transposed_bits)?;
         Ok(())
     }
 }
@@ -410,32 +655,33 @@ impl<S: Spec<F, 3, 2>, F: FieldExt> BoardInstructions<S, F> for BoardChip<S, F>
         ships: [BinaryValue; 5],
         placements: Placements<F>,
     ) -> Result<(), Error> {
+        // namespace each placement by ship so failures can be traced back to it
         PlacementChip::<F, 5>::new(self.config.placement.carrier).synthesize(
-            layouter,
+            &mut layouter.namespace(|| placement_namespace(ShipType::Carrier)),
             ships[0],
             placements[0].clone(),
             placements[1].clone(),
         )?;
         PlacementChip::<F, 4>::new(self.config.placement.battleship).synthesize(
-            layouter,
+            &mut layouter.namespace(|| placement_namespace(ShipType::Battleship)),
             ships[1],
             placements[2].clone(),
             placements[3].clone(),
         )?;
         PlacementChip::<F, 3>::new(self.config.placement.cruiser).synthesize(
-            layouter,
+            &mut layouter.namespace(|| placement_namespace(ShipType::Cruiser)),
             ships[2],
             placements[4].clone(),
             placements[5].clone(),
         )?;
         PlacementChip::<F, 3>::new(self.config.placement.submarine).synthesize(
-            layouter,
+            &mut layouter.namespace(|| placement_namespace(ShipType::Submarine)),
             ships[3],
             placements[6].clone(),
             placements[7].clone(),
         )?;
         PlacementChip::<F, 2>::new(self.config.placement.destroyer).synthesize(
-            layouter,
+            &mut layouter.namespace(|| placement_namespace(ShipType::Destroyer)),
             ships[4],
             placements[8].clone(),
             placements[9].clone(),
@@ -451,7 +697,7 @@ impl<S: Spec<F, 3, 2>, F: FieldExt> BoardInstructions<S, F> for BoardChip<S, F>
     ) -> Result<AssignedBits<F>, Error> {
         let chip = TransposeChip::<F>::new(self.config.transpose);
         let bits = board.bitfield::<F, BOARD_SIZE>();
-        Ok(chip.synthesize(layouter, bits, placements).unwrap())
+        chip.synthesize(layouter, bits, placements)
     }
 
     fn recompose_board(
@@ -473,11 +719,51 @@ impl<S: Spec<F, 3, 2>, F: FieldExt> BoardInstructions<S, F> for BoardChip<S, F>
         &self,
         layouter: &mut impl Layouter<F>,
         preimage: AssignedCell<F, F>,
+        salt: Option<BoardSalt<F>>,
     ) -> Result<AssignedCell<F, F>, Error> {
-        let chip = Pow5Chip::construct(self.config.poseidon.clone());
+        commit_board_state::<S, F>(
+            self.config.poseidon.clone(),
+            layouter,
+            self.config.advice[0],
+            self.config.instance,
+            preimage,
+            salt,
+        )
+    }
 
-        let hasher =
-            Hash::<_, _, S, ConstantLength<1>, 3, 2>::init(chip, layouter.namespace(|| "hasher"))?;
-        hasher.hash(layouter.namespace(|| "hash"), [preimage])
+    fn constrain_tonnage(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        transposed: AssignedBits<F>,
+    ) -> Result<(), Error> {
+        layouter.assign_region(
+            || "constrain board tonnage",
+            |mut region: Region<F>| {
+                // pad first row with 0
+                let mut sum = region.assign_advice_from_constant(
+                    || "pad tonnage running sum",
+                    self.config.advice[1],
+                    0,
+                    F::zero(),
+                )?;
+                for (i, cell) in transposed.iter().enumerate() {
+                    let bit = cell.copy_advice(
+                        || format!("copy transposed bit {}", i),
+                        &mut region,
+                        self.config.advice[0],
+                        i + 1,
+                    )?;
+                    sum = region.assign_advice(
+                        || format!("assign running sum (tonnage) {}", i),
+                        self.config.advice[1],
+                        i + 1,
+                        || sum.value().cloned() + bit.value(),
+                    )?;
+                    self.config.selectors[1].enable(&mut region, i + 1)?;
+                }
+                self.config.selectors[2].enable(&mut region, transposed.len())?;
+                Ok(())
+            },
+        )
     }
 }
@@ -0,0 +1,312 @@
+use {
+    crate::utils::{
+        binary::{pow2_table, BinaryValue},
+        ship::{Ship, ShipType, WitnessOption},
+    },
+    halo2_proofs::{
+        arithmetic::FieldExt,
+        circuit::{AssignedCell, Chip, Layouter, Region, Value},
+        plonk::{
+            Advice, Column, ConstraintSystem, Constraints, Error, Expression, Fixed, Selector,
+        },
+        poly::Rotation,
+    },
+    std::marker::PhantomData,
+};
+
+pub const HEAD_BITS: usize = 7; // bits needed to decompose a head cell index in [0, 100)
+
+/**
+ * Derive a ship's H, V placement commitments from its (x, y, z) coordinates in-circuit
+ * @dev a ship of length S with its head at cell h (transposed if vertical) commits to
+ *      2^h * (2^S - 1). h is decomposed into HEAD_BITS bits so 2^h is a product of constants and
+ *      cannot wrap the field. the commitments are still validated by the placement chips, so
+ *      coordinates off the board can only alias a valid placement (as with raw commitments)
+ *
+ * @param advice - 11 advice columns (the board chip's), laid out over 2 rows per ship:
+ *     * row 0: [x, y, z, head, head bits 0..7]
+ *     * row 1: [2^head, H, V]
+ * @param mask - fixed column holding 2^S - 1 for the ship in row 0
+ * @param selector - toggles the footprint constraints on row 0 of a ship's region
+ */
+#[derive(Clone, Copy, Debug)]
+pub struct FootprintConfig<F: FieldExt> {
+    pub advice: [Column<Advice>; 11],
+    pub mask: Column<Fixed>,
+    pub selector: Selector,
+    _marker: PhantomData<F>,
+}
+
+pub struct FootprintChip<F: FieldExt> {
+    config: FootprintConfig<F>,
+}
+
+impl<F: FieldExt> Chip<F> for FootprintChip<F> {
+    type Config = FootprintConfig<F>;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+impl<F: FieldExt> FootprintChip<F> {
+    pub fn new(config: FootprintConfig<F>) -> Self {
+        FootprintChip { config }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 11],
+        mask: Column<Fixed>,
+    ) -> FootprintConfig<F> {
+        // define selectors
+        let selector = meta.selector();
+
+        meta.create_gate("ship footprint", |meta| {
+            let one = Expression::Constant(F::one());
+            let ten = Expression::Constant(F::from(10));
+            let x = meta.query_advice(advice[0], Rotation::cur());
+            let y = meta.query_advice(advice[1], Rotation::cur());
+            let z = meta.query_advice(advice[2], Rotation::cur());
+            let head = meta.query_advice(advice[3], Rotation::cur());
+            let bits = (0..HEAD_BITS)
+                .map(|i| meta.query_advice(advice[4 + i], Rotation::cur()))
+                .collect::<Vec<Expression<F>>>();
+            let power = meta.query_advice(advice[0], Rotation::next());
+            let horizontal = meta.query_advice(advice[1], Rotation::next());
+            let vertical = meta.query_advice(advice[2], Rotation::next());
+            let mask = meta.query_fixed(mask, Rotation::cur());
+            // head = y * 10 + x, or x * 10 + y once a vertical ship is transposed
+            let cell = (one.clone() - z.clone()) * (y.clone() * ten.clone() + x.clone())
+                + z.clone() * (x * ten + y);
+            let recomposed = bits
+                .iter()
+                .enumerate()
+                .fold(Expression::Constant(F::zero()), |sum, (i, bit)| {
+                    sum + bit.clone() * Expression::Constant(pow2_table::<F>()[i])
+                });
+            // 2^head = product of 2^(2^i) for every set head bit i
+            // @dev degree HEAD_BITS + 1 (8): the same extended domain as the board's degree 6 gates
+            let exponentiated = bits
+                .iter()
+                .enumerate()
+                .fold(one.clone(), |product, (i, bit)| {
+                    let factor = Expression::Constant(pow2_table::<F>()[1 << i] - F::one());
+                    product * (one.clone() + bit.clone() * factor)
+                });
+            let mut constraints = vec![
+                (
+                    "Orientation is boolean",
+                    z.clone() * (one.clone() - z.clone()),
+                ),
+                ("Head cell from coordinates", head.clone() - cell),
+                ("Head bits recompose to head cell", head - recomposed),
+                ("Power of two of head cell", power.clone() - exponentiated),
+                (
+                    "Horizontal commitment from footprint",
+                    horizontal - (one.clone() - z.clone()) * power.clone() * mask.clone(),
+                ),
+                (
+                    "Vertical commitment from footprint",
+                    vertical - z * power * mask,
+                ),
+            ];
+            for bit in bits {
+                constraints.push(("Head bit is boolean", bit.clone() * (one.clone() - bit)));
+            }
+            let selector = meta.query_selector(selector);
+            Constraints::with_selector(selector, constraints)
+        });
+
+        FootprintConfig {
+            advice,
+            mask,
+            selector,
+            _marker: PhantomData,
+        }
+    }
+
+    /**
+     * Synthesize the H, V commitments of a ship from its coordinates
+     * @dev an omitted ship is witnessed at (0, 0, horizontal) with both commitments 0, which fails
+     *      the footprint constraints since every placed ship has a nonzero commitment
+     *
+     * @param ship_type - the ship type the region is laid out for (fixes the ship length)
+     * @param ship - the placed ship, or None if the ship is omitted from the deck
+     * @return - assigned [H, V] commitment cells
+     */
+    pub fn synthesize(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        ship_type: ShipType,
+        ship: Option<Ship>,
+    ) -> Result<[AssignedCell<F, F>; 2], Error> {
+        let (x, y, z) = ship.map_or((0, 0, false), |ship| (ship.x, ship.y, ship.z));
+        let head = match z {
+            true => x as u64 * 10 + y as u64,
+            false => y as u64 * 10 + x as u64,
+        };
+        let commitments = ship.map_or([BinaryValue::empty(); 2], |ship| {
+            ship.witness(WitnessOption::Default)
+        });
+        layouter.assign_region(
+            || format!("{} footprint", ship_type.name()),
+            |mut region: Region<F>| {
+                self.config.selector.enable(&mut region, 0)?;
+                region.assign_fixed(
+                    || "ship length mask",
+                    self.config.mask,
+                    0,
+                    || Value::known(pow2_table::<F>()[ship_type.length()] - F::one()),
+                )?;
+                let coordinates = [x as u64, y as u64, z as u64, head];
+                for (i, (name, value)) in
+                    ["x", "y", "z", "head"].iter().zip(coordinates).enumerate()
+                {
+                    region.assign_advice(
+                        || format!("ship {}", name),
+                        self.config.advice[i],
+                        0,
+                        || Value::known(F::from(value)),
+                    )?;
+                }
+                for i in 0..HEAD_BITS {
+                    region.assign_advice(
+                        || format!("head bit {}", i),
+                        self.config.advice[4 + i],
+                        0,
+                        || Value::known(F::from(head >> i & 1)),
+                    )?;
+                }
+                region.assign_advice(
+                    || "2^head",
+                    self.config.advice[0],
+                    1,
+                    || Value::known(F::from(2).pow_vartime([head])),
+                )?;
+                let mut assigned = Vec::<AssignedCell<F, F>>::new();
+                for (i, commitment) in commitments.iter().enumerate() {
+                    assigned.push(region.assign_advice(
+                        || format!("{} ship commitment", ["H", "V"][i]),
+                        self.config.advice[1 + i],
+                        1,
+                        || Value::known(F::from_u128(commitment.lower_u128())),
+                    )?);
+                }
+                Ok(assigned.try_into().unwrap())
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        halo2_proofs::{
+            circuit::SimpleFloorPlanner,
+            dev::MockProver,
+            pasta::Fp,
+            plonk::{Circuit, Instance},
+        },
+    };
+
+    #[derive(Clone, Copy, Debug)]
+    struct TestFootprintConfig {
+        footprint: FootprintConfig<Fp>,
+        instance: Column<Instance>,
+    }
+
+    // derives a ship's commitments and exports them as [H, V]
+    struct FootprintCircuit {
+        ship_type: ShipType,
+        ship: Option<Ship>,
+    }
+
+    impl Circuit<Fp> for FootprintCircuit {
+        type Config = TestFootprintConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            FootprintCircuit {
+                ship_type: self.ship_type,
+                ship: self.ship,
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> TestFootprintConfig {
+            let advice = [(); 11].map(|_| {
+                let col = meta.advice_column();
+                meta.enable_equality(col);
+                col
+            });
+            let mask = meta.fixed_column();
+            let instance = meta.instance_column();
+            meta.enable_equality(instance);
+            TestFootprintConfig {
+                footprint: FootprintChip::configure(meta, advice, mask),
+                instance,
+            }
+        }
+
+        fn synthesize(
+            &self,
+            config: TestFootprintConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let commitments = FootprintChip::new(config.footprint).synthesize(
+                &mut layouter,
+                self.ship_type,
+                self.ship,
+            )?;
+            for (row, commitment) in commitments.iter().enumerate() {
+                layouter.constrain_instance(commitment.cell(), config.instance, row)?;
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn footprint_matches_witness() {
+        for ship in [
+            Ship::new(ShipType::Carrier, 3, 3, true),
+            Ship::new(ShipType::Battleship, 5, 4, false),
+            Ship::new(ShipType::Destroyer, 8, 9, false),
+            Ship::new(ShipType::Cruiser, 9, 7, true),
+        ] {
+            let circuit = FootprintCircuit {
+                ship_type: ship.ship_type,
+                ship: Some(ship),
+            };
+            let instance = ship
+                .witness(WitnessOption::Default)
+                .map(|commitment| Fp::from_u128(commitment.lower_u128()))
+                .to_vec();
+            let prover = MockProver::run(4, &circuit, vec![instance.clone()]).unwrap();
+            assert_eq!(prover.verify(), Ok(()), "{:?}", ship);
+            // the orientation that was not placed commits to 0
+            let mut swapped = instance.clone();
+            swapped.reverse();
+            let prover = MockProver::run(4, &circuit, vec![swapped]).unwrap();
+            assert!(prover.verify().is_err());
+        }
+    }
+
+    #[test]
+    fn footprint_omitted_ship() {
+        let circuit = FootprintCircuit {
+            ship_type: ShipType::Submarine,
+            ship: None,
+        };
+        let prover = MockProver::run(4, &circuit, vec![vec![Fp::zero(); 2]]).unwrap();
+        let failures = prover.verify().unwrap_err();
+        assert!(failures[0]
+            .to_string()
+            .contains("Horizontal commitment from footprint"));
+    }
+}
@@ -0,0 +1,349 @@
+use {
+    crate::{
+        chips::{
+            bitify::{BitifyConfig, Num2BitsChip},
+            board::{commit_board_state, BoardSalt},
+            placement::{AssignedBits, PlacementChip, PlacementConfig, ShipLen, ValidShipLen},
+            shot::decompose_board_values,
+            subset::{SubsetChip, SubsetConfig},
+        },
+        utils::{
+            binary::BinaryValue,
+            board::BOARD_SIZE,
+            instance::{InstanceLayout, PublicInput},
+        },
+    },
+    halo2_gadgets::poseidon::{primitives::Spec, Pow5Chip, Pow5Config},
+    halo2_proofs::{
+        arithmetic::FieldExt,
+        circuit::{AssignedCell, Chip, Layouter, Value},
+        plonk::{Advice, Column, ConstraintSystem, Constraints, Error, Fixed, Instance, Selector},
+        poly::Rotation,
+    },
+    std::marker::PhantomData,
+};
+
+/**
+ * Storage for a proof that a ship placement is part of a committed board
+ *
+ * @param num2bits - num2bits config for board state and H, V ship commitments
+ * @param subset - subset config constraining ship bits to be flipped in board bits
+ * @param placement - placement config constraining the ship to be a valid ship of length L
+ * @param poseidon - poseidon config for hashing the board state into the board commitment
+ * @param input - advice column storing the private board state and ship commitments
+ * @param advice - advice columns shared throughout instructions/ chips/ regions
+ * @param fixed - fixed columns for constant values
+ * @param selector - toggles the H OR V == 0 constraint on the loaded ship commitments
+ */
+#[derive(Clone, Debug)]
+pub struct PlacementMembershipConfig<F: FieldExt, const L: usize>
+where
+    ShipLen<L>: ValidShipLen,
+{
+    pub num2bits: [BitifyConfig; 3],
+    pub subset: SubsetConfig<F>,
+    pub placement: PlacementConfig<F, L>,
+    pub poseidon: Pow5Config<F, 3, 2>,
+    pub input: Column<Advice>,
+    pub advice: [Column<Advice>; 4],
+    pub instance: Column<Instance>,
+    pub fixed: [Column<Fixed>; 6],
+    pub selector: Selector,
+    _marker: PhantomData<F>,
+}
+
+/**
+ * Chip for proving a board holds a ship of length L without revealing the board or the ship
+ *    * prove the private H, V ship commitments place a single valid ship of length L
+ *    * prove at most one of the H, V commitments is nonzero (like the board chip)
+ *    * prove every cell of the ship placement is flipped in the board state
+ *    * prove public commitment is the poseidon hash of the board state (salted if the game is)
+ *
+ * @dev the ship is never exported. vertical placements are committed transposed (like the board
+ *      chip), so the V bits are checked against the board bits in transposed order. the placement
+ *      chip only sees H + V, so without the orientation constraint a ship split across H and V
+ *      would check its cells against unrelated board cells
+ */
+pub struct PlacementMembershipChip<S: Spec<F, 3, 2>, F: FieldExt, const L: usize>
+where
+    ShipLen<L>: ValidShipLen,
+{
+    config: PlacementMembershipConfig<F, L>,
+    _marker: PhantomData<S>,
+}
+
+impl<S: Spec<F, 3, 2>, F: FieldExt, const L: usize> Chip<F> for PlacementMembershipChip<S, F, L>
+where
+    ShipLen<L>: ValidShipLen,
+{
+    type Config = PlacementMembershipConfig<F, L>;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+pub trait PlacementMembershipInstructions<S: Spec<F, 3, 2>, F: FieldExt> {
+    /**
+     * Load the private advice inputs into the chip
+     *
+     * @param board_state - 100 bit number to decompose to serialized board state
+     * @param ship - [H, V] commitments of the ship placement (V is transposed)
+     * @return - reference to assigned cells of [board_state, H, V]
+     */
+    fn load_advice(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        board_state: F,
+        ship: [F; 2],
+    ) -> Result<[AssignedCell<F, F>; 3], Error>;
+
+    /**
+     * Decompose board_state, H, V into 100 bits each
+     * @dev order in arrays: [board_state, H, V]
+     *
+     * @param num - assignments to board state/ ship commitment values
+     * @param bits - unassigned binary decomposition of assigned values
+     * @return - assignments to decomposed bits ([board_state, H, V])
+     */
+    fn decompose(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        num: [AssignedCell<F, F>; 3],
+        bits: [[F; BOARD_SIZE]; 3],
+    ) -> Result<[AssignedBits<F>; 3], Error>;
+
+    /**
+     * Commit to the private board state (see chips::board::commit_board_state)
+     *
+     * @param preimage - the private board state
+     * @param salt - per-game salt to commit to the board under, or None for the unsalted digest
+     * @return - assigned cell storing the commitment to the board state
+     */
+    fn hash_board(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        preimage: AssignedCell<F, F>,
+        salt: Option<BoardSalt<F>>,
+    ) -> Result<AssignedCell<F, F>, Error>;
+}
+
+impl<S: Spec<F, 3, 2>, F: FieldExt, const L: usize> PlacementMembershipChip<S, F, L>
+where
+    ShipLen<L>: ValidShipLen,
+{
+    pub fn new(config: PlacementMembershipConfig<F, L>) -> Self {
+        PlacementMembershipChip {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    /**
+     * Configure the computation space of the circuit & return PlacementMembershipConfig
+     */
+    pub fn configure(meta: &mut ConstraintSystem<F>) -> PlacementMembershipConfig<F, L> {
+        // define advice
+        let mut advice = Vec::<Column<Advice>>::new();
+        for _ in 0..4 {
+            let col = meta.advice_column();
+            meta.enable_equality(col);
+            advice.push(col);
+        }
+        let advice: [Column<Advice>; 4] = advice.try_into().unwrap();
+        let input = meta.advice_column();
+        meta.enable_equality(input);
+
+        // define fixed
+        let mut fixed = Vec::<Column<Fixed>>::new();
+        for _ in 0..6 {
+            fixed.push(meta.fixed_column());
+        }
+        // poseidon rc_a: fixed[3..6]
+        // poseidon rc_b: fixed[0..3]
+        // fixed[0] has constant enabled
+        let fixed: [Column<Fixed>; 6] = fixed.try_into().unwrap();
+        meta.enable_constant(fixed[0]);
+
+        // define instance
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        // define num2bits chips
+        let mut num2bits = Vec::<BitifyConfig>::new();
+        for _ in 0..3 {
+            num2bits.push(Num2BitsChip::<_, BOARD_SIZE>::configure(
+                meta, advice[0], advice[1], advice[2], fixed[0],
+            ));
+        }
+        let num2bits: [BitifyConfig; 3] = num2bits.try_into().unwrap();
+
+        // define subset chip (ship bits must be flipped in board bits)
+        let subset = SubsetChip::<F>::configure(meta, advice[0], advice[1]);
+
+        // define placement chip (ship must be a single valid ship of length L)
+        let placement =
+            PlacementChip::<F, L>::configure(meta, advice[0], advice[1], advice[2], fixed[0]);
+
+        // define poseidon hash chip
+        let poseidon = Pow5Chip::<F, 3, 2>::configure::<S>(
+            meta,
+            [advice[0], advice[1], advice[2]],
+            advice[3],
+            [fixed[3], fixed[4], fixed[5]],
+            [fixed[0], fixed[1], fixed[2]],
+        );
+
+        // define orientation gate
+        // input rows: [board_state, H, V]
+        let selector = meta.selector();
+        meta.create_gate("Commitment orientation H OR V == 0 constraint", |meta| {
+            let horizontal = meta.query_advice(input, Rotation::cur());
+            let vertical = meta.query_advice(input, Rotation::next());
+            let selector = meta.query_selector(selector);
+            Constraints::with_selector(selector, [("Ship H OR V == 0", horizontal * vertical)])
+        });
+
+        PlacementMembershipConfig {
+            num2bits,
+            subset,
+            placement,
+            poseidon,
+            input,
+            advice,
+            instance,
+            fixed,
+            selector,
+            _marker: PhantomData,
+        }
+    }
+
+    /**
+     * Synthesize a proof that a committed board holds a valid ship of length L
+     *
+     * @param board - the private board state in BinaryValue form
+     * @param ship - the private [H, V] ship commitments in BinaryValue form (see Ship::witness)
+     * @param salt - per-game salt the board is committed under (game id exported to salt.row)
+     *              or None for the unsalted digest
+     * @return - Ok if synthesis executes successfully
+     */
+    pub fn synthesize(
+        &self,
+        mut layouter: impl Layouter<F>,
+        board: BinaryValue,
+        ship: [BinaryValue; 2],
+        salt: Option<BoardSalt<F>>,
+    ) -> Result<(), Error> {
+        // compute values to witness
+        let board_state = F::from_u128(board.lower_u128());
+        let commitments = ship.map(|commitment| F::from_u128(commitment.lower_u128()));
+        let placement = ship[0].zip_checked(ship[1]).map_err(|_| Error::Synthesis)?;
+        let bits = [
+            board.bitfield::<F, BOARD_SIZE>(),
+            ship[0].bitfield::<F, BOARD_SIZE>(),
+            ship[1].bitfield::<F, BOARD_SIZE>(),
+        ];
+        // load inputs as advice
+        let inputs = self.load_advice(&mut layouter, board_state, commitments)?;
+        // decompose board state and ship commitments into constrained bits
+        let [board_bits, horizontal, vertical] =
+            self.decompose(&mut layouter, inputs.clone(), bits)?;
+        // constrain the ship to be a single valid ship of length L
+        PlacementChip::<F, L>::new(self.config.placement).synthesize(
+            &mut layouter,
+            placement,
+            horizontal.clone(),
+            vertical.clone(),
+        )?;
+        // constrain ship bits to be flipped in board bits (V against the transposed board)
+        let transposed = core::array::from_fn(|i| board_bits[i % 10 * 10 + i / 10].clone());
+        let subset = SubsetChip::<F>::new(self.config.subset);
+        subset.synthesize(&mut layouter, horizontal, board_bits)?;
+        subset.synthesize(&mut layouter, vertical, transposed)?;
+        // hash board state
+        let hashed_state = self.hash_board(&mut layouter, inputs[0].clone(), salt)?;
+        // export public values
+        let layout = InstanceLayout::Membership;
+        layouter.constrain_instance(
+            hashed_state.cell(),
+            self.config.instance,
+            layout.row(PublicInput::Digest),
+        )?;
+        Ok(())
+    }
+}
+
+impl<S: Spec<F, 3, 2>, F: FieldExt, const L: usize> PlacementMembershipInstructions<S, F>
+    for PlacementMembershipChip<S, F, L>
+where
+    ShipLen<L>: ValidShipLen,
+{
+    fn load_advice(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        board_state: F,
+        ship: [F; 2],
+    ) -> Result<[AssignedCell<F, F>; 3], Error> {
+        layouter.assign_region(
+            || "load private PlacementMembershipChip advice values",
+            |mut region| {
+                let board_state = region.assign_advice(
+                    || "assign board state",
+                    self.config.input,
+                    0,
+                    || Value::known(board_state),
+                )?;
+                let horizontal = region.assign_advice(
+                    || "assign H ship commitment",
+                    self.config.input,
+                    1,
+                    || Value::known(ship[0]),
+                )?;
+                let vertical = region.assign_advice(
+                    || "assign V ship commitment",
+                    self.config.input,
+                    2,
+                    || Value::known(ship[1]),
+                )?;
+                self.config.selector.enable(&mut region, 1)?;
+                Ok([board_state, horizontal, vertical])
+            },
+        )
+    }
+
+    fn decompose(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        num: [AssignedCell<F, F>; 3],
+        bits: [[F; BOARD_SIZE]; 3],
+    ) -> Result<[AssignedBits<F>; 3], Error> {
+        decompose_board_values(
+            layouter,
+            self.config.num2bits,
+            num,
+            bits,
+            ["board_state", "H", "V"],
+        )
+    }
+
+    fn hash_board(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        preimage: AssignedCell<F, F>,
+        salt: Option<BoardSalt<F>>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        commit_board_state::<S, F>(
+            self.config.poseidon.clone(),
+            layouter,
+            self.config.advice[0],
+            self.config.instance,
+            preimage,
+            salt,
+        )
+    }
+}
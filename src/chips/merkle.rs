@@ -0,0 +1,359 @@
+use {
+    crate::{
+        chips::{
+            board::{commit_board_state, BoardSalt},
+            poseidon::{hash_node, hash_single, MERKLE_NODE_TAG},
+        },
+        utils::{
+            binary::BinaryValue,
+            instance::{InstanceLayout, PublicInput},
+        },
+    },
+    halo2_gadgets::poseidon::{
+        primitives::{ConstantLength, Hash as Poseidon, Spec},
+        Pow5Chip, Pow5Config,
+    },
+    halo2_proofs::{
+        arithmetic::FieldExt,
+        circuit::{AssignedCell, Chip, Layouter, Value},
+        plonk::{
+            Advice, Column, ConstraintSystem, Constraints, Error, Expression, Fixed, Instance,
+            Selector,
+        },
+        poly::Rotation,
+    },
+    std::marker::PhantomData,
+};
+
+/**
+ * Storage for a proof that a board commitment is a leaf of a published Merkle root
+ *
+ * @param poseidon - poseidon config for the board commitment and every tree node
+ * @param input - advice column storing the private board state
+ * @param advice - advice columns shared throughout instructions/ chips/ regions
+ * @param instance - instance column exporting the salted board commitment, game id and root
+ * @param fixed - fixed columns for constant values
+ * @param selector - toggles the node ordering constraints on each level of the path
+ */
+#[derive(Clone, Debug)]
+pub struct MerkleMembershipConfig<F: FieldExt> {
+    pub poseidon: Pow5Config<F, 3, 2>,
+    pub input: Column<Advice>,
+    pub advice: [Column<Advice>; 4],
+    pub instance: Column<Instance>,
+    pub fixed: [Column<Fixed>; 6],
+    pub selector: Selector,
+    _marker: PhantomData<F>,
+}
+
+/**
+ * Chip for proving a private board is on an allowlist of boards committed to by a Merkle root
+ *    * prove the private tree leaf is the poseidon hash of the board state
+ *    * prove hashing the leaf up a private path of DEPTH siblings yields the public root
+ *    * prove public commitment is the salted commitment to the board state for the public game id
+ *
+ * @dev the leaf is never exported: anyone holding the allowlist could hash each listed board and
+ *      match it. the salted commitment (see chips::board::commit_board_state) binds the same board
+ *      to a game without revealing which leaf it is. nodes are hashed with hash_node
+ */
+pub struct MerkleMembershipChip<S: Spec<F, 3, 2>, F: FieldExt, const DEPTH: usize> {
+    config: MerkleMembershipConfig<F>,
+    _marker: PhantomData<S>,
+}
+
+impl<S: Spec<F, 3, 2>, F: FieldExt, const DEPTH: usize> Chip<F>
+    for MerkleMembershipChip<S, F, DEPTH>
+{
+    type Config = MerkleMembershipConfig<F>;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+impl<S: Spec<F, 3, 2>, F: FieldExt, const DEPTH: usize> MerkleMembershipChip<S, F, DEPTH> {
+    pub fn new(config: MerkleMembershipConfig<F>) -> Self {
+        MerkleMembershipChip {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    /**
+     * Configure the computation space of the circuit & return MerkleMembershipConfig
+     */
+    pub fn configure(meta: &mut ConstraintSystem<F>) -> MerkleMembershipConfig<F> {
+        // define advice
+        let mut advice = Vec::<Column<Advice>>::new();
+        for _ in 0..4 {
+            let col = meta.advice_column();
+            meta.enable_equality(col);
+            advice.push(col);
+        }
+        let advice: [Column<Advice>; 4] = advice.try_into().unwrap();
+        let input = meta.advice_column();
+        meta.enable_equality(input);
+
+        // define fixed
+        let mut fixed = Vec::<Column<Fixed>>::new();
+        for _ in 0..6 {
+            fixed.push(meta.fixed_column());
+        }
+        // poseidon rc_a: fixed[3..6]
+        // poseidon rc_b: fixed[0..3]
+        // fixed[0] has constant enabled
+        let fixed: [Column<Fixed>; 6] = fixed.try_into().unwrap();
+        meta.enable_constant(fixed[0]);
+
+        // define instance
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        // define node ordering gate
+        // row 0: [node, sibling, direction], row 1: [left, right]
+        let selector = meta.selector();
+        meta.create_gate("merkle node order", |meta| {
+            let one = Expression::Constant(F::one());
+            let node = meta.query_advice(advice[0], Rotation::cur());
+            let sibling = meta.query_advice(advice[1], Rotation::cur());
+            let direction = meta.query_advice(advice[2], Rotation::cur());
+            let left = meta.query_advice(advice[0], Rotation::next());
+            let right = meta.query_advice(advice[1], Rotation::next());
+            let selector = meta.query_selector(selector);
+            Constraints::with_selector(
+                selector,
+                [
+                    (
+                        "Path direction is boolean",
+                        direction.clone() * (one - direction.clone()),
+                    ),
+                    (
+                        "Left child",
+                        left - node.clone() - direction.clone() * (sibling.clone() - node.clone()),
+                    ),
+                    (
+                        "Right child",
+                        right - sibling.clone() - direction * (node - sibling),
+                    ),
+                ],
+            )
+        });
+
+        // define poseidon hash chip
+        let poseidon = Pow5Chip::<F, 3, 2>::configure::<S>(
+            meta,
+            [advice[0], advice[1], advice[2]],
+            advice[3],
+            [fixed[3], fixed[4], fixed[5]],
+            [fixed[0], fixed[1], fixed[2]],
+        );
+
+        MerkleMembershipConfig {
+            poseidon,
+            input,
+            advice,
+            instance,
+            fixed,
+            selector,
+            _marker: PhantomData,
+        }
+    }
+
+    /**
+     * Synthesize a proof that a board's commitment is a leaf of a Merkle root
+     *
+     * @param board - the private board state in BinaryValue form
+     * @param secret - the player's private secret the board commitment is salted with
+     * @param game_id - public identifier of the game the board commitment is bound to
+     * @param index - the private position of the board's leaf in the tree
+     * @param siblings - the private sibling of the path node at each level, leaf level first
+     * @return - Ok if synthesis executes successfully
+     */
+    pub fn synthesize(
+        &self,
+        mut layouter: impl Layouter<F>,
+        board: BinaryValue,
+        secret: F,
+        game_id: F,
+        index: usize,
+        siblings: [F; DEPTH],
+    ) -> Result<(), Error> {
+        // load board state as advice
+        let board_state = layouter.assign_region(
+            || "load private MerkleMembershipChip advice values",
+            |mut region| {
+                region.assign_advice(
+                    || "assign board state",
+                    self.config.input,
+                    0,
+                    || Value::known(F::from_u128(board.lower_u128())),
+                )
+            },
+        )?;
+        // hash board state into the private leaf
+        let leaf = hash_single::<S, F>(
+            self.config.poseidon.clone(),
+            &mut layouter,
+            self.config.advice[0],
+            board_state.clone(),
+        )?;
+        // hash up the path
+        let mut node = leaf;
+        for (level, sibling) in siblings.iter().enumerate() {
+            let children = self.order_children(&mut layouter, level, node, *sibling, index)?;
+            node = hash_node::<S, F>(
+                self.config.poseidon.clone(),
+                &mut layouter.namespace(|| format!("merkle level {}", level)),
+                self.config.advice[0],
+                children,
+            )?;
+        }
+        // commit to the board under the game salt (exports the game id)
+        let layout = InstanceLayout::MerkleMembership;
+        let salt = BoardSalt {
+            secret,
+            game_id,
+            row: layout.row(PublicInput::GameId),
+        };
+        let commitment = commit_board_state::<S, F>(
+            self.config.poseidon.clone(),
+            &mut layouter,
+            self.config.advice[0],
+            self.config.instance,
+            board_state,
+            Some(salt),
+        )?;
+        // export public values
+        for (cell, input) in [
+            (commitment.cell(), PublicInput::Digest),
+            (node.cell(), PublicInput::Root),
+        ] {
+            layouter.constrain_instance(cell, self.config.instance, layout.row(input))?;
+        }
+        Ok(())
+    }
+
+    /**
+     * Order a path node and its sibling into [left, right] children of the next level
+     * @dev the direction bit of a level is bit `level` of the leaf index (1 if node is the right child)
+     *
+     * @param level - the tree level of the node (0 for the leaf)
+     * @param node - assigned path node at this level
+     * @param sibling - sibling of the node at this level
+     * @param index - position of the leaf in the tree
+     * @return - assigned [left, right] children to hash into the next path node
+     */
+    fn order_children(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        level: usize,
+        node: AssignedCell<F, F>,
+        sibling: F,
+        index: usize,
+    ) -> Result<[AssignedCell<F, F>; 2], Error> {
+        let direction = index >> level & 1 == 1;
+        layouter.assign_region(
+            || format!("order merkle level {}", level),
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+                let node =
+                    node.copy_advice(|| "path node", &mut region, self.config.advice[0], 0)?;
+                region.assign_advice(
+                    || "sibling",
+                    self.config.advice[1],
+                    0,
+                    || Value::known(sibling),
+                )?;
+                region.assign_advice(
+                    || "direction",
+                    self.config.advice[2],
+                    0,
+                    || Value::known(F::from(direction as u64)),
+                )?;
+                let mut children = [node.value().copied(), Value::known(sibling)];
+                if direction {
+                    children.reverse();
+                }
+                let left = region.assign_advice(
+                    || "left child",
+                    self.config.advice[0],
+                    1,
+                    || children[0],
+                )?;
+                let right = region.assign_advice(
+                    || "right child",
+                    self.config.advice[1],
+                    1,
+                    || children[1],
+                )?;
+                Ok([left, right])
+            },
+        )
+    }
+}
+
+/**
+ * Compute every level of a Merkle tree of board commitments off-circuit
+ * @dev the leaves are padded with zeros to 2^DEPTH. panics if there are more than 2^DEPTH leaves.
+ *      nodes are hashed like hash_node
+ *
+ * @param leaves - board commitments (poseidon hashes of board states) in leaf order
+ * @return - DEPTH + 1 levels of nodes, leaves first and the root last
+ */
+fn merkle_levels<S: Spec<F, 3, 2>, F: FieldExt, const DEPTH: usize>(leaves: &[F]) -> Vec<Vec<F>> {
+    assert!(
+        leaves.len() <= 1 << DEPTH,
+        "{} leaves do not fit in a tree of depth {}",
+        leaves.len(),
+        DEPTH
+    );
+    let mut level = leaves.to_vec();
+    level.resize(1 << DEPTH, F::zero());
+    let mut levels = vec![level];
+    for _ in 0..DEPTH {
+        let next = levels
+            .last()
+            .unwrap()
+            .chunks(2)
+            .map(|pair| {
+                Poseidon::<F, S, ConstantLength<3>, 3, 2>::init().hash([
+                    pair[0],
+                    pair[1],
+                    F::from(MERKLE_NODE_TAG),
+                ])
+            })
+            .collect();
+        levels.push(next);
+    }
+    levels
+}
+
+/**
+ * Compute the Merkle root of an allowlist of board commitments off-circuit
+ *
+ * @param leaves - board commitments in leaf order (padded with zeros to 2^DEPTH)
+ * @return - the root published for the allowlist
+ */
+pub fn merkle_root<S: Spec<F, 3, 2>, F: FieldExt, const DEPTH: usize>(leaves: &[F]) -> F {
+    merkle_levels::<S, F, DEPTH>(leaves)[DEPTH][0]
+}
+
+/**
+ * Compute the siblings proving a leaf is in the tree off-circuit
+ *
+ * @param leaves - board commitments in leaf order (padded with zeros to 2^DEPTH)
+ * @param index - position of the leaf to prove
+ * @return - the sibling of the path node at each level, leaf level first
+ */
+pub fn merkle_path<S: Spec<F, 3, 2>, F: FieldExt, const DEPTH: usize>(
+    leaves: &[F],
+    index: usize,
+) -> [F; DEPTH] {
+    let levels = merkle_levels::<S, F, DEPTH>(leaves);
+    core::array::from_fn(|level| levels[level][(index >> level) ^ 1])
+}
@@ -2,4 +2,14 @@ pub mod bitify;
 pub mod placement;
 pub mod transpose;
 pub mod board;
-pub mod shot;
\ No newline at end of file
+pub mod shot;
+pub mod subset;
+pub mod membership;
+pub mod merkle;
+pub mod poseidon;
+pub mod win;
+pub mod no_touch;
+pub mod footprint;
+pub mod one_hot;
+pub mod reveal;
+pub mod tally;
\ No newline at end of file
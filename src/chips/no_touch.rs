@@ -0,0 +1,344 @@
+use {
+    crate::utils::board::BOARD_SIZE,
+    halo2_proofs::{
+        arithmetic::FieldExt,
+        circuit::{AssignedCell, Chip, Layouter, Region},
+        plonk::{Advice, Column, ConstraintSystem, Constraints, Error, Expression, Selector},
+        poly::Rotation,
+    },
+    std::marker::PhantomData,
+};
+
+pub const NO_TOUCH_WIDTH: usize = 11; // board columns + 1 padding column so neighbors do not wrap
+pub const NO_TOUCH_ROWS: usize = 12 + 10 * NO_TOUCH_WIDTH + 11; // 12 padding rows above, 11 below
+
+// rotations from a cell to each of its 8 neighbors (orthogonal and diagonal) in the padded grid
+const NEIGHBORS: [i32; 8] = [-12, -11, -10, -1, 1, 10, 11, 12];
+
+// constraint names indexed by ship [carrier, battleship, cruiser, submarine, destroyer]
+const CONSTRAINT_NAMES: [&str; 5] = [
+    "Aircraft Carrier does not touch another ship",
+    "Battleship does not touch another ship",
+    "Cruiser does not touch another ship",
+    "Submarine does not touch another ship",
+    "Destroyer does not touch another ship",
+];
+
+/**
+ * Return the row of the padded grid holding a board cell
+ * @dev row-major with NO_TOUCH_WIDTH cells per board row: x = 10 and rows outside of the board
+ *      are padded with 0 so that every neighbor rotation of a board cell is in the grid
+ *
+ * @param cell - board cell index y * 10 + x
+ * @return - the row in the no touch region the cell is assigned to
+ */
+pub fn grid_row(cell: usize) -> usize {
+    12 + cell / 10 * NO_TOUCH_WIDTH + cell % 10
+}
+
+/**
+ * Optional rule forbidding ships from touching, even diagonally
+ * @dev each ship's footprint (H + V bits) is dilated to its 8 neighbors by rotation queries and
+ *      checked against the footprints of the other 4 ships
+ *      requires a constant-enabled fixed column in the constraint system to pad the grid
+ *
+ * @param footprint_bits - H, V bit columns per ship (same order as the ship commitments)
+ * @param selector - toggles the no touch constraint on rows holding a board cell
+ */
+#[derive(Clone, Copy, Debug)]
+pub struct NoTouchConfig<F: FieldExt> {
+    pub footprint_bits: [Column<Advice>; 10],
+    pub selector: Selector,
+    _marker: PhantomData<F>,
+}
+
+pub struct NoTouchChip<F: FieldExt> {
+    config: NoTouchConfig<F>,
+}
+
+impl<F: FieldExt> Chip<F> for NoTouchChip<F> {
+    type Config = NoTouchConfig<F>;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+impl<F: FieldExt> NoTouchChip<F> {
+    pub fn new(config: NoTouchConfig<F>) -> Self {
+        NoTouchChip { config }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        footprint_bits: [Column<Advice>; 10],
+    ) -> NoTouchConfig<F> {
+        // define selectors
+        let selector = meta.selector();
+
+        meta.create_gate("no touch constraint", |meta| {
+            // footprint of ship i at a given rotation = H[i] + V[i]
+            let mut footprint = |rotation: i32| {
+                (0..5)
+                    .map(|i| {
+                        meta.query_advice(footprint_bits[i * 2], Rotation(rotation))
+                            + meta.query_advice(footprint_bits[i * 2 + 1], Rotation(rotation))
+                    })
+                    .collect::<Vec<Expression<F>>>()
+            };
+            let current = footprint(0);
+            let neighbors = NEIGHBORS.map(&mut footprint);
+            // footprint[i] * sum(other ship bits in neighbors) == 0
+            // sum of at most 32 bits cannot wrap in the field, so it is 0 only if no bit is set
+            let constraints = (0..5).map(|i| {
+                let touching = neighbors
+                    .iter()
+                    .flat_map(|ships| {
+                        ships
+                            .iter()
+                            .enumerate()
+                            .filter(move |(j, _)| *j != i)
+                            .map(|(_, ship)| ship.clone())
+                    })
+                    .fold(Expression::Constant(F::zero()), |sum, bit| sum + bit);
+                (CONSTRAINT_NAMES[i], current[i].clone() * touching)
+            });
+            let selector = meta.query_selector(selector);
+            Constraints::with_selector(selector, constraints.collect::<Vec<_>>())
+        });
+
+        NoTouchConfig {
+            footprint_bits,
+            selector,
+            _marker: PhantomData,
+        }
+    }
+
+    /**
+     * Synthesize a check that no two ships occupy neighboring cells
+     *
+     * @param placements - reference to bits2num chips' decomposed ship commitments
+     * @return - Ok if synthesized without error (constraint failures surface at verification)
+     */
+    pub fn synthesize(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        placements: [[AssignedCell<F, F>; BOARD_SIZE]; 10],
+    ) -> Result<(), Error> {
+        layouter.assign_region(
+            || "no touch ship footprints",
+            |mut region: Region<F>| {
+                // pad every grid row that does not hold a board cell with 0
+                let cells = (0..BOARD_SIZE).map(grid_row).collect::<Vec<usize>>();
+                for row in (0..NO_TOUCH_ROWS).filter(|row| !cells.contains(row)) {
+                    for col in 0..10 {
+                        region.assign_advice_from_constant(
+                            || format!("pad no touch grid {} row {}", col, row),
+                            self.config.footprint_bits[col],
+                            row,
+                            F::zero(),
+                        )?;
+                    }
+                }
+                // permute from bits2num chips (vertical placements are transposed)
+                for (col, placement) in placements.iter().enumerate() {
+                    for (cell, row) in cells.iter().enumerate() {
+                        let index = if col % 2 == 1 {
+                            cell % 10 * 10 + cell / 10
+                        } else {
+                            cell
+                        };
+                        placement[index].copy_advice(
+                            || format!("permute ship {} footprint bit {}", col / 2, cell),
+                            &mut region,
+                            self.config.footprint_bits[col],
+                            *row,
+                        )?;
+                    }
+                }
+                for row in cells {
+                    self.config.selector.enable(&mut region, row)?;
+                }
+                Ok(())
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        crate::utils::{
+            binary::BinaryValue, board::Board, deck::Deck, ship::DEFAULT_WITNESS_OPTIONS,
+        },
+        halo2_proofs::{
+            circuit::{SimpleFloorPlanner, Value},
+            dev::{metadata, FailureLocation, MockProver, VerifyFailure},
+            pasta::Fp,
+            plonk::Circuit,
+        },
+    };
+
+    #[derive(Clone, Copy, Debug)]
+    struct TestNoTouchConfig {
+        advice: [Column<Advice>; 10],
+        no_touch: NoTouchConfig<Fp>,
+    }
+
+    struct NoTouchCircuit {
+        ship_commitments: [BinaryValue; 10],
+    }
+
+    impl Circuit<Fp> for NoTouchCircuit {
+        type Config = TestNoTouchConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            NoTouchCircuit {
+                ship_commitments: self.ship_commitments,
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> TestNoTouchConfig {
+            let advice = [(); 10].map(|_| {
+                let col = meta.advice_column();
+                meta.enable_equality(col);
+                col
+            });
+            let fixed = meta.fixed_column();
+            meta.enable_constant(fixed);
+            TestNoTouchConfig {
+                advice,
+                no_touch: NoTouchChip::configure(meta, advice),
+            }
+        }
+
+        fn synthesize(
+            &self,
+            config: TestNoTouchConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let placements = layouter.assign_region(
+                || "load ship placements",
+                |mut region| {
+                    let mut placements = Vec::<[AssignedCell<Fp, Fp>; BOARD_SIZE]>::new();
+                    for (col, commitment) in self.ship_commitments.iter().enumerate() {
+                        let bits = commitment.bitfield::<Fp, BOARD_SIZE>();
+                        let mut cells = Vec::<AssignedCell<Fp, Fp>>::new();
+                        for (row, bit) in bits.iter().enumerate() {
+                            cells.push(region.assign_advice(
+                                || format!("ship commitment {} bit {}", col, row),
+                                config.advice[col],
+                                row,
+                                || Value::known(*bit),
+                            )?);
+                        }
+                        placements.push(cells.try_into().unwrap());
+                    }
+                    Ok(placements.try_into().unwrap())
+                },
+            )?;
+            NoTouchChip::new(config.no_touch).synthesize(&mut layouter, placements)
+        }
+    }
+
+    /**
+     * Mock prove the no touch rule over a deck
+     *
+     * @param deck - the fleet to check
+     * @return - the result of verifying the no touch circuit
+     */
+    fn prove_no_touch(deck: &Deck) -> Result<(), Vec<VerifyFailure>> {
+        let circuit = NoTouchCircuit {
+            ship_commitments: Board::from(deck).witness(DEFAULT_WITNESS_OPTIONS),
+        };
+        MockProver::run(9, &circuit, vec![]).unwrap().verify()
+    }
+
+    #[test]
+    fn adjacent_cells_match_chip() {
+        // the chip rejects a deck exactly when a ship covers a cell adjacent to another ship
+        for deck in [
+            Deck::default(),
+            Deck::from([
+                Some((3, 3, true)),
+                Some((5, 4, false)),
+                Some((0, 1, false)),
+                Some((0, 5, true)),
+                Some((6, 1, false)),
+            ]),
+        ] {
+            let ships = deck.ships();
+            let touching = ships.iter().any(|ship| {
+                let adjacent = ship.adjacent_cells();
+                ships
+                    .iter()
+                    .filter(|other| other.ship_type != ship.ship_type)
+                    .any(|other| other.cells().iter().any(|cell| adjacent.contains(cell)))
+            });
+            assert_eq!(prove_no_touch(&deck).is_ok(), !touching);
+        }
+    }
+
+    #[test]
+    fn valid_spaced_fleet() {
+        // board pattern 1: no two ships share an edge or corner
+        let deck = Deck::from([
+            Some((3, 3, true)),
+            Some((5, 4, false)),
+            Some((0, 1, false)),
+            Some((0, 5, true)),
+            Some((6, 1, false)),
+        ]);
+        assert_eq!(prove_no_touch(&deck), Ok(()));
+    }
+
+    #[test]
+    fn invalid_touching_fleet() {
+        // destroyer moved to (5, 3) lies directly above the battleship at (5, 4) without overlapping
+        let deck = Deck::from([
+            Some((3, 3, true)),
+            Some((5, 4, false)),
+            Some((0, 1, false)),
+            Some((0, 5, true)),
+            Some((5, 3, false)),
+        ]);
+        let failures = prove_no_touch(&deck)
+            .unwrap_err()
+            .into_iter()
+            .map(|failure| match failure {
+                VerifyFailure::ConstraintNotSatisfied {
+                    constraint,
+                    location,
+                    ..
+                } => (constraint, location),
+                other => panic!("unexpected failure {:?}", other),
+            })
+            .collect::<Vec<(metadata::Constraint, FailureLocation)>>();
+        // battleship cells (5, 4), (6, 4), (7, 4) and destroyer cells (5, 3), (6, 3) touch
+        let expected = [(4, 35), (4, 36), (1, 45), (1, 46), (1, 47)]
+            .into_iter()
+            .map(|(ship, cell)| {
+                (
+                    (
+                        (0, "no touch constraint").into(),
+                        ship,
+                        CONSTRAINT_NAMES[ship],
+                    )
+                        .into(),
+                    FailureLocation::InRegion {
+                        region: (1, "no touch ship footprints").into(),
+                        offset: grid_row(cell),
+                    },
+                )
+            })
+            .collect::<Vec<(metadata::Constraint, FailureLocation)>>();
+        assert_eq!(failures, expected);
+    }
+}
@@ -0,0 +1,385 @@
+use {
+    halo2_proofs::{
+        arithmetic::FieldExt,
+        circuit::{AssignedCell, Chip, Layouter, Region, Value},
+        plonk::{Advice, Column, ConstraintSystem, Constraints, Error, Expression, Selector},
+        poly::Rotation,
+    },
+    std::marker::PhantomData,
+};
+
+/**
+ * Compute the one-hot indicator vector of an index
+ *
+ * @param index - the position to flip (no bit is flipped if index >= N)
+ * @return - N field elements where only element [index] is 1
+ */
+pub fn one_hot<F: FieldExt, const N: usize>(index: F) -> [F; N] {
+    let mut indicator = [F::zero(); N];
+    for (i, bit) in indicator.iter_mut().enumerate() {
+        *bit = F::from(F::from(i as u64) == index);
+    }
+    indicator
+}
+
+/**
+ * Storage required to select one of N values by its index
+ * @dev a one-hot indicator vector is witnessed alongside the values and constrained by running sums:
+ *      - indicator_sum counts flipped indicator bits and must end at 1
+ *      - index_sum adds 1 on every row before the flipped bit and must end at the index
+ *      - selected_sum accumulates indicator * value and ends at the selected value
+ *      requires a constant-enabled fixed column in the constraint system to pad the region
+ *
+ * @param value - column storing the N values to select from
+ * @param indicator - column storing the one-hot indicator vector
+ * @param indicator_sum - running sum of indicator bits
+ * @param index_sum - running count of rows preceding the flipped indicator bit
+ * @param selected_sum - running sum of indicator * value
+ * @param selector - toggles the one hot constraints on a row
+ */
+#[derive(Clone, Copy, Debug)]
+pub struct OneHotSelectConfig<F: FieldExt, const N: usize> {
+    pub value: Column<Advice>,
+    pub indicator: Column<Advice>,
+    pub indicator_sum: Column<Advice>,
+    pub index_sum: Column<Advice>,
+    pub selected_sum: Column<Advice>,
+    pub selector: Selector,
+    _marker: PhantomData<F>,
+}
+
+pub struct OneHotSelectChip<F: FieldExt, const N: usize> {
+    config: OneHotSelectConfig<F, N>,
+}
+
+impl<F: FieldExt, const N: usize> Chip<F> for OneHotSelectChip<F, N> {
+    type Config = OneHotSelectConfig<F, N>;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+impl<F: FieldExt, const N: usize> OneHotSelectChip<F, N> {
+    pub fn new(config: OneHotSelectConfig<F, N>) -> Self {
+        OneHotSelectChip { config }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 5],
+    ) -> OneHotSelectConfig<F, N> {
+        let [value, indicator, indicator_sum, index_sum, selected_sum] = advice;
+        for column in advice {
+            meta.enable_equality(column);
+        }
+
+        // define selectors
+        let selector = meta.selector();
+
+        meta.create_gate("one hot select", |meta| {
+            let one = Expression::Constant(F::one());
+            let value = meta.query_advice(value, Rotation::cur());
+            let indicator = meta.query_advice(indicator, Rotation::cur());
+            let indicator_sum_prev = meta.query_advice(indicator_sum, Rotation::prev());
+            let indicator_sum = meta.query_advice(indicator_sum, Rotation::cur());
+            let index_sum_prev = meta.query_advice(index_sum, Rotation::prev());
+            let index_sum = meta.query_advice(index_sum, Rotation::cur());
+            let selected_sum_prev = meta.query_advice(selected_sum, Rotation::prev());
+            let selected_sum = meta.query_advice(selected_sum, Rotation::cur());
+            let selector = meta.query_selector(selector);
+            Constraints::with_selector(
+                selector,
+                [
+                    (
+                        "Indicator is boolean",
+                        indicator.clone() * (one.clone() - indicator.clone()),
+                    ),
+                    (
+                        "Running sum of indicator bits",
+                        indicator_sum_prev + indicator.clone() - indicator_sum.clone(),
+                    ),
+                    (
+                        "Running count of rows before flipped indicator",
+                        index_sum_prev + one - indicator_sum - index_sum,
+                    ),
+                    (
+                        "Running sum of selected value",
+                        selected_sum_prev + indicator * value - selected_sum,
+                    ),
+                ],
+            )
+        });
+
+        OneHotSelectConfig {
+            value,
+            indicator,
+            indicator_sum,
+            index_sum,
+            selected_sum,
+            selector,
+            _marker: PhantomData,
+        }
+    }
+
+    /**
+     * Constrain the output to equal values[index]
+     * @dev an index >= N cannot satisfy the constraints
+     *
+     * @param index - reference to the assigned position to select
+     * @param values - references to the assigned values to select from
+     * @return - reference to the selected value
+     */
+    pub fn synthesize(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        index: AssignedCell<F, F>,
+        values: [AssignedCell<F, F>; N],
+    ) -> Result<AssignedCell<F, F>, Error> {
+        let indicator = index.value().map(|index| one_hot::<F, N>(*index));
+        self.synthesize_with_indicator(layouter, index, values, indicator)
+    }
+
+    /**
+     * Constrain the output to equal values[index] given an explicit indicator vector
+     * @dev exposed so malformed indicators can be shown to fail. the constraints only hold if
+     *      indicator is one-hot at index
+     *
+     * @param index - reference to the assigned position to select
+     * @param values - references to the assigned values to select from
+     * @param indicator - witnessed indicator vector
+     * @return - reference to the selected value
+     */
+    pub fn synthesize_with_indicator(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        index: AssignedCell<F, F>,
+        values: [AssignedCell<F, F>; N],
+        indicator: Value<[F; N]>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        layouter.assign_region(
+            || "one hot select",
+            |mut region: Region<F>| {
+                // padding row
+                let mut indicator_sum = region.assign_advice_from_constant(
+                    || "pad indicator sum",
+                    self.config.indicator_sum,
+                    0,
+                    F::zero(),
+                )?;
+                let mut index_sum = region.assign_advice_from_constant(
+                    || "pad index sum",
+                    self.config.index_sum,
+                    0,
+                    F::zero(),
+                )?;
+                let mut selected_sum = region.assign_advice_from_constant(
+                    || "pad selected sum",
+                    self.config.selected_sum,
+                    0,
+                    F::zero(),
+                )?;
+                // running sums (offset by 1 for padding row)
+                for (i, value) in values.iter().enumerate() {
+                    let row = i + 1;
+                    value.copy_advice(
+                        || format!("copy value {}", i),
+                        &mut region,
+                        self.config.value,
+                        row,
+                    )?;
+                    let bit = indicator.map(|indicator| indicator[i]);
+                    region.assign_advice(
+                        || format!("indicator bit {}", i),
+                        self.config.indicator,
+                        row,
+                        || bit,
+                    )?;
+                    indicator_sum = region.assign_advice(
+                        || format!("indicator sum {}", i),
+                        self.config.indicator_sum,
+                        row,
+                        || indicator_sum.value().copied() + bit,
+                    )?;
+                    index_sum = region.assign_advice(
+                        || format!("index sum {}", i),
+                        self.config.index_sum,
+                        row,
+                        || {
+                            index_sum.value().copied() + Value::known(F::one())
+                                - indicator_sum.value()
+                        },
+                    )?;
+                    selected_sum = region.assign_advice(
+                        || format!("selected sum {}", i),
+                        self.config.selected_sum,
+                        row,
+                        || selected_sum.value().copied() + bit * value.value(),
+                    )?;
+                    self.config.selector.enable(&mut region, row)?;
+                }
+                // exactly one indicator bit is flipped, at the given index
+                region.constrain_constant(indicator_sum.cell(), F::one())?;
+                region.constrain_equal(index_sum.cell(), index.cell())?;
+                Ok(selected_sum)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        crate::utils::{
+            board::{Board, BOARD_SIZE},
+            deck::Deck,
+            ship::DEFAULT_WITNESS_OPTIONS,
+        },
+        halo2_proofs::{
+            circuit::SimpleFloorPlanner,
+            dev::MockProver,
+            pasta::Fp,
+            plonk::{Circuit, Instance},
+        },
+    };
+
+    #[derive(Clone, Copy, Debug)]
+    struct TestOneHotConfig {
+        input: Column<Advice>,
+        instance: Column<Instance>,
+        one_hot: OneHotSelectConfig<Fp, BOARD_SIZE>,
+    }
+
+    struct OneHotCircuit {
+        values: [Fp; BOARD_SIZE],
+        index: Fp,
+        indicator: [Fp; BOARD_SIZE],
+    }
+
+    impl Circuit<Fp> for OneHotCircuit {
+        type Config = TestOneHotConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            OneHotCircuit {
+                values: self.values,
+                index: self.index,
+                indicator: self.indicator,
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> TestOneHotConfig {
+            let advice = [(); 5].map(|_| meta.advice_column());
+            let input = meta.advice_column();
+            meta.enable_equality(input);
+            let instance = meta.instance_column();
+            meta.enable_equality(instance);
+            let fixed = meta.fixed_column();
+            meta.enable_constant(fixed);
+            TestOneHotConfig {
+                input,
+                instance,
+                one_hot: OneHotSelectChip::configure(meta, advice),
+            }
+        }
+
+        fn synthesize(
+            &self,
+            config: TestOneHotConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let (index, values) = layouter.assign_region(
+                || "load values",
+                |mut region| {
+                    let index = region.assign_advice(
+                        || "index",
+                        config.input,
+                        0,
+                        || Value::known(self.index),
+                    )?;
+                    let mut values = Vec::<AssignedCell<Fp, Fp>>::new();
+                    for (i, value) in self.values.iter().enumerate() {
+                        values.push(region.assign_advice(
+                            || format!("value {}", i),
+                            config.input,
+                            i + 1,
+                            || Value::known(*value),
+                        )?);
+                    }
+                    Ok((index, values.try_into().unwrap()))
+                },
+            )?;
+            let selected = OneHotSelectChip::new(config.one_hot).synthesize_with_indicator(
+                &mut layouter,
+                index,
+                values,
+                Value::known(self.indicator),
+            )?;
+            layouter.constrain_instance(selected.cell(), config.instance, 0)
+        }
+    }
+
+    /**
+     * Mock prove the selection of a value from the default board's bits
+     *
+     * @param index - position asserted to be selected
+     * @param indicator - witnessed indicator vector
+     * @param selected - value exported as the selection
+     * @return - the result of verifying the one hot circuit
+     */
+    fn prove_select(index: usize, indicator: [Fp; BOARD_SIZE], selected: Fp) -> bool {
+        let values = Board::from(&Deck::default())
+            .state(DEFAULT_WITNESS_OPTIONS)
+            .bitfield::<Fp, BOARD_SIZE>();
+        let circuit = OneHotCircuit {
+            values,
+            index: Fp::from(index as u64),
+            indicator,
+        };
+        MockProver::run(8, &circuit, vec![vec![selected]])
+            .unwrap()
+            .verify()
+            .is_ok()
+    }
+
+    #[test]
+    fn select_every_cell() {
+        let values = Board::from(&Deck::default())
+            .state(DEFAULT_WITNESS_OPTIONS)
+            .bitfield::<Fp, BOARD_SIZE>();
+        for (index, value) in values.iter().enumerate() {
+            let indicator = one_hot::<Fp, BOARD_SIZE>(Fp::from(index as u64));
+            assert!(prove_select(index, indicator, *value), "cell {}", index);
+            // the selection cannot be swapped for the other bit value
+            assert!(!prove_select(index, indicator, Fp::one() - value));
+        }
+    }
+
+    #[test]
+    fn malformed_indicator() {
+        // cell 0 is occupied by the carrier, cell 5 is empty
+        let mut two_hot = one_hot::<Fp, BOARD_SIZE>(Fp::from(5));
+        two_hot[0] = Fp::one();
+        let mut non_boolean = [Fp::zero(); BOARD_SIZE];
+        non_boolean[0] = Fp::from(2);
+        non_boolean[5] = -Fp::one();
+        let cases = [
+            // no bit flipped
+            ([Fp::zero(); BOARD_SIZE], Fp::zero()),
+            // flipped bit does not match the index
+            (one_hot::<Fp, BOARD_SIZE>(Fp::zero()), Fp::one()),
+            (two_hot, Fp::one()),
+            // sums to 1 without being one-hot
+            (non_boolean, Fp::from(2)),
+        ];
+        for (indicator, selected) in cases {
+            assert!(!prove_select(5, indicator, selected));
+        }
+    }
+}
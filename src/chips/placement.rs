@@ -1,14 +1,23 @@
 use {
-    crate::utils::{binary::BinaryValue, board::BOARD_SIZE},
+    crate::{
+        chips::bitify::{BitifyConfig, Num2BitsChip},
+        utils::{
+            binary::{pow2_table, BinaryValue},
+            board::BOARD_SIZE,
+            region::copy_cells,
+            ship::{Ship, WitnessOption},
+        },
+    },
     halo2_proofs::{
-        arithmetic::{lagrange_interpolate, FieldExt},
+        arithmetic::{eval_polynomial, lagrange_interpolate, FieldExt},
         circuit::{AssignedCell, Chip, Layouter, Region, Value},
         plonk::{
-            Advice, Column, ConstraintSystem, Constraints, Error, Expression, Fixed, Selector,
+            Advice, Column, ConstraintSystem, Constraints, Error, Expression, Fixed, Instance,
+            Selector,
         },
         poly::Rotation,
     },
-    std::marker::PhantomData,
+    std::{fmt, marker::PhantomData},
 };
 
 /**
@@ -20,7 +29,13 @@ use {
  *     * row 0 is padding for gate constraints
  *     * rows 1-100 perform running sum operations on bits. last row constrains output
  * @param selectors - array of 5 selectors that toggle constraints in chip
- *     * [placement_orientation, bit_sum, bit_adjacency, adjacency_permute, constrain_trace]
+ *     * [s_input, s_sum_bits, s_adjacency, s_permute, s_constrain] (see Display for gate layout)
+ *     * every selector must be queried by a gate in configure() (checked by all_selectors_gated test)
+ * @param s_nonzero - optional selector rejecting a zero H + V commitment in the load region
+ *     * only configured by configure_nonzero() for standalone placement proofs
+ * @param audit - optional (instance column, row) exporting the final full window sum and the number
+ *        of windows checked to rows [row, row + 1]
+ *     * only configured by configure_audit() for verifiers auditing the window check
  * @param ship - Object storing/ exporting ship positioning
  */
 #[derive(Clone, Copy, Debug)]
@@ -34,10 +49,137 @@ pub struct PlacementConfig<F: FieldExt, const S: usize> {
     pub s_adjacency: Selector, // count bits in bit window and increment prev window sum if full
     pub s_permute: Selector,   // copy previous window sum to current window sum
     pub s_constrain: Selector, // constrain full_window_sum to be 1 and bit_sum to be S
+    pub s_nonzero: Option<Selector>, // constrain H + V commitment to be nonzero when loaded
+    pub audit: Option<WindowAuditConfig>, // count window checks and export them with the window sum
     _marker: PhantomData<F>,
 }
 
-pub struct PlacementChip<F: FieldExt, const S: usize> {
+/**
+ * Storage for exporting how many bit windows a placement trace checked
+ *
+ * @param windows_checked - advice column storing a running count of the window check rows
+ * @param instance - the instance column to export to
+ * @param row - instance row of the final full window sum (windows checked follows at row + 1)
+ */
+#[derive(Clone, Copy, Debug)]
+pub struct WindowAuditConfig {
+    pub windows_checked: Column<Advice>,
+    pub instance: Column<Instance>,
+    pub row: usize,
+}
+
+/**
+ * Render the gate layout of the chip as a table of selector -> gate -> advice cells queried
+ * @dev written from the gates defined in configure(), not introspected from the constraint system
+ */
+impl<F: FieldExt, const S: usize> fmt::Display for PlacementConfig<F, S>
+where
+    ShipLen<S>: ValidShipLen,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let window = format!("bits[cur..cur+{}]", S - 1);
+        let mut rows = vec![
+            (
+                "s_input",
+                "sum inputted H, V bits",
+                "bits[cur], bit_sum[cur], full_window_sum[cur]".to_string(),
+            ),
+            (
+                "s_sum_bits",
+                "placement bit count",
+                "bits[cur], bit_sum[prev], bit_sum[cur]".to_string(),
+            ),
+            (
+                "s_adjacency",
+                "adjacency bit count",
+                format!("{}, full_window_sum[prev], full_window_sum[cur]", window),
+            ),
+            (
+                "s_permute",
+                "permute adjaceny bit count",
+                "full_window_sum[prev], full_window_sum[cur]".to_string(),
+            ),
+            (
+                "s_constrain",
+                "running sum constraints",
+                "bits[cur], bit_sum[cur], full_window_sum[cur]".to_string(),
+            ),
+        ];
+        if self.s_nonzero.is_some() {
+            rows.push((
+                "s_nonzero",
+                "nonzero placement commitment",
+                "bits[cur], bit_sum[cur], full_window_sum[cur]".to_string(),
+            ));
+        }
+        if self.audit.is_some() {
+            let queries = "windows_checked[prev], windows_checked[cur]";
+            rows.push(("s_adjacency", "window audit count", queries.to_string()));
+            rows.push(("s_permute", "window audit carry", queries.to_string()));
+        }
+        // the gate column fits the longest gate name of the configured gates
+        let width = rows
+            .iter()
+            .map(|(_, gate, _)| gate.len())
+            .max()
+            .unwrap_or(0);
+        writeln!(f, "PlacementConfig (S = {})", S)?;
+        writeln!(
+            f,
+            "{:<12} | {:<width$} | advice queried",
+            "selector", "gate"
+        )?;
+        for (selector, gate, queries) in rows {
+            writeln!(f, "{:<12} | {:<width$} | {}", selector, gate, queries)?;
+        }
+        if let Some(audit) = self.audit {
+            writeln!(
+                f,
+                "window audit exported: full_window_sum -> instance[{}], windows_checked -> instance[{}]",
+                audit.row,
+                audit.row + 1
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/**
+ * Ship length S carried as a type so it can be bounded by ValidShipLen
+ */
+pub struct ShipLen<const S: usize>;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/**
+ * Ship lengths supported by the placement chip/ gadget
+ * @dev sealed: implemented only for S in 1..=10 (a ship must fit in one row/ column of the board).
+ *      the standard fleet uses 5, 4, 3, 3, 2. instantiating with any other S fails to compile
+ * @dev S = 0 is rejected explicitly: the window incrementor would interpolate over [0] alone (every
+ *      window counts as full) and an empty commitment has no inverse for the nonzero witness, so a
+ *      zero-length ship could otherwise synthesize. every S-generic item in this module (chip,
+ *      gadget, trace/ simulation helpers, PlacementState assignment) carries this bound so no
+ *      entry point accepts it
+ */
+pub trait ValidShipLen: sealed::Sealed {}
+
+macro_rules! valid_ship_len {
+    ($($len:literal),*) => {
+        $(
+            impl sealed::Sealed for ShipLen<$len> {}
+            impl ValidShipLen for ShipLen<$len> {}
+        )*
+    };
+}
+
+valid_ship_len!(1, 2, 3, 4, 5, 6, 7, 8, 9, 10);
+
+pub struct PlacementChip<F: FieldExt, const S: usize>
+where
+    ShipLen<S>: ValidShipLen,
+{
     config: PlacementConfig<F, S>,
 }
 
@@ -78,15 +220,20 @@ pub trait PlacementInstructions<F: FieldExt, const S: usize> {
      * Constrain the witnessed running sum values for placement to be valid under game logic
      *
      * @param state - reference to assigned bit count and full bit window count cells
+     * @param orientation - 0 if the ship was placed horizontally, 1 if placed vertically
      */
     fn assign_constraint(
         &self,
         layouter: &mut impl Layouter<F>,
         state: PlacementState<F>,
+        orientation: Value<F>,
     ) -> Result<(), Error>;
 }
 
-impl<F: FieldExt, const S: usize> Chip<F> for PlacementChip<F, S> {
+impl<F: FieldExt, const S: usize> Chip<F> for PlacementChip<F, S>
+where
+    ShipLen<S>: ValidShipLen,
+{
     type Config = PlacementConfig<F, S>;
     type Loaded = ();
 
@@ -99,7 +246,10 @@ impl<F: FieldExt, const S: usize> Chip<F> for PlacementChip<F, S> {
     }
 }
 
-impl<F: FieldExt, const S: usize> PlacementChip<F, S> {
+impl<F: FieldExt, const S: usize> PlacementChip<F, S>
+where
+    ShipLen<S>: ValidShipLen,
+{
     pub fn new(config: PlacementConfig<F, S>) -> Self {
         PlacementChip { config }
     }
@@ -145,6 +295,9 @@ impl<F: FieldExt, const S: usize> PlacementChip<F, S> {
         });
 
         // selector[2] gate: full bit window running sum
+        // @dev interpolating the incrementor over [0, S] is degree S, so the gate is degree S + 1
+        //      (6 for a carrier). this raises the extended evaluation domain, not the row count
+        let incrementor = window_incrementor::<F, S>();
         meta.create_gate("adjacency bit count", |meta| {
             // count the number of bits in this gate and the proceeding `S` rows in bit column (A^2)
             let mut bit_count = meta.query_advice(bits, Rotation::cur());
@@ -185,20 +338,11 @@ impl<F: FieldExt, const S: usize> PlacementChip<F, S> {
              * @return - a boolean expression showing whether or not X = S (can be added as incrementor)
              */
             let interpolate_incrementor = |x: Expression<F>| -> Expression<F> {
-                // generate lagrange interpolation inputs
-                // if ship length is 4, then [0->0, 1->0, 2->0, 3->0, 4->1]
-                let mut points = Vec::<F>::new();
-                let mut evals = Vec::<F>::new();
-                for i in 0..=S {
-                    points.push(F::from(i as u64));
-                    evals.push(if i == S { F::one() } else { F::zero() });
-                }
-                let interpolated = lagrange_interpolate(&points, &evals);
                 let mut interpolated_value = Expression::Constant(F::zero());
-                for i in 0..interpolated.len() {
+                for (i, coefficient) in incrementor.iter().enumerate() {
                     let x_pow = exp_pow(x.clone(), i);
                     interpolated_value =
-                        interpolated_value.clone() + Expression::Constant(interpolated[i]) * x_pow;
+                        interpolated_value.clone() + Expression::Constant(*coefficient) * x_pow;
                 }
                 interpolated_value
             };
@@ -233,18 +377,34 @@ impl<F: FieldExt, const S: usize> PlacementChip<F, S> {
         meta.create_gate("running sum constraints", |meta| {
             // confirm the final output of the placement computation does not violate ship placement rules
             // @dev constraining of sum(h,v) to bits2num output occurs in synthesis
+            // @dev orientation (bits column) only selects which named window constraint reports a
+            //      failure; since it is boolean exactly one of the two always enforces the window count
             let ship_len = Expression::Constant(F::from(S as u64));
             let one = Expression::Constant(F::one());
+            let orientation = meta.query_advice(bits, Rotation::cur());
             let bit_count = meta.query_advice(bit_sum, Rotation::cur());
             let full_window_count = meta.query_advice(full_window_sum, Rotation::cur());
             // - constrain bit count to be equal to S
-            // - constrain exactly one full bit window
+            // - constrain orientation to be 0 (horizontal) or 1 (vertical)
+            // - constrain exactly one full bit window in the placed orientation
             let selector = meta.query_selector(s_constrain);
             Constraints::with_selector(
                 selector,
                 [
                     ("Placed ship of correct length", bit_count - ship_len),
-                    ("One full bit window", full_window_count - one),
+                    (
+                        "Orientation is boolean",
+                        orientation.clone() * (one.clone() - orientation.clone()),
+                    ),
+                    (
+                        "One full horizontal bit window",
+                        (one.clone() - orientation.clone())
+                            * (full_window_count.clone() - one.clone()),
+                    ),
+                    (
+                        "One full vertical bit window",
+                        orientation * (full_window_count - one),
+                    ),
                 ],
             )
         });
@@ -260,10 +420,94 @@ impl<F: FieldExt, const S: usize> PlacementChip<F, S> {
             s_adjacency,
             s_permute,
             s_constrain,
+            s_nonzero: None,
+            audit: None,
             _marker: PhantomData,
         }
     }
 
+    /**
+     * Extend a placement config to also reject an omitted ship (H = V = 0) in the load region
+     * @dev only constrains commitments loaded by synthesize_from_values/ synthesize_ship. a zero
+     *      commitment already fails "Placed ship of correct length", but only after the full trace
+     *      the inverse of H + V is witnessed in full_window_sum on the load row
+     *
+     * @param config - config returned by configure()
+     * @return - the config with s_nonzero set
+     */
+    pub fn configure_nonzero(
+        meta: &mut ConstraintSystem<F>,
+        mut config: PlacementConfig<F, S>,
+    ) -> PlacementConfig<F, S> {
+        let PlacementConfig {
+            bits,
+            bit_sum,
+            full_window_sum,
+            ..
+        } = config;
+        let s_nonzero = meta.selector();
+        meta.create_gate("nonzero placement commitment", |meta| {
+            let one = Expression::Constant(F::one());
+            let horizontal = meta.query_advice(bits, Rotation::cur());
+            let vertical = meta.query_advice(bit_sum, Rotation::cur());
+            let inverse = meta.query_advice(full_window_sum, Rotation::cur());
+            // (H + V) * inverse == 1 has no solution if H + V == 0
+            let selector = meta.query_selector(s_nonzero);
+            Constraints::with_selector(
+                selector,
+                [(
+                    "Placement commitment is nonzero",
+                    (horizontal + vertical) * inverse - one,
+                )],
+            )
+        });
+        config.s_nonzero = Some(s_nonzero);
+        config
+    }
+
+    /**
+     * Extend a placement config to export the window check to public instances
+     * @dev the final full window sum is copied from the running sum trace. windows checked is a
+     *      running count on the trace rows: it increments on every row s_adjacency checks a window
+     *      and carries over every row s_permute skips, so it counts the window checks actually
+     *      enabled in the circuit
+     *
+     * @param config - config returned by configure()
+     * @param windows_checked - advice column to count window checks in (not used by the chip)
+     * @param instance - the instance column to export to
+     * @param row - instance row of the final full window sum (windows checked follows at row + 1)
+     * @return - the config with audit set
+     */
+    pub fn configure_audit(
+        meta: &mut ConstraintSystem<F>,
+        mut config: PlacementConfig<F, S>,
+        windows_checked: Column<Advice>,
+        instance: Column<Instance>,
+        row: usize,
+    ) -> PlacementConfig<F, S> {
+        meta.enable_equality(windows_checked);
+        meta.enable_equality(instance);
+        meta.create_gate("window audit count", |meta| {
+            let one = Expression::Constant(F::one());
+            let prev = meta.query_advice(windows_checked, Rotation::prev());
+            let count = meta.query_advice(windows_checked, Rotation::cur());
+            let selector = meta.query_selector(config.s_adjacency);
+            Constraints::with_selector(selector, [("Count window check", count - prev - one)])
+        });
+        meta.create_gate("window audit carry", |meta| {
+            let prev = meta.query_advice(windows_checked, Rotation::prev());
+            let count = meta.query_advice(windows_checked, Rotation::cur());
+            let selector = meta.query_selector(config.s_permute);
+            Constraints::with_selector(selector, [("Carry window check count", count - prev)])
+        });
+        config.audit = Some(WindowAuditConfig {
+            windows_checked,
+            instance,
+            row,
+        });
+        config
+    }
+
     pub fn synthesize(
         &self,
         layouter: &mut impl Layouter<F>,
@@ -272,17 +516,202 @@ impl<F: FieldExt, const S: usize> PlacementChip<F, S> {
         vertical: AssignedBits<F>,
     ) -> Result<(), Error> {
         // load values in memoru
-        let bits = ship.bitfield();
-        let trace = compute_placement_trace::<F, S>(ship);
+        let gadget = PlacementGadget::<F, S>::new(ship);
+        self.synthesize_gadget(layouter, gadget, horizontal, vertical)
+    }
+
+    /**
+     * Synthesize a standalone placement proof from raw H, V commitment values
+     * @dev assigns and decomposes the commitments itself instead of expecting an outer region to
+     *
+     * @param num2bits - num2bits configs used to decompose [horizontal, vertical]
+     * @param horizontal - horizontal ship commitment value
+     * @param vertical - vertical ship commitment value
+     * @param gadget - off-circuit witness values for the collapsed H + V placement
+     * @return - assigned [horizontal, vertical] commitment cells (ex: to expose publicly)
+     */
+    pub fn synthesize_from_values(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        num2bits: [BitifyConfig; 2],
+        horizontal: Value<F>,
+        vertical: Value<F>,
+        gadget: PlacementGadget<F, S>,
+    ) -> Result<[AssignedCell<F, F>; 2], Error> {
+        // assign H, V commitments
+        let commitments = layouter.assign_region(
+            || "load placement commitments",
+            |mut region: Region<F>| {
+                let horizontal = region.assign_advice(
+                    || "H ship commitment",
+                    self.config.bits,
+                    0,
+                    || horizontal,
+                )?;
+                let vertical = region.assign_advice(
+                    || "V ship commitment",
+                    self.config.bit_sum,
+                    0,
+                    || vertical,
+                )?;
+                if let Some(s_nonzero) = self.config.s_nonzero {
+                    let sum = horizontal.value().copied() + vertical.value();
+                    region.assign_advice(
+                        || "H + V commitment inverse",
+                        self.config.full_window_sum,
+                        0,
+                        || sum.map(|sum| sum.invert().unwrap_or(F::zero())),
+                    )?;
+                    s_nonzero.enable(&mut region, 0)?;
+                }
+                Ok([horizontal, vertical])
+            },
+        )?;
+        // decompose H, V commitments
+        let horizontal = Num2BitsChip::<F, BOARD_SIZE>::from_assigned(commitments[0].clone())
+            .synthesize(num2bits[0], layouter.namespace(|| "H num2bits"))?;
+        let vertical = Num2BitsChip::<F, BOARD_SIZE>::from_assigned(commitments[1].clone())
+            .synthesize(num2bits[1], layouter.namespace(|| "V num2bits"))?;
+        self.synthesize_gadget(layouter, gadget, horizontal, vertical)?;
+        Ok(commitments)
+    }
+
+    /**
+     * Synthesize a standalone placement proof for a ship
+     * @dev preferred entry point: the H, V commitments are derived from the ship's orientation
+     *      so callers never split the placement themselves
+     *
+     * @param num2bits - num2bits configs used to decompose [horizontal, vertical]
+     * @param ship - the ship to place (must be S cells long)
+     * @param gadget - off-circuit witness values for the ship (see PlacementGadget::from_ship)
+     * @return - assigned [horizontal, vertical] commitment cells (ex: to expose publicly)
+     */
+    pub fn synthesize_ship(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        num2bits: [BitifyConfig; 2],
+        ship: Ship,
+        gadget: PlacementGadget<F, S>,
+    ) -> Result<[AssignedCell<F, F>; 2], Error> {
+        if ship.ship_type.length() != S {
+            return Err(Error::Synthesis);
+        }
+        let [horizontal, vertical] = ship
+            .witness(WitnessOption::Default)
+            .map(|commitment| Value::known(F::from_u128(commitment.lower_u128())));
+        self.synthesize_from_values(layouter, num2bits, horizontal, vertical, gadget)
+    }
+
+    /**
+     * Constrain a placement given its witness values and assigned H, V bit decompositions
+     *
+     * @param gadget - off-circuit witness values for the collapsed H + V placement
+     * @param horizontal - assigned cells for bits2num decomposition of horizontal commitment
+     * @param vertical - assigned cells for bits2num decomposition of vertical commitment
+     * @return - Ok if synthesis executes successfully
+     */
+    fn synthesize_gadget(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        gadget: PlacementGadget<F, S>,
+        horizontal: AssignedBits<F>,
+        vertical: AssignedBits<F>,
+    ) -> Result<(), Error> {
+        let orientation = placement_orientation(&vertical);
         // begin proof synthesis
-        let assigned_bits = self.load_bits(layouter, bits, horizontal, vertical)?;
-        let running_sums = self.placement_sums(layouter, assigned_bits, trace)?;
-        self.assign_constraint(layouter, running_sums)?;
+        let assigned_bits = self.load_bits(layouter, gadget.bits, horizontal, vertical)?;
+        self.check_bits(layouter, assigned_bits, gadget.trace, orientation)
+    }
+
+    /**
+     * Constrain already assigned placement bits to hold a single valid ship of length S
+     * @dev skips loading and collapsing H, V commitments (ex: bits decomposed from a hash preimage).
+     *      the bits must already be constrained boolean, and a vertical ship must be transposed into
+     *      a row as the collapsed H + V bits are
+     *
+     * @param bits - assigned bits of the placement in board order
+     * @param trace - running sum trace for the bits (see compute_placement_trace)
+     * @return - Ok if synthesis executes successfully
+     */
+    pub fn synthesize_bits_only(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        bits: AssignedBits<F>,
+        trace: PlacementTrace<F>,
+    ) -> Result<(), Error> {
+        // without H, V commitments a transposed ship is checked (and reported) as horizontal
+        self.check_bits(layouter, bits, trace, Value::known(F::zero()))
+    }
+
+    /**
+     * Run the placement running sums over assigned bits and constrain their output
+     *
+     * @param bits - assigned bits of the collapsed placement
+     * @param trace - running sum trace for the bits
+     * @param orientation - 0 if the ship was placed horizontally, 1 if placed vertically
+     * @return - Ok if synthesis executes successfully
+     */
+    fn check_bits(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        bits: AssignedBits<F>,
+        trace: PlacementTrace<F>,
+        orientation: Value<F>,
+    ) -> Result<(), Error> {
+        let running_sums = self.placement_sums(layouter, bits, trace)?;
+        let full_window_sum = running_sums.full_window_sum.clone();
+        let windows_checked = running_sums.windows_checked.clone();
+        self.assign_constraint(layouter, running_sums, orientation)?;
+        if let (Some(audit), Some(windows_checked)) = (self.config.audit, windows_checked) {
+            self.expose_window_audit(layouter, &full_window_sum, &windows_checked, audit)?;
+        }
         Ok(())
     }
+
+    /**
+     * Export the final full window sum and the number of windows checked to public instances
+     *
+     * @param full_window_sum - assigned final cell of the full window running sum trace
+     * @param windows_checked - assigned final cell of the window check count
+     * @param audit - the instance column and rows to export to
+     * @return - Ok if both values were constrained to the instance cells
+     */
+    fn expose_window_audit(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        full_window_sum: &AssignedCell<F, F>,
+        windows_checked: &AssignedCell<F, F>,
+        audit: WindowAuditConfig,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(full_window_sum.cell(), audit.instance, audit.row)?;
+        layouter.constrain_instance(windows_checked.cell(), audit.instance, audit.row + 1)
+    }
+
+    /**
+     * Export a ship placement commitment to a public instance column
+     * @dev lets a verifier tie a placement proof to an individually published ship commitment
+     *
+     * @param commitment - assigned cell storing the H or V ship commitment decomposed for this chip
+     * @param instance - the instance column to export the commitment to
+     * @param row - the row in the instance column the commitment is expected at
+     * @return - Ok if the commitment was constrained to the instance cell
+     */
+    pub fn expose_commitment(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        commitment: &AssignedCell<F, F>,
+        instance: Column<Instance>,
+        row: usize,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(commitment.cell(), instance, row)
+    }
 }
 
-impl<F: FieldExt, const S: usize> PlacementInstructions<F, S> for PlacementChip<F, S> {
+impl<F: FieldExt, const S: usize> PlacementInstructions<F, S> for PlacementChip<F, S>
+where
+    ShipLen<S>: ValidShipLen,
+{
+    #[cfg_attr(feature = "trace", tracing::instrument(level = "debug", skip_all, fields(ship_length = S)))]
     fn load_bits(
         &self,
         layouter: &mut impl Layouter<F>,
@@ -290,6 +719,13 @@ impl<F: FieldExt, const S: usize> PlacementInstructions<F, S> for PlacementChip<
         horizontal: AssignedBits<F>,
         vertical: AssignedBits<F>,
     ) -> Result<AssignedBits<F>, Error> {
+        #[cfg(feature = "trace")]
+        tracing::debug!(
+            region = "permute and collapse bit decompositions",
+            offset = 0,
+            rows = BOARD_SIZE,
+            bit_count = bits.iter().filter(|bit| **bit == F::one()).count()
+        );
         Ok(layouter.assign_region(
             || "permute and collapse bit decompositions",
             |mut region: Region<F>| {
@@ -320,12 +756,20 @@ impl<F: FieldExt, const S: usize> PlacementInstructions<F, S> for PlacementChip<
         )?)
     }
 
+    #[cfg_attr(feature = "trace", tracing::instrument(level = "debug", skip_all, fields(ship_length = S)))]
     fn placement_sums(
         &self,
         layouter: &mut impl Layouter<F>,
         bits2num: AssignedBits<F>,
         trace: PlacementTrace<F>,
     ) -> Result<PlacementState<F>, Error> {
+        #[cfg(feature = "trace")]
+        tracing::debug!(
+            region = "placement running sum trace",
+            offset = BOARD_SIZE,
+            bit_sum = ?trace[0][BOARD_SIZE - 1],
+            full_window_sum = ?trace[1][BOARD_SIZE - 1]
+        );
         Ok(layouter.assign_region(
             || "placement running sum trace",
             |mut region: Region<F>| {
@@ -341,14 +785,30 @@ impl<F: FieldExt, const S: usize> PlacementInstructions<F, S> for PlacementChip<
         )?)
     }
 
+    #[cfg_attr(feature = "trace", tracing::instrument(level = "debug", skip_all, fields(ship_length = S)))]
     fn assign_constraint(
         &self,
         layouter: &mut impl Layouter<F>,
         state: PlacementState<F>,
+        orientation: Value<F>,
     ) -> Result<(), Error> {
+        #[cfg(feature = "trace")]
+        tracing::debug!(
+            region = "constrain running sum output",
+            offset = 0,
+            orientation = ?orientation,
+            bit_sum = ?state.bit_sum.value(),
+            full_window_sum = ?state.full_window_sum.value()
+        );
         Ok(layouter.assign_region(
             || "constrain running sum output",
             |mut region: Region<F>| {
+                region.assign_advice(
+                    || "assign placement orientation",
+                    self.config.bits,
+                    0,
+                    || orientation,
+                )?;
                 state.bit_sum.copy_advice(
                     || "copy bit sum total count to constraint region",
                     &mut region,
@@ -371,15 +831,99 @@ impl<F: FieldExt, const S: usize> PlacementInstructions<F, S> for PlacementChip<
 pub type AssignedBits<F> = [AssignedCell<F, F>; BOARD_SIZE];
 pub type PlacementTrace<F> = [[F; BOARD_SIZE]; 2];
 
+/**
+ * Determine whether the trace checks the S-bit window starting at a board cell
+ * @dev windows that would wrap onto the next row are skipped (s_permute carries the sum instead)
+ *
+ * @param cell - the board cell [0-99] the window starts at
+ * @return - true if the window fits in the cell's row
+ */
+pub fn checks_window<const S: usize>(cell: usize) -> bool
+where
+    ShipLen<S>: ValidShipLen,
+{
+    cell % 10 + S <= 10
+}
+
+/**
+ * Count the S-bit windows the placement trace checks for a full bit window
+ *
+ * @return - number of board cells a window fits from, (10 - S + 1) per row
+ */
+pub fn windows_checked<const S: usize>() -> usize
+where
+    ShipLen<S>: ValidShipLen,
+{
+    (0..BOARD_SIZE)
+        .filter(|cell| checks_window::<S>(*cell))
+        .count()
+}
+
+/**
+ * Determine the orientation of a placement from its vertical bit decomposition
+ *
+ * @param vertical - assigned cells for bits2num decomposition of vertical commitment
+ * @return - 1 if any vertical bit is flipped (vertical placement), 0 otherwise (horizontal placement)
+ */
+pub fn placement_orientation<F: FieldExt>(vertical: &AssignedBits<F>) -> Value<F> {
+    vertical
+        .iter()
+        .fold(Value::known(F::zero()), |sum, bit| sum + bit.value())
+        .map(|sum| F::from(sum != F::zero()))
+}
+
+/**
+ * Return the coefficients of the polynomial incrementing the full window running sum
+ * @dev interpolated over [0, S] -> [0, .., 0, 1] (ex: S = 4 maps 0, 1, 2, 3 -> 0 and 4 -> 1), so
+ *      evaluating it at a window's bit count is 1 only for a full window. shared by the adjacency
+ *      gate and verify_placement so both evaluate the same polynomial
+ *
+ * @return - S + 1 coefficients in ascending degree order
+ */
+pub fn window_incrementor<F: FieldExt, const S: usize>() -> Vec<F>
+where
+    ShipLen<S>: ValidShipLen,
+{
+    let points = (0..=S).map(|i| F::from(i as u64)).collect::<Vec<F>>();
+    let evals = (0..=S)
+        .map(|i| F::from((i == S) as u64))
+        .collect::<Vec<F>>();
+    lagrange_interpolate(&points, &evals)
+}
+
+/**
+ * Return the S bits starting at an offset, if they lie within a single board row
+ * @dev the length is validated before slicing so a misjudged offset surfaces as an error naming
+ *      it instead of a slice panic. an offset past the last row is rejected even though
+ *      offset % 10 + S <= 10 alone would accept it
+ *
+ * @param bits - bit decomposition of a placement
+ * @param offset - index of the first bit in the window
+ * @return - bits[offset..offset + S], or WindowOutOfBounds if it wraps a row or leaves the board
+ */
+pub fn get_window<F: FieldExt, const S: usize>(
+    bits: &[F; BOARD_SIZE],
+    offset: usize,
+) -> Result<[F; S], GadgetError>
+where
+    ShipLen<S>: ValidShipLen,
+{
+    if offset % 10 + S > 10 || offset + S > BOARD_SIZE {
+        return Err(GadgetError::WindowOutOfBounds(offset));
+    }
+    Ok(bits[offset..offset + S].try_into().unwrap())
+}
+
 /**
  * Given a ShipPlacement object, construct the running sum traces
  *
  * @param ship - ship helper object
  * @return - bit_sum and full_bit_window cell values for assignment
  */
-pub fn compute_placement_trace<F: FieldExt, const S: usize>(
-    ship: BinaryValue,
-) -> PlacementTrace<F> {
+pub fn compute_placement_trace<F: FieldExt, const S: usize>(ship: BinaryValue) -> PlacementTrace<F>
+where
+    ShipLen<S>: ValidShipLen,
+{
     let bits = ship.bitfield::<F, BOARD_SIZE>();
     // compute bit_sum trace
     let mut trace: Vec<F> = Vec::<F>::new();
@@ -390,11 +934,8 @@ pub fn compute_placement_trace<F: FieldExt, const S: usize>(
     let bit_sum: [F; BOARD_SIZE] = trace.try_into().unwrap();
 
     // function for returning increment
-    // expects permute case check to be done lower in stack
-    let increment = |offset: usize| {
-        let bit_count = bits[offset..offset + S]
-            .iter()
-            .fold(F::zero(), |sum: F, elem: &F| sum + elem);
+    let increment = |window: [F; S]| {
+        let bit_count = window.iter().fold(F::zero(), |sum: F, elem: &F| sum + elem);
         let v = if bit_count.eq(&F::from(S as u64)) {
             F::one()
         } else {
@@ -404,72 +945,482 @@ pub fn compute_placement_trace<F: FieldExt, const S: usize>(
     };
 
     // compute full bit window trace
-    trace = vec![increment(0)];
+    trace = vec![get_window::<F, S>(&bits, 0).map_or(F::zero(), increment)];
     for i in 1..bits.len() {
-        if i % 10 + S > 10 {
+        match get_window::<F, S>(&bits, i) {
             // permute case
-            trace.push(trace[i - 1]);
-        } else {
+            Err(_) => trace.push(trace[i - 1]),
             // bit window check case
-            trace.push(trace[i - 1] + increment(i))
+            Ok(window) => trace.push(trace[i - 1] + increment(window)),
         }
     }
     let full_window_sum: [F; BOARD_SIZE] = trace.try_into().unwrap();
     [bit_sum, full_window_sum]
 }
 
-// defines storage of final running bit and full bit window sums
-pub struct PlacementState<F: FieldExt> {
-    pub bit_sum: AssignedCell<F, F>,
-    pub full_window_sum: AssignedCell<F, F>,
+/**
+ * Final running sums and constraint results computed by the placement chip for a witness
+ *
+ * @param bit_sum - total count of flipped bits across H + V
+ * @param full_window_sum - total count of full S-bit windows that do not wrap a row
+ * @param correct_length - "Placed ship of correct length" holds (bit_sum == S)
+ * @param boolean_orientation - "Orientation is boolean" holds
+ * @param horizontal_window - "One full horizontal bit window" holds
+ * @param vertical_window - "One full vertical bit window" holds
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PlacementOutcome {
+    pub bit_sum: u64,
+    pub full_window_sum: u64,
+    pub correct_length: bool,
+    pub boolean_orientation: bool,
+    pub horizontal_window: bool,
+    pub vertical_window: bool,
 }
 
-impl<F: FieldExt> PlacementState<F> {
+impl PlacementOutcome {
     /**
-     * Construct a new PlacementState object
-     * @dev use assign_padding_row() in practice
+     * Return true if every placement constraint holds (the chip accepts the witness)
+     */
+    pub fn accepted(self) -> bool {
+        self.correct_length
+            && self.boolean_orientation
+            && self.horizontal_window
+            && self.vertical_window
+    }
+}
+
+/**
+ * Simulate the placement chip's constraints off-circuit using integer arithmetic
+ * @dev written independently of compute_placement_trace for differential testing against the chip
+ * @dev expects H and V to not share a flipped bit (the chip witnesses their sum as one bit)
+ *
+ * @param placement - [horizontal, vertical] ship commitments as witnessed into the chip
+ * @return - running sum totals and the result of each "running sum constraints" constraint
+ */
+pub fn simulate_placement<const S: usize>(placement: [BinaryValue; 2]) -> PlacementOutcome
+where
+    ShipLen<S>: ValidShipLen,
+{
+    let [horizontal, vertical] = placement;
+    let bits: Vec<u64> = (0..BOARD_SIZE)
+        .map(|i| horizontal.value[i] as u64 + vertical.value[i] as u64)
+        .collect();
+    let bit_sum = bits.iter().sum::<u64>();
+    // windows that would wrap onto the next row are skipped by the permute gate
+    let full_window_sum = (0..BOARD_SIZE)
+        .filter(|&i| checks_window::<S>(i))
+        .filter(|&i| bits[i..i + S].iter().sum::<u64>() == S as u64)
+        .count() as u64;
+    let orientation = vertical.value[..BOARD_SIZE].any();
+    PlacementOutcome {
+        bit_sum,
+        full_window_sum,
+        correct_length: bit_sum == S as u64,
+        boolean_orientation: true,
+        horizontal_window: orientation || full_window_sum == 1,
+        vertical_window: !orientation || full_window_sum == 1,
+    }
+}
+
+/**
+ * A placement rule broken by a supplied bit decomposition
+ *
+ * @param Composition - the bits do not recompose to the ship commitment (num2bits)
+ * @param NonBooleanBit - the bit at the given index is not 0 or 1 (num2bits)
+ * @param IncorrectLength - the bits do not sum to S ("Placed ship of correct length")
+ * @param FullWindowCount - the bits do not hold exactly one full bit window ("One full _ bit window")
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PlacementViolation {
+    Composition,
+    NonBooleanBit(usize),
+    IncorrectLength,
+    FullWindowCount,
+}
+
+/**
+ * Check supplied bits against every constraint the num2bits and placement chips apply to them
+ * @dev reference implementation of the chip: windows are counted with the same interpolated
+ *      incrementor the "adjacency bit count" gate uses, so non-boolean bits are counted as in-circuit
+ *
+ * @param ship - collapsed H + V ship commitment the bits are decomposed from
+ * @param bits - the bit decomposition supplied as witness (little endian)
+ * @return - Ok if the chip would accept the bits, otherwise every violated constraint
+ */
+pub fn verify_placement<F: FieldExt, const S: usize>(
+    ship: BinaryValue,
+    bits: [F; BOARD_SIZE],
+) -> Result<(), Vec<PlacementViolation>>
+where
+    ShipLen<S>: ValidShipLen,
+{
+    let mut violations = Vec::<PlacementViolation>::new();
+    let composed = bits
+        .iter()
+        .zip(pow2_table::<F>())
+        .fold(F::zero(), |sum, (bit, e2)| sum + *bit * e2);
+    if composed != F::from_u128(ship.lower_u128()) {
+        violations.push(PlacementViolation::Composition);
+    }
+    for (i, bit) in bits.iter().enumerate() {
+        if *bit * (F::one() - bit) != F::zero() {
+            violations.push(PlacementViolation::NonBooleanBit(i));
+        }
+    }
+    if bits.iter().fold(F::zero(), |sum, bit| sum + bit) != F::from(S as u64) {
+        violations.push(PlacementViolation::IncorrectLength);
+    }
+    let incrementor = window_incrementor::<F, S>();
+    // windows that would wrap onto the next row are skipped by the permute gate
+    let full_window_sum = (0..BOARD_SIZE)
+        .filter(|i| i % 10 + S <= 10)
+        .fold(F::zero(), |sum, i| {
+            let bit_count = bits[i..i + S].iter().fold(F::zero(), |sum, bit| sum + bit);
+            sum + eval_polynomial(&incrementor, bit_count)
+        });
+    if full_window_sum != F::one() {
+        violations.push(PlacementViolation::FullWindowCount);
+    }
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(violations)
+    }
+}
+
+/**
+ * Reasons a mask of occupied cells cannot be read as a single ship placement
+ *
+ * @param OffBoard - a bit beyond the 100 board cells is set
+ * @param IncorrectLength - the mask does not occupy exactly S cells (holds the number occupied)
+ * @param NonContiguous - the occupied cells do not form one straight horizontal or vertical line
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MaskError {
+    OffBoard,
+    IncorrectLength(usize),
+    NonContiguous,
+}
+
+/**
+ * Internal inconsistencies of a placement gadget that would fail the chip's output constraints
+ *
+ * @param BitSum - the last row of the bit_sum trace is not S
+ * @param FullWindowSum - the last row of the full_window_sum trace is not 1
+ * @param BitCount - the number of set bits is not S (holds the number set)
+ * @param WindowOutOfBounds - an S-bit window at the held offset wraps a row or leaves the board
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GadgetError {
+    BitSum,
+    FullWindowSum,
+    BitCount(usize),
+    WindowOutOfBounds(usize),
+}
+
+/**
+ * Compute the [H, V] commitments of a ship of length S with its head at (x, y)
+ * @dev vertical placements are committed transposed (bit x * 10 + y for cell (x, y)). a single
+ *      cell (S = 1) is both horizontal and vertical, so it is canonicalized to z = false: either z
+ *      commits H at bit y * 10 + x, rather than H there or V at the transposed bit
+ * @dev expects the ship to fit on the board
+ *
+ * @param x - horizontal coordinate of the ship head
+ * @param y - vertical coordinate of the ship head
+ * @param z - if true the ship extends vertically from the head, otherwise horizontally
+ * @return - [horizontal, vertical] commitments where the unplaced orientation is empty
+ */
+pub fn placement_commitments<const S: usize>(x: u8, y: u8, z: bool) -> [BinaryValue; 2]
+where
+    ShipLen<S>: ValidShipLen,
+{
+    let z = z && S > 1;
+    let (x, y) = (x as usize, y as usize);
+    let mut placement = BinaryValue::empty();
+    for i in 0..S {
+        let index = if z { x * 10 + y + i } else { y * 10 + x + i };
+        placement.value.set(index, true);
+    }
+    match z {
+        true => [BinaryValue::empty(), placement],
+        false => [placement, BinaryValue::empty()],
+    }
+}
+
+/**
+ * Cache key for the placement of a ship of length S (ex: reusing gadgets when batch proving)
+ * @dev two keys are equal (and hash the same) iff they share x, y, z and S. the ship type is not
+ *      part of the key, so a cruiser and a submarine with the same head share one gadget. S = 1 is
+ *      canonicalized to z = false (see placement_commitments)
+ *
+ * @param x - horizontal coordinate of the ship head
+ * @param y - vertical coordinate of the ship head
+ * @param z - if true the ship extends vertically from the head, otherwise horizontally
+ */
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ShipPlacement<const S: usize>
+where
+    ShipLen<S>: ValidShipLen,
+{
+    pub x: u8,
+    pub y: u8,
+    pub z: bool,
+}
+
+impl<const S: usize> ShipPlacement<S>
+where
+    ShipLen<S>: ValidShipLen,
+{
+    /**
+     * Construct the key for a ship of length S with its head at (x, y)
      *
-     * @param bit_sum - reference to assigned bit_sum cell
-     * @param full_window_sum - reference to assigned full_bit_window cell
-     * @return - BoardState object
+     * @param x - horizontal coordinate of the ship head
+     * @param y - vertical coordinate of the ship head
+     * @param z - if true the ship extends vertically from the head, otherwise horizontally
+     * @return - the placement key
      */
-    pub fn new(bit_sum: AssignedCell<F, F>, full_window_sum: AssignedCell<F, F>) -> Self {
-        PlacementState {
-            bit_sum,
-            full_window_sum,
+    pub fn new(x: u8, y: u8, z: bool) -> Self {
+        ShipPlacement {
+            x,
+            y,
+            z: z && S > 1,
         }
     }
 
     /**
-     * Adds a row of 0's to the start of the region to prevent unexpected
-     * constraints by gates using Rotation::prev() in first row (first w/o padding)
+     * Construct the key for a placed ship
      *
-     * @param region - the "placement running sum trace" region to pad first row of
-     * @param config - PlacementChip config holding advice columns to assign to
-     * @return - if successful padding first row, return new PlacementState w/ cells assigned to 0
+     * @param ship - the ship to key the placement of
+     * @return - the placement key, or None if the ship's length is not S
      */
-    pub fn assign_padding_row<const S: usize>(
-        region: &mut Region<F>,
-        config: &PlacementConfig<F, S>,
-    ) -> Result<Self, Error> {
-        let bit_sum = region.assign_advice_from_constant(
-            || "pad bit count running sum",
-            config.bit_sum,
-            0,
-            F::zero(),
-        )?;
-        let full_window_sum = region.assign_advice_from_constant(
-            || "pad full bit window running sum",
-            config.full_window_sum,
-            0,
-            F::zero(),
-        )?;
-        Ok(PlacementState::<F>::new(bit_sum, full_window_sum))
+    pub fn from_ship(ship: Ship) -> Option<Self> {
+        match ship.ship_type.length() == S {
+            true => Some(ShipPlacement::new(ship.x, ship.y, ship.z)),
+            false => None,
+        }
     }
 
     /**
-     * Permute the assigned cells from the bit2num region to the running sum trace region
-     * @notice bits are permuted to advice[1][1..100] (row 0 is padding)
+     * Compute the witness values for the keyed placement
+     *
+     * @return - PlacementGadget equal to PlacementGadget::from_head(x, y, z)
+     */
+    pub fn gadget<F: FieldExt>(self) -> PlacementGadget<F, S> {
+        PlacementGadget::from_head(self.x, self.y, self.z)
+    }
+}
+
+/**
+ * Off-circuit witness values for a placement proof
+ * @dev holds field arrays so cannot be hashed - key caches on the ShipPlacement it was built from
+ *
+ * @param bits - bit decomposition of the collapsed H + V placement
+ * @param trace - bit_sum and full_window_sum running sum traces over bits
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PlacementGadget<F: FieldExt, const S: usize>
+where
+    ShipLen<S>: ValidShipLen,
+{
+    pub bits: [F; BOARD_SIZE],
+    pub trace: PlacementTrace<F>,
+}
+
+impl<F: FieldExt, const S: usize> PlacementGadget<F, S>
+where
+    ShipLen<S>: ValidShipLen,
+{
+    /**
+     * Compute the witness values for a ship placement
+     *
+     * @param placement - collapsed H + V ship placement (transposed if vertical)
+     * @return - PlacementGadget holding bits and running sum traces for the placement
+     */
+    pub fn new(placement: BinaryValue) -> Self {
+        PlacementGadget {
+            bits: placement.bitfield::<F, BOARD_SIZE>(),
+            trace: compute_placement_trace::<F, S>(placement),
+        }
+    }
+
+    /**
+     * Compute the witness values for a ship placed with its honest H, V commitments
+     *
+     * @param ship - the ship to compute placement witness values for
+     * @return - PlacementGadget holding bits and running sum traces for the ship
+     */
+    pub fn from_ship(ship: Ship) -> Self {
+        let [horizontal, vertical] = ship.witness(WitnessOption::Default);
+        PlacementGadget::new(horizontal.zip(vertical))
+    }
+
+    /**
+     * Compute the witness values for a ship of length S with its head at (x, y)
+     * @dev S = 1 is canonicalized to z = false (see placement_commitments)
+     *
+     * @param x - horizontal coordinate of the ship head
+     * @param y - vertical coordinate of the ship head
+     * @param z - if true the ship extends vertically from the head, otherwise horizontally
+     * @return - PlacementGadget holding bits and running sum traces for the placement
+     */
+    pub fn from_head(x: u8, y: u8, z: bool) -> Self {
+        let [horizontal, vertical] = placement_commitments::<S>(x, y, z);
+        PlacementGadget::new(horizontal.zip(vertical))
+    }
+
+    /**
+     * Compute the witness values for a ship given only the board cells it occupies
+     * @dev infers the origin from the lowest occupied cell and the orientation from the next.
+     *      a single cell (S = 1) has no next cell and is always treated as horizontal
+     *
+     * @param mask - occupied board cells (bit y * 10 + x), as exported by Ship::to_mask
+     * @return - PlacementGadget equal to from_ship for the ship occupying the mask, or why none does
+     */
+    pub fn from_mask(mask: BinaryValue) -> Result<Self, MaskError> {
+        if mask.value[BOARD_SIZE..].any() {
+            return Err(MaskError::OffBoard);
+        }
+        let cells = mask.value[0..BOARD_SIZE]
+            .iter_ones()
+            .collect::<Vec<usize>>();
+        if cells.len() != S {
+            return Err(MaskError::IncorrectLength(cells.len()));
+        }
+        // cells ascend, so a horizontal ship steps by 1 within a row and a vertical ship by 10
+        let head = cells[0];
+        let z = S > 1 && cells[1] - head == 10;
+        let contiguous = match z {
+            true => cells
+                .iter()
+                .enumerate()
+                .all(|(i, cell)| *cell == head + i * 10),
+            false => {
+                head % 10 + S <= 10 && cells.iter().enumerate().all(|(i, cell)| *cell == head + i)
+            }
+        };
+        if !contiguous {
+            return Err(MaskError::NonContiguous);
+        }
+        // vertical placements are committed transposed
+        let mut placement = BinaryValue::empty();
+        for cell in cells {
+            let index = if z { cell % 10 * 10 + cell / 10 } else { cell };
+            placement.value.set(index, true);
+        }
+        Ok(PlacementGadget::new(placement))
+    }
+
+    /**
+     * Check the gadget describes exactly one ship of length S before it is synthesized
+     * @dev mirrors the "constrain running sum output" gate so malformed gadgets fail early
+     *
+     * @return - Ok if the gadget is consistent, otherwise the first inconsistency found
+     */
+    pub fn check_invariants(&self) -> Result<(), GadgetError> {
+        if self.trace[0][BOARD_SIZE - 1] != F::from(S as u64) {
+            return Err(GadgetError::BitSum);
+        }
+        if self.trace[1][BOARD_SIZE - 1] != F::one() {
+            return Err(GadgetError::FullWindowSum);
+        }
+        let count = self.bits.iter().filter(|bit| **bit == F::one()).count();
+        if count != S {
+            return Err(GadgetError::BitCount(count));
+        }
+        Ok(())
+    }
+
+    /**
+     * Recompose the bits into the decimal placement commitment
+     * @dev equals the sum of the H and V commitments that num2bits decomposes
+     *
+     * @return - sum(bits[i] * 2^i)
+     */
+    pub fn commitment(&self) -> F {
+        self.bits
+            .iter()
+            .zip(pow2_table::<F>())
+            .fold(F::zero(), |sum, (bit, e2)| sum + *bit * e2)
+    }
+}
+
+// same as PlacementGadget::from_ship so generic code can accept Into<PlacementGadget<F, S>>
+impl<F: FieldExt, const S: usize> From<Ship> for PlacementGadget<F, S>
+where
+    ShipLen<S>: ValidShipLen,
+{
+    fn from(ship: Ship) -> Self {
+        PlacementGadget::from_ship(ship)
+    }
+}
+
+// defines storage of final running bit and full bit window sums (and window check count if audited)
+pub struct PlacementState<F: FieldExt> {
+    pub bit_sum: AssignedCell<F, F>,
+    pub full_window_sum: AssignedCell<F, F>,
+    pub windows_checked: Option<AssignedCell<F, F>>,
+}
+
+impl<F: FieldExt> PlacementState<F> {
+    /**
+     * Construct a new PlacementState object
+     * @dev use assign_padding_row() in practice
+     *
+     * @param bit_sum - reference to assigned bit_sum cell
+     * @param full_window_sum - reference to assigned full_bit_window cell
+     * @return - BoardState object
+     */
+    pub fn new(bit_sum: AssignedCell<F, F>, full_window_sum: AssignedCell<F, F>) -> Self {
+        PlacementState {
+            bit_sum,
+            full_window_sum,
+            windows_checked: None,
+        }
+    }
+
+    /**
+     * Adds a row of 0's to the start of the region to prevent unexpected
+     * constraints by gates using Rotation::prev() in first row (first w/o padding)
+     *
+     * @param region - the "placement running sum trace" region to pad first row of
+     * @param config - PlacementChip config holding advice columns to assign to
+     * @return - if successful padding first row, return new PlacementState w/ cells assigned to 0
+     */
+    pub fn assign_padding_row<const S: usize>(
+        region: &mut Region<F>,
+        config: &PlacementConfig<F, S>,
+    ) -> Result<Self, Error>
+    where
+        ShipLen<S>: ValidShipLen,
+    {
+        let bit_sum = region.assign_advice_from_constant(
+            || "pad bit count running sum",
+            config.bit_sum,
+            0,
+            F::zero(),
+        )?;
+        let full_window_sum = region.assign_advice_from_constant(
+            || "pad full bit window running sum",
+            config.full_window_sum,
+            0,
+            F::zero(),
+        )?;
+        let mut state = PlacementState::<F>::new(bit_sum, full_window_sum);
+        if let Some(audit) = config.audit {
+            state.windows_checked = Some(region.assign_advice_from_constant(
+                || "pad window check count",
+                audit.windows_checked,
+                0,
+                F::zero(),
+            )?);
+        }
+        Ok(state)
+    }
+
+    /**
+     * Permute the assigned cells from the bit2num region to the running sum trace region
+     * @notice bits are permuted to advice[1][1..100] (row 0 is padding)
      *
      * @param self - PlacementState<F>
      * @param bits - assigned bit cells to copy advice from
@@ -483,25 +1434,13 @@ impl<F: FieldExt> PlacementState<F> {
         bits: &AssignedBits<F>,
         region: &mut Region<F>,
         config: &PlacementConfig<F, S>,
-    ) -> Result<AssignedBits<F>, Error> {
-        let mut permuted: Vec<AssignedCell<F, F>> = Vec::<AssignedCell<F, F>>::new();
-        for i in 0..bits.len() {
-            let bit = &bits[i];
-            permuted.push(bit.copy_advice(
-                || format!("permute bit {}", i),
-                region,
-                config.bits,
-                i + 1, // offset + 1 for padded row
-            )?);
-        }
-        Ok(AssignedBits::from(
-            permuted
-                .iter()
-                .map(|bit| bit.clone())
-                .collect::<Vec<AssignedCell<F, F>>>()
-                .try_into()
-                .unwrap(),
-        ))
+    ) -> Result<AssignedBits<F>, Error>
+    where
+        ShipLen<S>: ValidShipLen,
+    {
+        // offset + 1 for padded row
+        let permuted = copy_cells(region, bits, config.bits, 1)?;
+        Ok(permuted.try_into().unwrap())
     }
 
     /**
@@ -518,7 +1457,10 @@ impl<F: FieldExt> PlacementState<F> {
         region: &mut Region<F>,
         config: &PlacementConfig<F, S>,
         trace: &PlacementTrace<F>,
-    ) -> Result<PlacementState<F>, Error> {
+    ) -> Result<PlacementState<F>, Error>
+    where
+        ShipLen<S>: ValidShipLen,
+    {
         // first iteration
         let mut bit_sum_cell = region.assign_advice(
             || format!("assign running sum (bit count) {}", 0),
@@ -534,6 +1476,9 @@ impl<F: FieldExt> PlacementState<F> {
         )?;
         config.s_sum_bits.enable(region, 1)?;
         config.s_adjacency.enable(region, 1)?;
+        let mut windows_checked = 1;
+        let mut windows_checked_cell =
+            self.assign_window_count(region, config, 1, windows_checked)?;
         // iterate through trace
         // for offset in 2..=BOARD_SIZE {
         for offset in 2..=BOARD_SIZE {
@@ -555,15 +1500,1790 @@ impl<F: FieldExt> PlacementState<F> {
 
             // toggle selectors
             config.s_sum_bits.enable(region, offset)?;
-            if adjusted_offset % 10 + S > 10 {
-                config.s_permute.enable(region, offset)?;
-            } else {
+            if checks_window::<S>(adjusted_offset) {
                 config.s_adjacency.enable(region, offset)?;
+                windows_checked += 1;
+            } else {
+                config.s_permute.enable(region, offset)?;
             }
+            windows_checked_cell =
+                self.assign_window_count(region, config, offset, windows_checked)?;
         }
         Ok(PlacementState {
             bit_sum: bit_sum_cell,
             full_window_sum: full_window_sum_cell,
+            windows_checked: windows_checked_cell,
         })
     }
+
+    /**
+     * Assign the running count of window checks on a trace row if the config is audited
+     *
+     * @param region - the "placement running sum trace" region to assign the count in
+     * @param config - the PlacementChip config holding the audit column
+     * @param offset - the row of the trace to assign the count to
+     * @param count - the number of windows checked up to and including this row
+     * @return - the assigned count, or None if the config does not audit window checks
+     */
+    fn assign_window_count<const S: usize>(
+        &self,
+        region: &mut Region<F>,
+        config: &PlacementConfig<F, S>,
+        offset: usize,
+        count: u64,
+    ) -> Result<Option<AssignedCell<F, F>>, Error>
+    where
+        ShipLen<S>: ValidShipLen,
+    {
+        config
+            .audit
+            .map(|audit| {
+                region.assign_advice(
+                    || format!("assign window check count {}", offset - 1),
+                    audit.windows_checked,
+                    offset,
+                    || Value::known(F::from(count)),
+                )
+            })
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        crate::{
+            test_utils::empirical_min_k,
+            utils::{
+                layout::{min_k, LayoutRecorder},
+                ship::ShipType,
+            },
+        },
+        halo2_proofs::{
+            circuit::SimpleFloorPlanner,
+            dev::{metadata, CircuitGates, FailureLocation, MockProver, VerifyFailure},
+            pasta::Fp,
+            plonk::{Any, Circuit},
+        },
+        proptest::prelude::*,
+        std::collections::HashMap,
+    };
+
+    const CIRCUIT_SIZE: u32 = 9; // 2^CIRCUIT_SIZE rows used in circuit
+
+    #[derive(Clone, Copy, Debug)]
+    struct TestPlacementConfig<const S: usize> {
+        num2bits: [BitifyConfig; 2],
+        placement: PlacementConfig<Fp, S>,
+        advice: [Column<Advice>; 3],
+        instance: Column<Instance>,
+    }
+
+    #[derive(Debug, Clone)]
+    struct PlacementCircuit<const S: usize>
+    where
+        ShipLen<S>: ValidShipLen,
+    {
+        horizontal: BinaryValue,
+        vertical: BinaryValue,
+    }
+
+    impl<const S: usize> PlacementCircuit<S>
+    where
+        ShipLen<S>: ValidShipLen,
+    {
+        fn new(horizontal: BinaryValue, vertical: BinaryValue) -> Self {
+            Self {
+                horizontal,
+                vertical,
+            }
+        }
+
+        // construct the circuit from a ship and a witness option
+        fn from_ship(ship: Ship, option: WitnessOption) -> Self {
+            let [horizontal, vertical] = ship.witness(option);
+            Self::new(horizontal, vertical)
+        }
+    }
+
+    impl<const S: usize> Circuit<Fp> for PlacementCircuit<S>
+    where
+        ShipLen<S>: ValidShipLen,
+    {
+        type Config = TestPlacementConfig<S>;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> TestPlacementConfig<S> {
+            // create circuit columns
+            let mut advice = Vec::<Column<Advice>>::new();
+            for _ in 0..3 {
+                let col = meta.advice_column();
+                meta.enable_equality(col);
+                advice.push(col);
+            }
+            let advice: [Column<Advice>; 3] = advice.try_into().unwrap();
+            let fixed = meta.fixed_column();
+            meta.enable_constant(fixed);
+            let instance = meta.instance_column();
+            meta.enable_equality(instance);
+            // configure chips with columns
+            let num2bits = [(); 2].map(|_| {
+                Num2BitsChip::<_, BOARD_SIZE>::configure(
+                    meta, advice[0], advice[1], advice[2], fixed,
+                )
+            });
+            let placement =
+                PlacementChip::<Fp, S>::configure(meta, advice[0], advice[1], advice[2], fixed);
+            TestPlacementConfig {
+                num2bits,
+                placement,
+                advice,
+                instance,
+            }
+        }
+
+        fn synthesize(
+            &self,
+            config: TestPlacementConfig<S>,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            // load H, V commitments
+            let commitments = layouter.assign_region(
+                || "load ship commitments",
+                |mut region: Region<Fp>| {
+                    let horizontal = region.assign_advice(
+                        || "H ship commitment",
+                        config.advice[0],
+                        0,
+                        || Value::known(Fp::from_u128(self.horizontal.lower_u128())),
+                    )?;
+                    let vertical = region.assign_advice(
+                        || "V ship commitment",
+                        config.advice[1],
+                        0,
+                        || Value::known(Fp::from_u128(self.vertical.lower_u128())),
+                    )?;
+                    Ok([horizontal, vertical])
+                },
+            )?;
+            // decompose H, V commitments
+            let horizontal = Num2BitsChip::<Fp, BOARD_SIZE>::new(
+                commitments[0].clone(),
+                self.horizontal.bitfield::<Fp, BOARD_SIZE>(),
+            )
+            .synthesize(config.num2bits[0], layouter.namespace(|| "H num2bits"))?;
+            let vertical = Num2BitsChip::<Fp, BOARD_SIZE>::new(
+                commitments[1].clone(),
+                self.vertical.bitfield::<Fp, BOARD_SIZE>(),
+            )
+            .synthesize(config.num2bits[1], layouter.namespace(|| "V num2bits"))?;
+            // check placement and export commitments
+            let chip = PlacementChip::<Fp, S>::new(config.placement);
+            let ship = self.horizontal.zip(self.vertical);
+            chip.synthesize(&mut layouter, ship, horizontal, vertical)?;
+            chip.expose_commitment(&mut layouter, &commitments[0], config.instance, 0)?;
+            chip.expose_commitment(&mut layouter, &commitments[1], config.instance, 1)?;
+            Ok(())
+        }
+    }
+
+    // placement circuit driven from raw commitment values via synthesize_from_values
+    #[derive(Debug, Clone)]
+    struct StandalonePlacementCircuit<const S: usize>
+    where
+        ShipLen<S>: ValidShipLen,
+    {
+        horizontal: Value<Fp>,
+        vertical: Value<Fp>,
+        gadget: PlacementGadget<Fp, S>,
+    }
+
+    impl<const S: usize> StandalonePlacementCircuit<S>
+    where
+        ShipLen<S>: ValidShipLen,
+    {
+        fn new(ship: Ship) -> Self {
+            let [horizontal, vertical] = ship.witness(WitnessOption::Default);
+            Self {
+                horizontal: Value::known(Fp::from_u128(horizontal.lower_u128())),
+                vertical: Value::known(Fp::from_u128(vertical.lower_u128())),
+                gadget: PlacementGadget::new(horizontal.zip(vertical)),
+            }
+        }
+    }
+
+    impl<const S: usize> Circuit<Fp> for StandalonePlacementCircuit<S>
+    where
+        ShipLen<S>: ValidShipLen,
+    {
+        type Config = TestPlacementConfig<S>;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                horizontal: Value::unknown(),
+                vertical: Value::unknown(),
+                gadget: self.gadget,
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> TestPlacementConfig<S> {
+            PlacementCircuit::<S>::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: TestPlacementConfig<S>,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = PlacementChip::<Fp, S>::new(config.placement);
+            let commitments = chip.synthesize_from_values(
+                &mut layouter,
+                config.num2bits,
+                self.horizontal,
+                self.vertical,
+                self.gadget,
+            )?;
+            chip.expose_commitment(&mut layouter, &commitments[0], config.instance, 0)?;
+            chip.expose_commitment(&mut layouter, &commitments[1], config.instance, 1)?;
+            Ok(())
+        }
+    }
+
+    // standalone placement circuit that also rejects a zero H + V commitment in the load region
+    #[derive(Debug, Clone)]
+    struct NonzeroPlacementCircuit<const S: usize>(StandalonePlacementCircuit<S>)
+    where
+        ShipLen<S>: ValidShipLen;
+
+    impl<const S: usize> Circuit<Fp> for NonzeroPlacementCircuit<S>
+    where
+        ShipLen<S>: ValidShipLen,
+    {
+        type Config = TestPlacementConfig<S>;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self(self.0.without_witnesses())
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> TestPlacementConfig<S> {
+            let mut config = StandalonePlacementCircuit::<S>::configure(meta);
+            config.placement = PlacementChip::<Fp, S>::configure_nonzero(meta, config.placement);
+            config
+        }
+
+        fn synthesize(
+            &self,
+            config: TestPlacementConfig<S>,
+            layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            self.0.synthesize(config, layouter)
+        }
+    }
+
+    // standalone placement circuit that also exports the window audit after the commitments
+    #[derive(Debug, Clone)]
+    struct AuditPlacementCircuit<const S: usize>(StandalonePlacementCircuit<S>)
+    where
+        ShipLen<S>: ValidShipLen;
+
+    impl<const S: usize> Circuit<Fp> for AuditPlacementCircuit<S>
+    where
+        ShipLen<S>: ValidShipLen,
+    {
+        type Config = TestPlacementConfig<S>;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self(self.0.without_witnesses())
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> TestPlacementConfig<S> {
+            let mut config = StandalonePlacementCircuit::<S>::configure(meta);
+            let windows_checked = meta.advice_column();
+            config.placement = PlacementChip::<Fp, S>::configure_audit(
+                meta,
+                config.placement,
+                windows_checked,
+                config.instance,
+                2,
+            );
+            config
+        }
+
+        fn synthesize(
+            &self,
+            config: TestPlacementConfig<S>,
+            layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            self.0.synthesize(config, layouter)
+        }
+    }
+
+    // placement circuit checking bits decomposed by a separate num2bits chip via synthesize_bits_only
+    #[derive(Debug, Clone)]
+    struct BitsOnlyCircuit<const S: usize>
+    where
+        ShipLen<S>: ValidShipLen,
+    {
+        placement: BinaryValue,
+    }
+
+    impl<const S: usize> Circuit<Fp> for BitsOnlyCircuit<S>
+    where
+        ShipLen<S>: ValidShipLen,
+    {
+        type Config = TestPlacementConfig<S>;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> TestPlacementConfig<S> {
+            PlacementCircuit::<S>::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: TestPlacementConfig<S>,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let value = layouter.assign_region(
+                || "load placement",
+                |mut region: Region<Fp>| {
+                    region.assign_advice(
+                        || "placement",
+                        config.advice[0],
+                        0,
+                        || Value::known(Fp::from_u128(self.placement.lower_u128())),
+                    )
+                },
+            )?;
+            let bits = Num2BitsChip::<Fp, BOARD_SIZE>::new(
+                value,
+                self.placement.bitfield::<Fp, BOARD_SIZE>(),
+            )
+            .synthesize(config.num2bits[0], layouter.namespace(|| "num2bits"))?;
+            let chip = PlacementChip::<Fp, S>::new(config.placement);
+            let trace = compute_placement_trace::<Fp, S>(self.placement);
+            chip.synthesize_bits_only(&mut layouter, bits, trace)
+        }
+    }
+
+    #[derive(Clone, Copy, Debug)]
+    struct PinnedBitsConfig {
+        test: TestPlacementConfig<3>,
+        expected: Column<Fixed>,
+        pin: Selector,
+    }
+
+    // permutes num2bits output into the trace region and pins advice[1] to the expected bits
+    #[derive(Debug, Clone)]
+    struct PinnedBitsCircuit {
+        placement: BinaryValue,
+        offset: usize, // row the first bit is expected at (padding row puts it at 1)
+    }
+
+    impl Circuit<Fp> for PinnedBitsCircuit {
+        type Config = PinnedBitsConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> PinnedBitsConfig {
+            let test = PlacementCircuit::<3>::configure(meta);
+            let expected = meta.fixed_column();
+            let pin = meta.selector();
+            meta.create_gate("pinned permuted bit", |meta| {
+                let bit = meta.query_advice(test.placement.bits, Rotation::cur());
+                let expected = meta.query_fixed(expected, Rotation::cur());
+                let pin = meta.query_selector(pin);
+                Constraints::with_selector(pin, [("Bit at expected row", bit - expected)])
+            });
+            PinnedBitsConfig {
+                test,
+                expected,
+                pin,
+            }
+        }
+
+        fn synthesize(
+            &self,
+            config: PinnedBitsConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let value = layouter.assign_region(
+                || "load placement",
+                |mut region: Region<Fp>| {
+                    region.assign_advice(
+                        || "placement",
+                        config.test.advice[0],
+                        0,
+                        || Value::known(Fp::from_u128(self.placement.lower_u128())),
+                    )
+                },
+            )?;
+            let bits = Num2BitsChip::<Fp, BOARD_SIZE>::new(
+                value,
+                self.placement.bitfield::<Fp, BOARD_SIZE>(),
+            )
+            .synthesize(config.test.num2bits[0], layouter.namespace(|| "num2bits"))?;
+            let expected = self.placement.bitfield::<Fp, BOARD_SIZE>();
+            layouter.assign_region(
+                || "placement running sum trace",
+                |mut region: Region<Fp>| {
+                    let placement = &config.test.placement;
+                    let mut state = PlacementState::assign_padding_row(&mut region, placement)?;
+                    let permuted = state.permute_bits2num(&bits, &mut region, placement)?;
+                    for (i, bit) in expected.iter().enumerate() {
+                        config.pin.enable(&mut region, i + self.offset)?;
+                        region.assign_fixed(
+                            || format!("expected bit {}", i),
+                            config.expected,
+                            i + self.offset,
+                            || Value::known(*bit),
+                        )?;
+                        // the permuted cell carries the same witness as its bits2num source
+                        bits[i]
+                            .value()
+                            .zip(permuted[i].value())
+                            .assert_if_known(|(source, copy)| source == copy);
+                    }
+                    Ok(())
+                },
+            )
+        }
+    }
+
+    // placement circuit driven from a ship via synthesize_ship
+    #[derive(Debug, Clone)]
+    struct ShipCircuit<const S: usize>
+    where
+        ShipLen<S>: ValidShipLen,
+    {
+        ship: Ship,
+    }
+
+    impl<const S: usize> Circuit<Fp> for ShipCircuit<S>
+    where
+        ShipLen<S>: ValidShipLen,
+    {
+        type Config = TestPlacementConfig<S>;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> TestPlacementConfig<S> {
+            PlacementCircuit::<S>::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: TestPlacementConfig<S>,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = PlacementChip::<Fp, S>::new(config.placement);
+            let commitments = chip.synthesize_ship(
+                &mut layouter,
+                config.num2bits,
+                self.ship,
+                PlacementGadget::from_ship(self.ship),
+            )?;
+            chip.expose_commitment(&mut layouter, &commitments[0], config.instance, 0)?;
+            chip.expose_commitment(&mut layouter, &commitments[1], config.instance, 1)?;
+            Ok(())
+        }
+    }
+
+    // malicious layout enabling the final trace constraint on a zero padding row
+    #[derive(Debug, Clone)]
+    struct PaddingConstraintCircuit<const S: usize>;
+
+    impl<const S: usize> Circuit<Fp> for PaddingConstraintCircuit<S>
+    where
+        ShipLen<S>: ValidShipLen,
+    {
+        type Config = TestPlacementConfig<S>;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> TestPlacementConfig<S> {
+            PlacementCircuit::<S>::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: TestPlacementConfig<S>,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            layouter.assign_region(
+                || "constrain padding row",
+                |mut region: Region<Fp>| {
+                    let _ = PlacementState::assign_padding_row(&mut region, &config.placement)?;
+                    region.assign_advice_from_constant(
+                        || "pad orientation",
+                        config.placement.bits,
+                        0,
+                        Fp::zero(),
+                    )?;
+                    config.placement.s_constrain.enable(&mut region, 0)?;
+                    Ok(())
+                },
+            )
+        }
+    }
+
+    // malicious layout overwriting the full window running sum padding cell with a nonzero value
+    #[derive(Debug, Clone)]
+    struct NonzeroPaddingCircuit<const S: usize>
+    where
+        ShipLen<S>: ValidShipLen,
+    {
+        ship: Ship,
+        padding: Fp,
+    }
+
+    impl<const S: usize> Circuit<Fp> for NonzeroPaddingCircuit<S>
+    where
+        ShipLen<S>: ValidShipLen,
+    {
+        type Config = TestPlacementConfig<S>;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> TestPlacementConfig<S> {
+            PlacementCircuit::<S>::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: TestPlacementConfig<S>,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let [horizontal, vertical] = self.ship.witness(WitnessOption::Default);
+            let gadget = PlacementGadget::<Fp, S>::new(horizontal.zip(vertical));
+            // load H, V bits directly (decomposition is covered by the num2bits tests)
+            let (h_bits, v_bits) = layouter.assign_region(
+                || "load H, V bits",
+                |mut region: Region<Fp>| {
+                    let mut assign = |bits: [Fp; BOARD_SIZE], column: Column<Advice>| {
+                        (0..BOARD_SIZE)
+                            .map(|i| {
+                                region.assign_advice(
+                                    || format!("bit {}", i),
+                                    column,
+                                    i,
+                                    || Value::known(bits[i]),
+                                )
+                            })
+                            .collect::<Result<Vec<AssignedCell<Fp, Fp>>, Error>>()
+                    };
+                    let h_bits = assign(horizontal.bitfield::<Fp, BOARD_SIZE>(), config.advice[0])?;
+                    let v_bits = assign(vertical.bitfield::<Fp, BOARD_SIZE>(), config.advice[1])?;
+                    Ok((h_bits, v_bits))
+                },
+            )?;
+            let h_bits: AssignedBits<Fp> = h_bits.try_into().unwrap();
+            let v_bits: AssignedBits<Fp> = v_bits.try_into().unwrap();
+            let orientation = placement_orientation(&v_bits);
+            let chip = PlacementChip::<Fp, S>::new(config.placement);
+            let bits = chip.load_bits(&mut layouter, gadget.bits, h_bits, v_bits)?;
+            // shift the full window running sum up by the padding so every gate still holds
+            let mut trace = gadget.trace;
+            for sum in trace[1].iter_mut() {
+                *sum += self.padding;
+            }
+            let state = layouter.assign_region(
+                || "placement running sum trace",
+                |mut region: Region<Fp>| {
+                    let mut state =
+                        PlacementState::assign_padding_row(&mut region, &config.placement)?;
+                    // overwrite the constant-bound padding value
+                    region.assign_advice(
+                        || "malicious full bit window padding",
+                        config.placement.full_window_sum,
+                        0,
+                        || Value::known(self.padding),
+                    )?;
+                    let _ = state.permute_bits2num(&bits, &mut region, &config.placement)?;
+                    state.assign_running_sum_trace(&mut region, &config.placement, &trace)
+                },
+            )?;
+            chip.assign_constraint(&mut layouter, state, orientation)
+        }
+    }
+
+    /**
+     * Compute the public instance values exported by the test placement circuit
+     *
+     * @param circuit - the test circuit to export H and V commitments from
+     * @return - public instance column values [H, V]
+     */
+    fn commitments<const S: usize>(circuit: &PlacementCircuit<S>) -> Vec<Fp>
+    where
+        ShipLen<S>: ValidShipLen,
+    {
+        vec![
+            Fp::from_u128(circuit.horizontal.lower_u128()),
+            Fp::from_u128(circuit.vertical.lower_u128()),
+        ]
+    }
+
+    #[test]
+    fn valid_exposed_commitment() {
+        // cruiser placed horizontally at x: 2, y: 3
+        let ship = Ship::new(ShipType::Cruiser, 2, 3, false);
+        let circuit = PlacementCircuit::<3>::from_ship(ship, WitnessOption::Default);
+        let instance = commitments(&circuit);
+        let prover = MockProver::run(CIRCUIT_SIZE, &circuit, vec![instance]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    #[test]
+    fn valid_placement_from_values() {
+        // battleship placed vertically at x: 6, y: 2
+        let ship = Ship::new(ShipType::Battleship, 6, 2, true);
+        let circuit = StandalonePlacementCircuit::<4>::new(ship);
+        let instance = commitments(&PlacementCircuit::<4>::from_ship(
+            ship,
+            WitnessOption::Default,
+        ));
+        let prover = MockProver::run(CIRCUIT_SIZE, &circuit, vec![instance]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    #[test]
+    fn invalid_zero_commitment() {
+        // omitted ship: no placement in either orientation
+        let circuit = NonzeroPlacementCircuit::<4>(StandalonePlacementCircuit {
+            horizontal: Value::known(Fp::zero()),
+            vertical: Value::known(Fp::zero()),
+            gadget: PlacementGadget::new(BinaryValue::empty()),
+        });
+        let prover = MockProver::run(CIRCUIT_SIZE, &circuit, vec![vec![Fp::zero(); 2]]).unwrap();
+        let failures = prover
+            .verify()
+            .unwrap_err()
+            .into_iter()
+            .filter_map(|failure| match failure {
+                VerifyFailure::ConstraintNotSatisfied {
+                    constraint,
+                    location,
+                    ..
+                } => Some((constraint, location)),
+                _ => None,
+            })
+            .collect::<Vec<(metadata::Constraint, FailureLocation)>>();
+        // rejected on the load row, not only by the output constraints after the trace
+        let expected: (metadata::Constraint, FailureLocation) = (
+            (
+                (7, "nonzero placement commitment").into(),
+                0,
+                "Placement commitment is nonzero",
+            )
+                .into(),
+            FailureLocation::InRegion {
+                region: (0, "load placement commitments").into(),
+                offset: 0,
+            },
+        );
+        assert!(failures.contains(&expected), "{:?}", failures);
+        // valid placements still pass the nonzero gate
+        let ship = Ship::new(ShipType::Battleship, 6, 2, true);
+        let circuit = NonzeroPlacementCircuit::<4>(StandalonePlacementCircuit::new(ship));
+        let instance = commitments(&PlacementCircuit::<4>::from_ship(
+            ship,
+            WitnessOption::Default,
+        ));
+        let prover = MockProver::run(CIRCUIT_SIZE, &circuit, vec![instance]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    #[test]
+    fn valid_placement_from_ship() {
+        for z in [false, true] {
+            // submarine placed at x: 4, y: 1 in either orientation
+            let ship = Ship::new(ShipType::Submarine, 4, 1, z);
+            let instance = commitments(&PlacementCircuit::<3>::from_ship(
+                ship,
+                WitnessOption::Default,
+            ));
+            // only the commitment for the ship's orientation is nonzero
+            assert_ne!(instance[z as usize], Fp::zero());
+            assert_eq!(instance[!z as usize], Fp::zero());
+            let prover =
+                MockProver::run(CIRCUIT_SIZE, &ShipCircuit::<3> { ship }, vec![instance]).unwrap();
+            assert_eq!(prover.verify(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn invalid_ship_length_from_ship() {
+        // a destroyer cannot be synthesized by a cruiser length placement chip
+        let ship = Ship::new(ShipType::Destroyer, 4, 1, false);
+        assert!(matches!(
+            MockProver::run(CIRCUIT_SIZE, &ShipCircuit::<3> { ship }, vec![vec![]]),
+            Err(Error::Synthesis)
+        ));
+    }
+
+    #[test]
+    fn invalid_gadget_bits_from_values() {
+        // battleship placed vertically at x: 6, y: 2
+        let ship = Ship::new(ShipType::Battleship, 6, 2, true);
+        let mut circuit = StandalonePlacementCircuit::<4>::new(ship);
+        // witness bits (and a consistent trace) for the battleship one row up instead
+        circuit.gadget = PlacementGadget::from_ship(Ship::new(ShipType::Battleship, 6, 3, true));
+        let instance = commitments(&PlacementCircuit::<4>::from_ship(
+            ship,
+            WitnessOption::Default,
+        ));
+        let prover = MockProver::run(CIRCUIT_SIZE, &circuit, vec![instance]).unwrap();
+        // collapsed bits (advice 0) differ from the committed V decomposition (advice 2) at
+        // transposed bits 62 (only committed) and 66 (only witnessed)
+        let failure =
+            |offset: usize, sum: &str, vertical: &str| VerifyFailure::ConstraintNotSatisfied {
+                constraint: ((2, "sum inputted H, V bits").into(), 0, "h + v = sum").into(),
+                location: FailureLocation::InRegion {
+                    region: (3, "permute and collapse bit decompositions").into(),
+                    offset,
+                },
+                cell_values: vec![
+                    (((Any::Advice, 0).into(), 0).into(), String::from(sum)),
+                    (((Any::Advice, 1).into(), 0).into(), String::from("0")),
+                    (((Any::Advice, 2).into(), 0).into(), String::from(vertical)),
+                ],
+            };
+        assert_eq!(
+            prover.verify(),
+            Err(vec![failure(62, "0", "1"), failure(66, "1", "0")])
+        );
+    }
+
+    #[test]
+    fn invalid_exposed_commitment() {
+        // cruiser placed horizontally at x: 2, y: 3
+        let ship = Ship::new(ShipType::Cruiser, 2, 3, false);
+        let circuit = PlacementCircuit::<3>::from_ship(ship, WitnessOption::Default);
+        // publish a commitment to the cruiser placed at x: 3, y: 3 instead
+        let other = Ship::new(ShipType::Cruiser, 3, 3, false);
+        let instance = commitments(&PlacementCircuit::<3>::from_ship(
+            other,
+            WitnessOption::Default,
+        ));
+        let prover = MockProver::run(CIRCUIT_SIZE, &circuit, vec![instance]).unwrap();
+        // expect the copy constraint between H commitment and public instance to fail
+        assert_eq!(
+            prover.verify(),
+            Err(vec![
+                VerifyFailure::Permutation {
+                    column: metadata::Column::from((Any::Advice, 0)),
+                    location: FailureLocation::InRegion {
+                        region: (0, "load ship commitments").into(),
+                        offset: 0
+                    }
+                },
+                VerifyFailure::Permutation {
+                    column: metadata::Column::from((Any::Instance, 0)),
+                    location: FailureLocation::OutsideRegion { row: 0 }
+                }
+            ])
+        );
+    }
+
+    /**
+     * Mock prove a placement and compare the result against the off-circuit simulator
+     *
+     * @param ship - the ship to place
+     * @param option - the witness generation option applied to the placement
+     * @return - the simulated outcome and whether the mock prover accepted the placement
+     */
+    fn differential<const S: usize>(ship: Ship, option: WitnessOption) -> (PlacementOutcome, bool)
+    where
+        ShipLen<S>: ValidShipLen,
+    {
+        let circuit = PlacementCircuit::<S>::from_ship(ship, option);
+        let outcome = simulate_placement::<S>([circuit.horizontal, circuit.vertical]);
+        // simulated running sums match the trace the chip witnesses
+        let gadget = PlacementGadget::<Fp, S>::new(circuit.horizontal.zip(circuit.vertical));
+        assert_eq!(gadget.trace[0][BOARD_SIZE - 1], Fp::from(outcome.bit_sum));
+        assert_eq!(
+            gadget.trace[1][BOARD_SIZE - 1],
+            Fp::from(outcome.full_window_sum)
+        );
+        let instance = commitments(&circuit);
+        let prover = MockProver::run(CIRCUIT_SIZE, &circuit, vec![instance]).unwrap();
+        (outcome, prover.verify().is_ok())
+    }
+
+    /**
+     * Strategy producing any ship type at any head on the board with any witness option
+     * @dev placements may wrap rows; only placements that would index off the board are filtered
+     */
+    fn arb_placement() -> impl Strategy<Value = (Ship, WitnessOption)> {
+        let ship_types = [
+            ShipType::Carrier,
+            ShipType::Battleship,
+            ShipType::Cruiser,
+            ShipType::Submarine,
+            ShipType::Destroyer,
+        ];
+        let options = [
+            WitnessOption::Default,
+            WitnessOption::DualPlacement,
+            WitnessOption::Nonconsecutive,
+            WitnessOption::ExtraBit,
+            WitnessOption::Oversized,
+            WitnessOption::Undersized,
+        ];
+        (
+            0..ship_types.len(),
+            0..10u8,
+            0..10u8,
+            any::<bool>(),
+            0..options.len(),
+        )
+            .prop_map(move |(i, x, y, z, option)| {
+                (Ship::new(ship_types[i], x, y, z), options[option])
+            })
+            // witness options may flip the bit after the ship's last bit
+            .prop_filter("placement indexes off board", |(ship, _)| {
+                ship.coordinates(true).iter().all(|c| c + 1 < BOARD_SIZE)
+            })
+    }
+
+    /**
+     * Check every step of a placement's running sum traces
+     * @dev field elements have no order, so non-decreasing is checked as each step adding 0 or 1
+     *
+     * @param ship - the ship to place
+     * @param option - the witness generation option applied to the placement
+     * @return - the first (trace, row) stepping by anything other than 0 or 1, if any
+     */
+    fn non_monotonic_step<const S: usize>(
+        ship: Ship,
+        option: WitnessOption,
+    ) -> Option<(usize, usize)>
+    where
+        ShipLen<S>: ValidShipLen,
+    {
+        let [horizontal, vertical] = ship.witness(option);
+        let gadget = PlacementGadget::<Fp, S>::new(horizontal.zip(vertical));
+        (0..2).find_map(|trace| {
+            (1..BOARD_SIZE)
+                .find(|i| {
+                    let step = gadget.trace[trace][*i] - gadget.trace[trace][i - 1];
+                    step != Fp::zero() && step != Fp::one()
+                })
+                .map(|i| (trace, i))
+        })
+    }
+
+    /**
+     * Check the placement simulator and the mock proven chip agree on a placement
+     *
+     * @param ship - the ship to place
+     * @param option - the witness generation option applied to the placement
+     * @return - Ok if the simulator accepts exactly when the chip verifies
+     */
+    fn check_differential(ship: Ship, option: WitnessOption) -> Result<(), TestCaseError> {
+        let (outcome, verified) = match ship.ship_type.length() {
+            5 => differential::<5>(ship, option),
+            4 => differential::<4>(ship, option),
+            3 => differential::<3>(ship, option),
+            _ => differential::<2>(ship, option),
+        };
+        prop_assert_eq!(outcome.accepted(), verified, "{:?} {:?}", ship, outcome);
+        Ok(())
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(1000))]
+
+        #[test]
+        fn trace_monotonic((ship, option) in arb_placement()) {
+            let step = match ship.ship_type.length() {
+                5 => non_monotonic_step::<5>(ship, option),
+                4 => non_monotonic_step::<4>(ship, option),
+                3 => non_monotonic_step::<3>(ship, option),
+                _ => non_monotonic_step::<2>(ship, option),
+            };
+            prop_assert_eq!(step, None, "{:?} {:?}", ship, option);
+        }
+
+        #[test]
+        #[ignore] // slow: mock proves every case. run with `cargo test -- --ignored`
+        fn simulator_matches_chip_exhaustive((ship, option) in arb_placement()) {
+            check_differential(ship, option)?;
+        }
+    }
+
+    proptest! {
+        // each case mock proves a placement circuit, so only a sample runs by default
+        #![proptest_config(ProptestConfig::with_cases(48))]
+
+        #[test]
+        fn simulator_matches_chip((ship, option) in arb_placement()) {
+            check_differential(ship, option)?;
+        }
+    }
+
+    #[test]
+    fn simulate_valid_placement() {
+        let ship = Ship::new(ShipType::Battleship, 3, 4, true);
+        let outcome = simulate_placement::<4>(ship.witness(WitnessOption::Default));
+        assert_eq!(
+            outcome,
+            PlacementOutcome {
+                bit_sum: 4,
+                full_window_sum: 1,
+                correct_length: true,
+                boolean_orientation: true,
+                horizontal_window: true,
+                vertical_window: true,
+            }
+        );
+    }
+
+    #[test]
+    fn simulate_row_overflow() {
+        // horizontal cruiser at x: 8 wraps onto the next row
+        let ship = Ship::new(ShipType::Cruiser, 8, 3, false);
+        let outcome = simulate_placement::<3>(ship.witness(WitnessOption::Default));
+        assert_eq!(outcome.full_window_sum, 0);
+        assert!(!outcome.horizontal_window);
+        assert!(outcome.vertical_window);
+        assert!(!outcome.accepted());
+    }
+
+    #[test]
+    fn verify_placement_accepts_gadget_bits() {
+        for ship in [
+            Ship::new(ShipType::Carrier, 0, 0, false),
+            Ship::new(ShipType::Battleship, 3, 4, true),
+            Ship::new(ShipType::Destroyer, 8, 9, false),
+        ] {
+            let [horizontal, vertical] = ship.witness(WitnessOption::Default);
+            let placement = horizontal.zip(vertical);
+            let result = match ship.ship_type.length() {
+                5 => verify_placement::<Fp, 5>(
+                    placement,
+                    PlacementGadget::<Fp, 5>::new(placement).bits,
+                ),
+                4 => verify_placement::<Fp, 4>(
+                    placement,
+                    PlacementGadget::<Fp, 4>::new(placement).bits,
+                ),
+                _ => verify_placement::<Fp, 2>(
+                    placement,
+                    PlacementGadget::<Fp, 2>::new(placement).bits,
+                ),
+            };
+            assert_eq!(result, Ok(()));
+        }
+    }
+
+    #[test]
+    fn verify_placement_rejects_tampered_bits() {
+        // destroyer placed horizontally at x: 0, y: 0 occupies bits 0 and 1
+        let ship = Ship::new(ShipType::Destroyer, 0, 0, false);
+        let [horizontal, vertical] = ship.witness(WitnessOption::Default);
+        let placement = horizontal.zip(vertical);
+        let gadget = PlacementGadget::<Fp, 2>::new(placement);
+
+        // unset a bit of the ship
+        let mut bits = gadget.bits;
+        bits[1] = Fp::zero();
+        assert_eq!(
+            verify_placement::<Fp, 2>(placement, bits),
+            Err(vec![
+                PlacementViolation::Composition,
+                PlacementViolation::IncorrectLength,
+                PlacementViolation::FullWindowCount,
+            ])
+        );
+
+        // 3 * 2^0 + 0 * 2^1 still recomposes to the commitment, so only booleanity catches it
+        let mut bits = gadget.bits;
+        bits[0] = Fp::from(3);
+        bits[1] = Fp::zero();
+        assert_eq!(
+            verify_placement::<Fp, 2>(placement, bits),
+            Err(vec![
+                PlacementViolation::NonBooleanBit(0),
+                PlacementViolation::IncorrectLength,
+                PlacementViolation::FullWindowCount,
+            ])
+        );
+
+        // bits honestly decomposed from a ship wrapping onto the next row
+        let ship = Ship::new(ShipType::Destroyer, 9, 6, false);
+        let [horizontal, vertical] = ship.witness(WitnessOption::Default);
+        let placement = horizontal.zip(vertical);
+        assert_eq!(
+            verify_placement::<Fp, 2>(placement, PlacementGadget::<Fp, 2>::new(placement).bits),
+            Err(vec![PlacementViolation::FullWindowCount])
+        );
+    }
+
+    /**
+     * Round trip every on-board placement of a ship type through to_mask/ from_mask
+     *
+     * @param ship_type - the type of ship (of length S) to place
+     */
+    fn mask_round_trip<const S: usize>(ship_type: ShipType)
+    where
+        ShipLen<S>: ValidShipLen,
+    {
+        for z in [false, true] {
+            for head in 0..(11 - S) * 10 {
+                // head runs along the ship's axis so every placement stays on the board
+                let (x, y) = match z {
+                    true => (head / (11 - S), head % (11 - S)),
+                    false => (head % (11 - S), head / (11 - S)),
+                };
+                let ship = Ship::new(ship_type, x as u8, y as u8, z);
+                assert_eq!(
+                    PlacementGadget::<Fp, S>::from_mask(ship.to_mask()),
+                    Ok(PlacementGadget::<Fp, S>::from_ship(ship))
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "debug-advice")]
+    #[test]
+    fn advice_grid_matches_trace() {
+        let ship = Ship::new(ShipType::Battleship, 6, 2, true);
+        let circuit = PlacementCircuit::<4>::from_ship(ship, WitnessOption::Default);
+        let config = PlacementCircuit::<4>::configure(&mut ConstraintSystem::default());
+        let placement = config.placement;
+        let grid = crate::utils::debug::advice_grid(
+            &circuit,
+            config,
+            vec![placement.fixed],
+            "placement running sum trace",
+            &[placement.bits, placement.bit_sum, placement.full_window_sum],
+        )
+        .unwrap();
+        // padding row followed by one row per board cell
+        assert_eq!(grid.len(), BOARD_SIZE + 1);
+        assert_eq!(grid[0], vec![Fp::zero(); 3]);
+        let gadget = PlacementGadget::<Fp, 4>::from_ship(ship);
+        for (i, row) in grid[1..].iter().enumerate() {
+            assert_eq!(
+                row,
+                &vec![gadget.bits[i], gadget.trace[0][i], gadget.trace[1][i]]
+            );
+        }
+    }
+
+    #[test]
+    fn check_invariants_corrupted_gadget() {
+        let gadget =
+            PlacementGadget::<Fp, 4>::from_ship(Ship::new(ShipType::Battleship, 6, 2, true));
+        assert_eq!(gadget.check_invariants(), Ok(()));
+        // running sum ends one short of the ship length
+        let mut corrupted = gadget;
+        corrupted.trace[0][BOARD_SIZE - 1] -= Fp::one();
+        assert_eq!(corrupted.check_invariants(), Err(GadgetError::BitSum));
+        // no full bit window counted
+        let mut corrupted = gadget;
+        corrupted.trace[1][BOARD_SIZE - 1] = Fp::zero();
+        assert_eq!(
+            corrupted.check_invariants(),
+            Err(GadgetError::FullWindowSum)
+        );
+        // a bit set outside of the ship that the traces do not account for
+        let mut corrupted = gadget;
+        corrupted.bits[0] = Fp::one();
+        assert_eq!(corrupted.check_invariants(), Err(GadgetError::BitCount(5)));
+    }
+
+    #[test]
+    fn gadget_from_ship_into() {
+        let ship = Ship::new(ShipType::Cruiser, 2, 3, true);
+        let gadget: PlacementGadget<Fp, 3> = ship.into();
+        assert_eq!(gadget, PlacementGadget::from_ship(ship));
+        let [horizontal, vertical] = ship.witness(WitnessOption::Default);
+        assert_eq!(gadget, PlacementGadget::new(horizontal.zip(vertical)));
+    }
+
+    #[test]
+    fn gadget_bits_match_mask_endianness() {
+        // corners, edges and the middle of the board in both orientations
+        let ships = [
+            Ship::new(ShipType::Carrier, 0, 0, false),
+            Ship::new(ShipType::Carrier, 0, 0, true),
+            Ship::new(ShipType::Battleship, 6, 9, false),
+            Ship::new(ShipType::Battleship, 9, 6, true),
+            Ship::new(ShipType::Cruiser, 7, 0, false),
+            Ship::new(ShipType::Submarine, 0, 7, true),
+            Ship::new(ShipType::Destroyer, 4, 5, false),
+            Ship::new(ShipType::Destroyer, 4, 5, true),
+        ];
+        for ship in ships {
+            let bits = match ship.ship_type.length() {
+                5 => PlacementGadget::<Fp, 5>::from_ship(ship).bits,
+                4 => PlacementGadget::<Fp, 4>::from_ship(ship).bits,
+                3 => PlacementGadget::<Fp, 3>::from_ship(ship).bits,
+                _ => PlacementGadget::<Fp, 2>::from_ship(ship).bits,
+            };
+            // bit y * 10 + x of the mask is cell (x, y); vertical gadget bits are transposed
+            let mask = ship.to_mask().bitfield::<Fp, BOARD_SIZE>();
+            for (i, bit) in bits.iter().enumerate() {
+                let cell = if ship.z { i % 10 * 10 + i / 10 } else { i };
+                assert_eq!(*bit, mask[cell], "bit #{} of {:?}", i, ship);
+            }
+            assert_eq!(
+                bits.iter().filter(|bit| **bit == Fp::one()).count(),
+                ship.ship_type.length()
+            );
+        }
+    }
+
+    #[test]
+    fn window_incrementor_selects_full_window() {
+        let incrementor = window_incrementor::<Fp, 5>();
+        assert_eq!(incrementor.len(), 6);
+        for bit_count in 0..=5u64 {
+            let expected = Fp::from((bit_count == 5) as u64);
+            assert_eq!(eval_polynomial(&incrementor, Fp::from(bit_count)), expected);
+        }
+    }
+
+    #[test]
+    fn supported_ship_lengths() {
+        // the smallest and largest lengths bounded by ValidShipLen, placed along the first row
+        let single = crate::utils::shot::serialize_cells(&[(4, 0)]);
+        let full_row =
+            crate::utils::shot::serialize_cells(&(0..10).map(|x| (x, 0)).collect::<Vec<_>>());
+        assert_eq!(
+            PlacementGadget::<Fp, 1>::new(single).check_invariants(),
+            Ok(())
+        );
+        assert_eq!(
+            PlacementGadget::<Fp, 10>::new(full_row).check_invariants(),
+            Ok(())
+        );
+        assert!(simulate_placement::<1>([single, BinaryValue::empty()]).accepted());
+        assert!(simulate_placement::<10>([full_row, BinaryValue::empty()]).accepted());
+    }
+
+    #[test]
+    fn get_window_bounds() {
+        let bits =
+            PlacementGadget::<Fp, 2>::from_ship(Ship::new(ShipType::Destroyer, 8, 9, false)).bits;
+        assert_eq!(get_window::<Fp, 2>(&bits, 98), Ok([Fp::one(); 2]));
+        assert_eq!(get_window::<Fp, 2>(&bits, 97), Ok([Fp::zero(), Fp::one()]));
+        // wraps from the end of a row onto the next
+        assert_eq!(
+            get_window::<Fp, 2>(&bits, 99),
+            Err(GadgetError::WindowOutOfBounds(99))
+        );
+        // passes the row check (100 % 10 + 2 <= 10) but lies past the last row
+        assert_eq!(
+            get_window::<Fp, 2>(&bits, 100),
+            Err(GadgetError::WindowOutOfBounds(100))
+        );
+    }
+
+    #[test]
+    fn from_mask_round_trip() {
+        mask_round_trip::<5>(ShipType::Carrier);
+        mask_round_trip::<4>(ShipType::Battleship);
+        mask_round_trip::<3>(ShipType::Cruiser);
+        mask_round_trip::<2>(ShipType::Destroyer);
+    }
+
+    /**
+     * Mock prove every on-board placement of a ship type through the placement chip
+     *
+     * @param ship_type - the type of ship (of length S) to place
+     */
+    fn prove_every_placement<const S: usize>(ship_type: ShipType)
+    where
+        ShipLen<S>: ValidShipLen,
+    {
+        for z in [false, true] {
+            for x in 0..10 {
+                for y in 0..10 {
+                    let ship = Ship::new(ship_type, x, y, z);
+                    if (if z { y } else { x }) as usize + S > 10 {
+                        continue;
+                    }
+                    let circuit = PlacementCircuit::<S>::from_ship(ship, WitnessOption::Default);
+                    let instance = commitments(&circuit);
+                    let prover = MockProver::run(CIRCUIT_SIZE, &circuit, vec![instance]).unwrap();
+                    assert_eq!(prover.verify(), Ok(()), "{:?}", ship);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[ignore] // slow: run with `cargo test -- --ignored`
+    fn valid_every_placement() {
+        prove_every_placement::<5>(ShipType::Carrier);
+        prove_every_placement::<4>(ShipType::Battleship);
+        prove_every_placement::<3>(ShipType::Cruiser);
+        prove_every_placement::<3>(ShipType::Submarine);
+        prove_every_placement::<2>(ShipType::Destroyer);
+    }
+
+    #[test]
+    fn single_cell_orientation_canonical() {
+        // a length 1 ship commits the same placement whichever orientation it is given
+        for (x, y) in [(0, 0), (4, 0), (2, 7), (9, 9)] {
+            let horizontal = placement_commitments::<1>(x, y, false);
+            assert_eq!(placement_commitments::<1>(x, y, true), horizontal);
+            assert_eq!(
+                PlacementGadget::<Fp, 1>::from_head(x, y, true),
+                PlacementGadget::<Fp, 1>::from_head(x, y, false)
+            );
+            let circuit = PlacementCircuit::<1>::new(horizontal[0], horizontal[1]);
+            let prover = MockProver::run(CIRCUIT_SIZE, &circuit, vec![commitments(&circuit)]);
+            assert_eq!(prover.unwrap().verify(), Ok(()), "cell ({}, {})", x, y);
+        }
+        // longer ships keep their orientation and match the ship witness
+        let ship = Ship::new(ShipType::Cruiser, 2, 3, true);
+        assert_eq!(
+            placement_commitments::<3>(2, 3, true),
+            ship.witness(WitnessOption::Default)
+        );
+        assert_eq!(
+            PlacementGadget::<Fp, 3>::from_head(2, 3, true),
+            PlacementGadget::from_ship(ship)
+        );
+    }
+
+    #[test]
+    fn from_mask_single_cell() {
+        for cell in 0..BOARD_SIZE {
+            let mut mask = BinaryValue::empty();
+            mask.value.set(cell, true);
+            let gadget = PlacementGadget::<Fp, 1>::from_mask(mask).unwrap();
+            assert_eq!(gadget, PlacementGadget::<Fp, 1>::new(mask), "cell {}", cell);
+            assert_eq!(gadget.check_invariants(), Ok(()), "cell {}", cell);
+        }
+    }
+
+    #[test]
+    fn from_mask_invalid() {
+        let mask = |cells: &[usize]| {
+            let mut mask = BinaryValue::empty();
+            for cell in cells {
+                mask.value.set(*cell, true);
+            }
+            mask
+        };
+        for (cells, error) in [
+            (vec![0, 1], MaskError::IncorrectLength(2)),
+            (vec![0, 1, 2, 3], MaskError::IncorrectLength(4)),
+            // gap in the middle of a row
+            (vec![0, 1, 3], MaskError::NonContiguous),
+            // wraps from the end of row 0 onto row 1
+            (vec![8, 9, 10], MaskError::NonContiguous),
+            // turns a corner
+            (vec![0, 10, 11], MaskError::NonContiguous),
+            (vec![0, 1, 120], MaskError::OffBoard),
+        ] {
+            assert_eq!(
+                PlacementGadget::<Fp, 3>::from_mask(mask(&cells)),
+                Err(error)
+            );
+        }
+    }
+
+    #[test]
+    fn padding_row_fails_trace_constraint() {
+        let prover =
+            MockProver::run(CIRCUIT_SIZE, &PaddingConstraintCircuit::<4>, vec![vec![]]).unwrap();
+        // padded zeros count no bits (0 - S != 0) and no full bit window (0 - 1 != 0)
+        assert_eq!(
+            prover.verify(),
+            Err(vec![
+                VerifyFailure::ConstraintNotSatisfied {
+                    constraint: (
+                        (6, "running sum constraints").into(),
+                        0,
+                        "Placed ship of correct length",
+                    )
+                        .into(),
+                    location: FailureLocation::InRegion {
+                        region: (0, "constrain padding row").into(),
+                        offset: 0,
+                    },
+                    cell_values: vec![(((Any::Advice, 1).into(), 0).into(), String::from("0"))],
+                },
+                VerifyFailure::ConstraintNotSatisfied {
+                    constraint: (
+                        (6, "running sum constraints").into(),
+                        2,
+                        "One full horizontal bit window",
+                    )
+                        .into(),
+                    location: FailureLocation::InRegion {
+                        region: (0, "constrain padding row").into(),
+                        offset: 0,
+                    },
+                    cell_values: vec![
+                        (((Any::Advice, 0).into(), 0).into(), String::from("0")),
+                        (((Any::Advice, 2).into(), 0).into(), String::from("0")),
+                    ],
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn nonzero_padding_breaks_constant_binding() {
+        // destroyer wrapping from row 6 to row 7 has no full bit window
+        let ship = Ship::new(ShipType::Destroyer, 9, 6, false);
+        // honest zero padding: the running sum counts no full window
+        let circuit = NonzeroPaddingCircuit::<2> {
+            ship,
+            padding: Fp::zero(),
+        };
+        let prover = MockProver::run(CIRCUIT_SIZE, &circuit, vec![vec![]]).unwrap();
+        assert!(prover.verify().is_err());
+        // padding of 1 satisfies every running sum gate, so only the copy to the fixed zero catches it
+        let circuit = NonzeroPaddingCircuit::<2> {
+            ship,
+            padding: Fp::one(),
+        };
+        let prover = MockProver::run(CIRCUIT_SIZE, &circuit, vec![vec![]]).unwrap();
+        assert_eq!(
+            prover.verify(),
+            Err(vec![
+                VerifyFailure::Permutation {
+                    column: metadata::Column::from((Any::Advice, 2)),
+                    location: FailureLocation::InRegion {
+                        region: (2, "placement running sum trace").into(),
+                        offset: 0
+                    }
+                },
+                VerifyFailure::Permutation {
+                    column: metadata::Column::from((Any::Fixed, 0)),
+                    location: FailureLocation::OutsideRegion { row: 1 }
+                }
+            ])
+        );
+    }
+
+    /**
+     * Subscriber recording the name of every span opened while it is the default
+     */
+    #[cfg(feature = "trace")]
+    struct SpanRecorder {
+        spans: std::sync::Mutex<Vec<&'static str>>,
+    }
+
+    #[cfg(feature = "trace")]
+    impl tracing::Subscriber for SpanRecorder {
+        fn enabled(&self, _: &tracing::Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+            let mut spans = self.spans.lock().unwrap();
+            spans.push(span.metadata().name());
+            tracing::span::Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, _: &tracing::span::Id, _: &tracing::span::Record<'_>) {}
+
+        fn record_follows_from(&self, _: &tracing::span::Id, _: &tracing::span::Id) {}
+
+        fn event(&self, _: &tracing::Event<'_>) {}
+
+        fn enter(&self, _: &tracing::span::Id) {}
+
+        fn exit(&self, _: &tracing::span::Id) {}
+    }
+
+    #[cfg(feature = "trace")]
+    #[test]
+    fn trace_spans_cover_placement() {
+        let recorder = std::sync::Arc::new(SpanRecorder {
+            spans: std::sync::Mutex::new(Vec::new()),
+        });
+        let ship = Ship::new(ShipType::Cruiser, 2, 3, false);
+        let circuit = PlacementCircuit::<3>::from_ship(ship, WitnessOption::Default);
+        let instance = commitments(&circuit);
+        tracing::subscriber::with_default(recorder.clone(), || {
+            let prover = MockProver::run(CIRCUIT_SIZE, &circuit, vec![instance]).unwrap();
+            assert_eq!(prover.verify(), Ok(()));
+        });
+        let spans = recorder.spans.lock().unwrap();
+        for name in [
+            "synthesize",
+            "load_bits",
+            "placement_sums",
+            "assign_constraint",
+        ] {
+            assert!(spans.contains(&name), "missing span {}", name);
+        }
+    }
+
+    /**
+     * Return the max gate degree of a placement chip for ship length S
+     */
+    fn placement_degree<const S: usize>() -> usize
+    where
+        ShipLen<S>: ValidShipLen,
+    {
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let advice = [(); 3].map(|_| meta.advice_column());
+        let fixed = meta.fixed_column();
+        PlacementChip::<Fp, S>::configure(&mut meta, advice[0], advice[1], advice[2], fixed);
+        meta.degree()
+    }
+
+    #[test]
+    fn gate_degree_per_ship_length() {
+        // adjacency gate dominates with degree S + 1
+        assert_eq!(placement_degree::<2>(), 3);
+        assert_eq!(placement_degree::<3>(), 4);
+        assert_eq!(placement_degree::<4>(), 5);
+        assert_eq!(placement_degree::<5>(), 6);
+    }
+
+    /**
+     * Return the selectors of a placement chip for ship length S that no gate queries
+     * @dev CircuitGates renders selector queries as "S{index}", so index comes from the selector's Debug repr
+     */
+    fn unused_selectors<const S: usize>() -> Vec<&'static str>
+    where
+        ShipLen<S>: ValidShipLen,
+    {
+        let config = PlacementCircuit::<S>::configure(&mut ConstraintSystem::default()).placement;
+        // first line of the csv lists every query made by any gate in the circuit
+        let csv = CircuitGates::collect::<Fp, PlacementCircuit<S>>().queries_to_csv();
+        let queries = csv
+            .lines()
+            .next()
+            .unwrap()
+            .split(',')
+            .collect::<Vec<&str>>();
+        [
+            ("s_input", config.s_input),
+            ("s_sum_bits", config.s_sum_bits),
+            ("s_adjacency", config.s_adjacency),
+            ("s_permute", config.s_permute),
+            ("s_constrain", config.s_constrain),
+        ]
+        .into_iter()
+        .filter(|(_, selector)| {
+            let debug = format!("{:?}", selector);
+            let index = debug
+                .trim_start_matches("Selector(")
+                .split(',')
+                .next()
+                .unwrap();
+            !queries.contains(&format!("S{}", index).as_str())
+        })
+        .map(|(name, _)| name)
+        .collect()
+    }
+
+    #[test]
+    fn all_selectors_gated() {
+        // a selector no gate queries toggles nothing: its constraint silently vanished in a refactor
+        assert!(unused_selectors::<2>().is_empty());
+        assert!(unused_selectors::<3>().is_empty());
+        assert!(unused_selectors::<4>().is_empty());
+        assert!(unused_selectors::<5>().is_empty());
+    }
+
+    #[test]
+    fn circuit_size_rows() {
+        // row count (not gate degree) sets the minimum circuit size for every classic ship length
+        let ship = Ship::new(ShipType::Carrier, 2, 3, false);
+        let circuit = PlacementCircuit::<5>::from_ship(ship, WitnessOption::Default);
+        let instance = commitments(&circuit);
+        let prover = MockProver::run(CIRCUIT_SIZE, &circuit, vec![instance.clone()]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+        assert!(MockProver::run(CIRCUIT_SIZE - 1, &circuit, vec![instance]).is_err());
+        let ship = Ship::new(ShipType::Destroyer, 2, 3, false);
+        let circuit = PlacementCircuit::<2>::from_ship(ship, WitnessOption::Default);
+        let instance = commitments(&circuit);
+        assert!(MockProver::run(CIRCUIT_SIZE - 1, &circuit, vec![instance]).is_err());
+    }
+
+    /**
+     * Lay out the placement test circuit for a ship and check its row and selector budget
+     *
+     * @param ship - the ship of length S to place
+     * @return - the number of rows the layout touches
+     */
+    fn layout_budget<const S: usize>(ship: Ship) -> usize
+    where
+        ShipLen<S>: ValidShipLen,
+    {
+        let circuit = PlacementCircuit::<S>::from_ship(ship, WitnessOption::Default);
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let config = PlacementCircuit::<S>::configure(&mut meta);
+        let mut layout = LayoutRecorder::default();
+        layout
+            .synthesize(&circuit, config, vec![config.placement.fixed])
+            .unwrap();
+        // layout fits in the usable (unblinded) rows of a 2^CIRCUIT_SIZE circuit
+        assert!(layout.rows <= (1 << CIRCUIT_SIZE) - (meta.blinding_factors() + 1));
+        // every row of the trace enables exactly the expected selectors
+        let placement = config.placement;
+        for (selector, count) in [
+            (placement.s_input, BOARD_SIZE),
+            (placement.s_sum_bits, BOARD_SIZE),
+            (placement.s_adjacency, 10 * (11 - S)),
+            (placement.s_permute, 10 * (S - 1)),
+            (placement.s_constrain, 1),
+        ] {
+            assert_eq!(layout.selectors[&selector], count);
+        }
+        layout.rows
+    }
+
+    #[test]
+    fn layout_budget_per_ship_length() {
+        // the layout does not depend on S:
+        // 1 (commitments) + 2 * 101 (num2bits) + 100 (collapse bits) + 101 (running sums) + 1 (output)
+        let rows = [
+            layout_budget::<5>(Ship::new(ShipType::Carrier, 0, 0, false)),
+            layout_budget::<4>(Ship::new(ShipType::Battleship, 6, 2, true)),
+            layout_budget::<3>(Ship::new(ShipType::Cruiser, 2, 3, false)),
+            layout_budget::<2>(Ship::new(ShipType::Destroyer, 8, 9, false)),
+        ];
+        assert_eq!(rows, [405; 4]);
+    }
+
+    #[test]
+    fn bits_only_placement() {
+        // vertical battleship supplied already transposed into a row
+        let ship = Ship::new(ShipType::Battleship, 6, 2, true);
+        let circuit = BitsOnlyCircuit::<4> {
+            placement: ship.bits(true),
+        };
+        let prover = MockProver::run(CIRCUIT_SIZE, &circuit, vec![vec![]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+        // untransposed vertical bits are 4 cells but not one full row window
+        let circuit = BitsOnlyCircuit::<4> {
+            placement: ship.bits(false),
+        };
+        let prover = MockProver::run(CIRCUIT_SIZE, &circuit, vec![vec![]]).unwrap();
+        let failures = prover.verify().unwrap_err();
+        assert_eq!(failures.len(), 1);
+        assert!(failures[0]
+            .to_string()
+            .contains("One full horizontal bit window"));
+        // a destroyer's bits are too short for a battleship
+        let circuit = BitsOnlyCircuit::<4> {
+            placement: Ship::new(ShipType::Destroyer, 0, 0, false).bits(false),
+        };
+        let prover = MockProver::run(CIRCUIT_SIZE, &circuit, vec![vec![]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+    #[test]
+    fn permute_bits2num_offsets() {
+        // first and last bit set, plus an interior bit so no shift of the pattern matches itself
+        let mut grid = [[false; 10]; 10];
+        grid[0][0] = true;
+        grid[0][1] = true;
+        grid[9][9] = true;
+        let placement = BinaryValue::from_grid(&grid);
+        let circuit = PinnedBitsCircuit {
+            placement,
+            offset: 1,
+        };
+        let prover = MockProver::run(CIRCUIT_SIZE, &circuit, vec![vec![]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+        // pinning the bits one row early or late catches the first and last bit out of place
+        // @dev rows 0 and 101 are never copied to, so bit 0 / 99 expected there always fails
+        for (offset, rows) in [(0, vec![0, 2, 99]), (2, vec![3, 100, 101])] {
+            let circuit = PinnedBitsCircuit { placement, offset };
+            let prover = MockProver::run(CIRCUIT_SIZE, &circuit, vec![vec![]]).unwrap();
+            let failing = prover
+                .verify()
+                .unwrap_err()
+                .into_iter()
+                .filter_map(|failure| match failure {
+                    VerifyFailure::ConstraintNotSatisfied {
+                        location: FailureLocation::InRegion { offset, .. },
+                        ..
+                    } => Some(offset),
+                    _ => None,
+                })
+                .collect::<Vec<usize>>();
+            assert_eq!(failing, rows, "offset {}", offset);
+        }
+    }
+
+    #[test]
+    fn window_audit_instances() {
+        // cruiser placed vertically at x: 4, y: 1 (transposed into a single full row window)
+        let ship = Ship::new(ShipType::Cruiser, 4, 1, true);
+        let circuit = AuditPlacementCircuit::<3>(StandalonePlacementCircuit::new(ship));
+        let mut instance = commitments(&PlacementCircuit::<3>::from_ship(
+            ship,
+            WitnessOption::Default,
+        ));
+        // 8 windows fit in each of the 10 rows for a ship of length 3
+        assert_eq!(windows_checked::<3>(), 80);
+        instance.extend([Fp::one(), Fp::from(80)]);
+        let prover = MockProver::run(CIRCUIT_SIZE, &circuit, vec![instance.clone()]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+        // neither the full window sum nor the windows checked can be claimed differently
+        for row in [2, 3] {
+            let mut wrong = instance.clone();
+            wrong[row] += Fp::one();
+            let prover = MockProver::run(CIRCUIT_SIZE, &circuit, vec![wrong]).unwrap();
+            assert!(
+                prover.verify().is_err(),
+                "instance row {} is unconstrained",
+                row
+            );
+        }
+        // the count follows the window check rows of the ship length (9 per row for a destroyer)
+        let ship = Ship::new(ShipType::Destroyer, 8, 9, false);
+        let circuit = AuditPlacementCircuit::<2>(StandalonePlacementCircuit::new(ship));
+        let mut instance = commitments(&PlacementCircuit::<2>::from_ship(
+            ship,
+            WitnessOption::Default,
+        ));
+        instance.extend([Fp::one(), Fp::from(90)]);
+        let prover = MockProver::run(CIRCUIT_SIZE, &circuit, vec![instance]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    #[test]
+    fn min_k_matches_circuit_size() {
+        let ship = Ship::new(ShipType::Cruiser, 2, 3, false);
+        let circuit = PlacementCircuit::<3>::from_ship(ship, WitnessOption::Default);
+        let min_k = min_k(&circuit, |config: &TestPlacementConfig<3>| {
+            vec![config.placement.fixed]
+        })
+        .unwrap();
+        assert_eq!(
+            min_k,
+            empirical_min_k(&circuit, vec![commitments(&circuit)])
+        );
+        assert_eq!(min_k, CIRCUIT_SIZE);
+    }
+
+    #[test]
+    fn config_display_layout() {
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let advice = [(); 3].map(|_| meta.advice_column());
+        let fixed = meta.fixed_column();
+        let instance = meta.instance_column();
+        let config =
+            PlacementChip::<Fp, 4>::configure(&mut meta, advice[0], advice[1], advice[2], fixed);
+        let expected = [
+            "PlacementConfig (S = 4)",
+            "selector     | gate                       | advice queried",
+            "s_input      | sum inputted H, V bits     | bits[cur], bit_sum[cur], full_window_sum[cur]",
+            "s_sum_bits   | placement bit count        | bits[cur], bit_sum[prev], bit_sum[cur]",
+            "s_adjacency  | adjacency bit count        | bits[cur..cur+3], full_window_sum[prev], full_window_sum[cur]",
+            "s_permute    | permute adjaceny bit count | full_window_sum[prev], full_window_sum[cur]",
+            "s_constrain  | running sum constraints    | bits[cur], bit_sum[cur], full_window_sum[cur]",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(config.to_string(), expected);
+        // a config rejecting zero commitments also lists the nonzero gate (widening the gate column)
+        let nonzero = PlacementChip::<Fp, 4>::configure_nonzero(&mut meta, config);
+        assert_eq!(
+            nonzero.to_string().lines().nth(7),
+            Some("s_nonzero    | nonzero placement commitment | bits[cur], bit_sum[cur], full_window_sum[cur]")
+        );
+        // an audited config also lists the window count gates and where they are exported
+        let audited =
+            PlacementChip::<Fp, 4>::configure_audit(&mut meta, config, advice[2], instance, 2);
+        let expected = [
+            "s_adjacency  | window audit count         | windows_checked[prev], windows_checked[cur]",
+            "s_permute    | window audit carry         | windows_checked[prev], windows_checked[cur]",
+            "window audit exported: full_window_sum -> instance[2], windows_checked -> instance[3]",
+            "",
+        ]
+        .join("\n");
+        assert!(audited.to_string().ends_with(&expected));
+        // gate names in the table match the gates registered by configure()
+        let gates = format!("{:?}", meta);
+        for name in [
+            "sum inputted H, V bits",
+            "placement bit count",
+            "adjacency bit count",
+            "permute adjaceny bit count",
+            "running sum constraints",
+            "window audit count",
+            "window audit carry",
+        ] {
+            assert!(gates.contains(name), "no gate named {}", name);
+        }
+    }
+
+    #[test]
+    fn placement_gadget_commitment() {
+        for z in [false, true] {
+            let [horizontal, vertical] =
+                Ship::new(ShipType::Carrier, 2, 5, z).witness(WitnessOption::Default);
+            let gadget = PlacementGadget::<Fp, 5>::new(horizontal.zip(vertical));
+            assert_eq!(
+                gadget.commitment(),
+                Fp::from_u128(horizontal.lower_u128()) + Fp::from_u128(vertical.lower_u128())
+            );
+        }
+    }
+
+    #[test]
+    fn placement_gadget_cache() {
+        let mut cache = HashMap::<ShipPlacement<4>, PlacementGadget<Fp, 4>>::new();
+        let ship = Ship::new(ShipType::Battleship, 3, 4, true);
+        let key = ShipPlacement::<4>::from_ship(ship).unwrap();
+        let gadget = key.gadget::<Fp>();
+        assert_eq!(gadget, PlacementGadget::from_ship(ship));
+        cache.insert(key, gadget);
+        // equal x, y, z retrieves the cached gadget
+        assert_eq!(cache.get(&ShipPlacement::new(3, 4, true)), Some(&gadget));
+        // any differing field is a different key
+        assert!(!cache.contains_key(&ShipPlacement::new(3, 4, false)));
+        assert!(!cache.contains_key(&ShipPlacement::new(4, 4, true)));
+        // a ship of another length cannot be keyed into the cache
+        assert_eq!(
+            ShipPlacement::<4>::from_ship(Ship::new(ShipType::Cruiser, 3, 4, true)),
+            None
+        );
+        // re-inserting an equal key replaces rather than duplicates the entry
+        cache.insert(key, PlacementGadget::<Fp, 4>::new(ship.bits(true)));
+        assert_eq!(cache.len(), 1);
+        // a cruiser and a submarine with the same head share one entry
+        let mut cache = HashMap::<ShipPlacement<3>, PlacementGadget<Fp, 3>>::new();
+        for ship_type in [ShipType::Cruiser, ShipType::Submarine] {
+            let key = ShipPlacement::<3>::from_ship(Ship::new(ship_type, 2, 5, false)).unwrap();
+            cache.entry(key).or_insert_with(|| key.gadget());
+        }
+        assert_eq!(cache.len(), 1);
+        // length 1 placements collide across orientations
+        assert_eq!(
+            ShipPlacement::<1>::new(2, 5, true),
+            ShipPlacement::new(2, 5, false)
+        );
+    }
 }
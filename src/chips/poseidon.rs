@@ -0,0 +1,161 @@
+use {
+    halo2_gadgets::poseidon::{
+        primitives::{ConstantLength, Spec},
+        PaddedWord, Pow5Chip, Pow5Config, Sponge,
+    },
+    halo2_proofs::{
+        arithmetic::FieldExt,
+        circuit::{AssignedCell, Layouter},
+        plonk::{Advice, Column, Error},
+    },
+};
+
+/**
+ * Poseidon hash a single assigned value in the ConstantLength<1> domain
+ * @dev equivalent to halo2_gadgets::poseidon::Hash::hash but absorbs the zero padding as an
+ *      advice cell constrained to the constant 0. halo2_proofs 0.2 does not evaluate fixed cell
+ *      values during witness generation, so the fixed padding cell used by Hash::hash is unknown
+ *      when copied into the sponge and create_proof fails (MockProver is unaffected)
+ *
+ * @param config - the configured poseidon chip
+ * @param layouter - the layouter to assign the hash regions with
+ * @param padding - an equality-enabled advice column to load the zero padding into
+ * @param preimage - the assigned value to hash
+ * @return - assigned cell storing the poseidon hash of the preimage
+ */
+pub fn hash_single<S: Spec<F, 3, 2>, F: FieldExt>(
+    config: Pow5Config<F, 3, 2>,
+    layouter: &mut impl Layouter<F>,
+    padding: Column<Advice>,
+    preimage: AssignedCell<F, F>,
+) -> Result<AssignedCell<F, F>, Error> {
+    let zero = layouter.assign_region(
+        || "load poseidon padding",
+        |mut region| region.assign_advice_from_constant(|| "padding", padding, 0, F::zero()),
+    )?;
+    let chip = Pow5Chip::construct(config);
+    let mut sponge =
+        Sponge::<_, _, S, _, ConstantLength<1>, 3, 2>::new(chip, layouter.namespace(|| "hasher"))?;
+    for (i, word) in [preimage, zero].into_iter().enumerate() {
+        sponge.absorb(
+            layouter.namespace(|| format!("absorb_{}", i)),
+            PaddedWord::Message(word),
+        )?;
+    }
+    sponge
+        .finish_absorbing(layouter.namespace(|| "finish absorbing"))?
+        .squeeze(layouter.namespace(|| "squeeze"))
+}
+
+/**
+ * Poseidon hash two assigned values in the ConstantLength<2> domain
+ * @dev two words fill the rate exactly, so unlike hash_single no padding cell is absorbed
+ *
+ * @param config - the configured poseidon chip
+ * @param layouter - the layouter to assign the hash regions with
+ * @param preimage - the assigned values to hash, in absorption order
+ * @return - assigned cell storing the poseidon hash of the preimage
+ */
+pub fn hash_pair<S: Spec<F, 3, 2>, F: FieldExt>(
+    config: Pow5Config<F, 3, 2>,
+    layouter: &mut impl Layouter<F>,
+    preimage: [AssignedCell<F, F>; 2],
+) -> Result<AssignedCell<F, F>, Error> {
+    let chip = Pow5Chip::construct(config);
+    let mut sponge =
+        Sponge::<_, _, S, _, ConstantLength<2>, 3, 2>::new(chip, layouter.namespace(|| "hasher"))?;
+    for (i, word) in preimage.into_iter().enumerate() {
+        sponge.absorb(
+            layouter.namespace(|| format!("absorb_{}", i)),
+            PaddedWord::Message(word),
+        )?;
+    }
+    sponge
+        .finish_absorbing(layouter.namespace(|| "finish absorbing"))?
+        .squeeze(layouter.namespace(|| "squeeze"))
+}
+
+/**
+ * Tag absorbed after the children of every Merkle tree node
+ * @dev separates tree nodes from the ConstantLength<2> hashes used by salted commitments
+ */
+pub const MERKLE_NODE_TAG: u64 = 0x6e6f6465; // "node"
+
+/**
+ * Poseidon hash two Merkle tree children into their parent node
+ * @dev hashes [left, right, MERKLE_NODE_TAG] in the ConstantLength<3> domain, so a node never
+ *      shares a domain with a leaf (ConstantLength<1>) or a salted commitment (ConstantLength<2>).
+ *      the tag and the zero padding are absorbed as advice cells constrained to constants (see
+ *      hash_single)
+ *
+ * @param config - the configured poseidon chip
+ * @param layouter - the layouter to assign the hash regions with
+ * @param constants - an equality-enabled advice column to load the tag and padding into
+ * @param children - the assigned [left, right] children of the node
+ * @return - assigned cell storing the poseidon hash of the node
+ */
+pub fn hash_node<S: Spec<F, 3, 2>, F: FieldExt>(
+    config: Pow5Config<F, 3, 2>,
+    layouter: &mut impl Layouter<F>,
+    constants: Column<Advice>,
+    children: [AssignedCell<F, F>; 2],
+) -> Result<AssignedCell<F, F>, Error> {
+    let [tag, zero] = layouter.assign_region(
+        || "load merkle node tag",
+        |mut region| {
+            let tag = region.assign_advice_from_constant(
+                || "node tag",
+                constants,
+                0,
+                F::from(MERKLE_NODE_TAG),
+            )?;
+            let zero = region.assign_advice_from_constant(|| "padding", constants, 1, F::zero())?;
+            Ok([tag, zero])
+        },
+    )?;
+    let chip = Pow5Chip::construct(config);
+    let mut sponge =
+        Sponge::<_, _, S, _, ConstantLength<3>, 3, 2>::new(chip, layouter.namespace(|| "hasher"))?;
+    let [left, right] = children;
+    for (i, word) in [left, right, tag, zero].into_iter().enumerate() {
+        sponge.absorb(
+            layouter.namespace(|| format!("absorb_{}", i)),
+            PaddedWord::Message(word),
+        )?;
+    }
+    sponge
+        .finish_absorbing(layouter.namespace(|| "finish absorbing"))?
+        .squeeze(layouter.namespace(|| "squeeze"))
+}
+
+/**
+ * Commit to an assigned value under a salt derived from a player secret and a game id
+ * @dev salt = poseidon(secret, game_id), commitment = poseidon(preimage, salt), both in the
+ *      ConstantLength<2> domain. rotating game_id every game changes the commitment to an
+ *      unchanged preimage, so reusing a board layout across games is not visible on chain
+ *
+ * @param config - the configured poseidon chip
+ * @param layouter - the layouter to assign the hash regions with
+ * @param preimage - the assigned value to commit to
+ * @param secret - the assigned private player secret
+ * @param game_id - the assigned per-game identifier
+ * @return - assigned cell storing the salted commitment to the preimage
+ */
+pub fn commit_salted<S: Spec<F, 3, 2>, F: FieldExt>(
+    config: Pow5Config<F, 3, 2>,
+    layouter: &mut impl Layouter<F>,
+    preimage: AssignedCell<F, F>,
+    secret: AssignedCell<F, F>,
+    game_id: AssignedCell<F, F>,
+) -> Result<AssignedCell<F, F>, Error> {
+    let salt = hash_pair::<S, F>(
+        config.clone(),
+        &mut layouter.namespace(|| "derive salt"),
+        [secret, game_id],
+    )?;
+    hash_pair::<S, F>(
+        config,
+        &mut layouter.namespace(|| "salted commitment"),
+        [preimage, salt],
+    )
+}
@@ -0,0 +1,178 @@
+use {
+    crate::{
+        chips::board::{commit_board_state, BoardSalt},
+        utils::{
+            binary::BinaryValue,
+            instance::{InstanceLayout, PublicInput},
+        },
+    },
+    halo2_gadgets::poseidon::{primitives::Spec, Pow5Chip, Pow5Config},
+    halo2_proofs::{
+        arithmetic::FieldExt,
+        circuit::{AssignedCell, Chip, Layouter, Value},
+        plonk::{Advice, Column, ConstraintSystem, Error, Fixed, Instance},
+    },
+    std::marker::PhantomData,
+};
+
+/**
+ * Storage for a proof that a revealed board opens an earlier board commitment
+ *
+ * @param poseidon - poseidon config for hashing the board state into the board commitment
+ * @param input - advice column storing the revealed board state
+ * @param advice - advice columns used by the poseidon chip
+ * @param instance - instance column exporting the board commitment and revealed board
+ * @param fixed - fixed columns for constant values
+ */
+#[derive(Clone, Debug)]
+pub struct RevealConfig<F: FieldExt> {
+    pub poseidon: Pow5Config<F, 3, 2>,
+    pub input: Column<Advice>,
+    pub advice: [Column<Advice>; 4],
+    pub instance: Column<Instance>,
+    pub fixed: [Column<Fixed>; 6],
+    _marker: PhantomData<F>,
+}
+
+/**
+ * Chip for opening a board commitment at the end of a game
+ *    * prove public commitment is the poseidon hash of the board state (salted if the game is)
+ *    * export the board state publicly so every cell can be audited against the shots answered
+ */
+pub struct RevealChip<S: Spec<F, 3, 2>, F: FieldExt> {
+    config: RevealConfig<F>,
+    _marker: PhantomData<S>,
+}
+
+impl<S: Spec<F, 3, 2>, F: FieldExt> Chip<F> for RevealChip<S, F> {
+    type Config = RevealConfig<F>;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+impl<S: Spec<F, 3, 2>, F: FieldExt> RevealChip<S, F> {
+    pub fn new(config: RevealConfig<F>) -> Self {
+        RevealChip {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    /**
+     * Configure the computation space of the circuit & return RevealConfig
+     */
+    pub fn configure(meta: &mut ConstraintSystem<F>) -> RevealConfig<F> {
+        // define advice
+        let mut advice = Vec::<Column<Advice>>::new();
+        for _ in 0..4 {
+            let col = meta.advice_column();
+            meta.enable_equality(col);
+            advice.push(col);
+        }
+        let advice: [Column<Advice>; 4] = advice.try_into().unwrap();
+        let input = meta.advice_column();
+        meta.enable_equality(input);
+
+        // define fixed
+        let mut fixed = Vec::<Column<Fixed>>::new();
+        for _ in 0..6 {
+            fixed.push(meta.fixed_column());
+        }
+        // poseidon rc_a: fixed[3..6]
+        // poseidon rc_b: fixed[0..3]
+        // fixed[0] has constant enabled
+        let fixed: [Column<Fixed>; 6] = fixed.try_into().unwrap();
+        meta.enable_constant(fixed[0]);
+
+        // define instance
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        // define poseidon hash chip
+        let poseidon = Pow5Chip::<F, 3, 2>::configure::<S>(
+            meta,
+            [advice[0], advice[1], advice[2]],
+            advice[3],
+            [fixed[3], fixed[4], fixed[5]],
+            [fixed[0], fixed[1], fixed[2]],
+        );
+
+        RevealConfig {
+            poseidon,
+            input,
+            advice,
+            instance,
+            fixed,
+            _marker: PhantomData,
+        }
+    }
+
+    /**
+     * Synthesize a proof that a revealed board opens the board commitment
+     * @dev the board state needs no decomposition: the board proof already showed the committed
+     *      preimage is a valid board, and the hash binds the revealed state to that preimage
+     *
+     * @param board - the revealed board state in BinaryValue form
+     * @param salt - per-game salt the board is committed under (game id exported to salt.row)
+     *              or None for the unsalted digest
+     * @return - Ok if synthesis executes successfully
+     */
+    pub fn synthesize(
+        &self,
+        mut layouter: impl Layouter<F>,
+        board: BinaryValue,
+        salt: Option<BoardSalt<F>>,
+    ) -> Result<(), Error> {
+        // load revealed board state as advice
+        let board_state = self.load_board(&mut layouter, F::from_u128(board.lower_u128()))?;
+        // hash board state
+        let hashed_state = commit_board_state::<S, F>(
+            self.config.poseidon.clone(),
+            &mut layouter,
+            self.config.advice[0],
+            self.config.instance,
+            board_state.clone(),
+            salt,
+        )?;
+        // export public values
+        let layout = InstanceLayout::Reveal;
+        for (cell, input) in [
+            (hashed_state.cell(), PublicInput::Digest),
+            (board_state.cell(), PublicInput::Board),
+        ] {
+            layouter.constrain_instance(cell, self.config.instance, layout.row(input))?;
+        }
+        Ok(())
+    }
+
+    /**
+     * Load the revealed board state into the chip
+     *
+     * @param board_state - 100 bit number storing the serialized board state
+     * @return - reference to the assigned board state
+     */
+    fn load_board(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        board_state: F,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        layouter.assign_region(
+            || "load revealed board state",
+            |mut region| {
+                region.assign_advice(
+                    || "assign board state",
+                    self.config.input,
+                    0,
+                    || Value::known(board_state),
+                )
+            },
+        )
+    }
+}
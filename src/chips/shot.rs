@@ -1,13 +1,20 @@
-use halo2_gadgets::poseidon::primitives::ConstantLength;
-
 use {
     crate::{
-        chips::bitify::{BitifyConfig, Num2BitsChip},
-        utils::{binary::BinaryValue, board::BOARD_SIZE},
+        chips::{
+            bitify::{BitifyConfig, Num2BitsChip},
+            board::{commit_board_state, BoardSalt},
+            placement::AssignedBits,
+        },
+        utils::{
+            binary::BinaryValue,
+            board::BOARD_SIZE,
+            instance::{InstanceLayout, PublicInput},
+            shot::serialize_cells,
+        },
     },
     halo2_gadgets::poseidon::{
         primitives::Spec,
-        Hash, Pow5Chip, Pow5Config,
+        Pow5Chip, Pow5Config,
     },
     halo2_proofs::{
         arithmetic::FieldExt,
@@ -53,6 +60,34 @@ pub fn compute_shot_trace<F: FieldExt>(
     ]
 }
 
+/**
+ * Decompose assigned 100 bit values serialized like board state into constrained bits
+ * @dev shared by every chip that compares a board state bit by bit against another serialized board
+ *
+ * @param num2bits - num2bits config to decompose each value with
+ * @param num - assignments to each value
+ * @param bits - unassigned binary decomposition of each value
+ * @param labels - name of each value, used to namespace its num2bits region
+ * @return - assignments to the decomposed bits of each value, in the order given
+ */
+pub fn decompose_board_values<F: FieldExt, const N: usize>(
+    layouter: &mut impl Layouter<F>,
+    num2bits: [BitifyConfig; N],
+    num: [AssignedCell<F, F>; N],
+    bits: [[F; BOARD_SIZE]; N],
+    labels: [&str; N],
+) -> Result<[AssignedBits<F>; N], Error> {
+    let mut assigned = Vec::<AssignedBits<F>>::new();
+    for i in 0..N {
+        let chip = Num2BitsChip::<F, BOARD_SIZE>::new(num[i].clone(), bits[i]);
+        assigned.push(chip.synthesize(
+            num2bits[i],
+            layouter.namespace(|| format!("{} num2bits", labels[i])),
+        )?);
+    }
+    Ok(assigned.try_into().unwrap())
+}
+
 /**
  * Storage for a proof that a shot hits/ misses a given board commitment
  *
@@ -69,7 +104,7 @@ pub struct ShotConfig<F: FieldExt> {
     pub advice: [Column<Advice>; 4],
     pub instance: Column<Instance>,
     pub fixed: [Column<Fixed>; 6],
-    pub selectors: [Selector; 3],
+    pub selectors: [Selector; 4],
     _marker: PhantomData<F>,
 }
 
@@ -99,6 +134,7 @@ pub trait ShotInstructions<S: Spec<F, 3, 2>, F: FieldExt> {
      * @param board_commitment - instance poseidon hash of board_state
      * @param shot_commitment - instance 100 bit number (1 bit flipped) representing shot
      * @param hit - instance (constrained to be boolean) value conveying shot hit status
+     * @param nonce - instance turn nonce binding the proof to a single turn (replay protection)
      * @return reference to assigned cells of each input in order above
      */
     fn load_advice(
@@ -108,7 +144,8 @@ pub trait ShotInstructions<S: Spec<F, 3, 2>, F: FieldExt> {
         board_commitment: F,
         shot_commitment: F,
         hit: F,
-    ) -> Result<[AssignedCell<F, F>; 4], Error>;
+        nonce: F,
+    ) -> Result<[AssignedCell<F, F>; 5], Error>;
 
     /**
      * Decompose board_state, shot_commitment into 100 bits each
@@ -156,15 +193,29 @@ pub trait ShotInstructions<S: Spec<F, 3, 2>, F: FieldExt> {
     ) -> Result<(), Error>;
 
     /**
-     * Hash the private board state
+     * Constrain the running sum output of a region shot to count no hits
+     *
+     * @param hit_sum - reference to the final count of region cells occupied on the board
+     * @return - ok if the synthesis executed successfully
+     */
+    fn region_miss_output(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        hit_sum: AssignedCell<F, F>,
+    ) -> Result<(), Error>;
+
+    /**
+     * Commit to the private board state (see chips::board::commit_board_state)
      *
      * @param preimage - the private board state (bits2num'ed)
-     * @return - assigned cell storing poseidon hash of the board state
+     * @param salt - per-game salt to commit to the board under, or None for the unsalted digest
+     * @return - assigned cell storing the commitment to the board state
      */
     fn hash_board(
         &self,
         layouter: &mut impl Layouter<F>,
         preimage: AssignedCell<F, F>,
+        salt: Option<BoardSalt<F>>,
     ) -> Result<AssignedCell<F, F>, Error>;
 }
 
@@ -209,10 +260,10 @@ impl<S: Spec<F, 3, 2>, F: FieldExt> ShotChip<S, F> {
 
         // define selectors
         let mut selectors = Vec::<Selector>::new();
-        for _ in 0..3 {
+        for _ in 0..4 {
             selectors.push(meta.selector());
         }
-        let selectors: [Selector; 3] = selectors.try_into().unwrap();
+        let selectors: [Selector; 4] = selectors.try_into().unwrap();
 
         // define bits2num chips
         let mut num2bits = Vec::<BitifyConfig>::new();
@@ -291,6 +342,14 @@ impl<S: Spec<F, 3, 2>, F: FieldExt> ShotChip<S, F> {
             )
         });
 
+        meta.create_gate("constrain region miss output", |meta| {
+            let hit_count = meta.query_advice(advice[2], Rotation::cur());
+            // constrain using selector[3]
+            // - no cell in the region is occupied by a ship
+            let selector = meta.query_selector(selectors[3]);
+            Constraints::with_selector(selector, [("Region contains no ship cell", hit_count)])
+        });
+
         // return config
         ShotConfig {
             num2bits,
@@ -310,6 +369,9 @@ impl<S: Spec<F, 3, 2>, F: FieldExt> ShotChip<S, F> {
      * @param board - the board state in BinaryValue form for bits-> integer functions
      * @param shot - the shot commitment in BinaryValue form for bits-> integer functions
      * @param hit - true/ false assertion if shot produces hit on board
+     * @param nonce - monotonic turn nonce exported publicly so the proof cannot be replayed
+     * @param salt - per-game salt the board is committed under (game id exported to salt.row)
+     *              or None for the unsalted digest
      * @return - Ok if synthesis executes successfully
      */
     pub fn synthesize(
@@ -318,6 +380,8 @@ impl<S: Spec<F, 3, 2>, F: FieldExt> ShotChip<S, F> {
         board: BinaryValue,
         shot: BinaryValue,
         hit: BinaryValue,
+        nonce: F,
+        salt: Option<BoardSalt<F>>,
     ) -> Result<(), Error> {
         // compute values to witness
         let board_state = F::from_u128(board.lower_u128());
@@ -335,6 +399,7 @@ impl<S: Spec<F, 3, 2>, F: FieldExt> ShotChip<S, F> {
             board_commitment,
             shot_commitment,
             F::from_u128(hit.lower_u128()),
+            nonce,
         )?;
         // decompose board_state and ship_commitment into constrained bits
         let assigned_bits =
@@ -344,11 +409,84 @@ impl<S: Spec<F, 3, 2>, F: FieldExt> ShotChip<S, F> {
         // constrain results of running sum
         self.running_sum_output(&mut layouter, inputs[3].clone(), running_sum_results)?;
         // hash board state
-        let hashed_state = self.hash_board(&mut layouter, inputs[1].clone())?;
+        let hashed_state = self.hash_board(&mut layouter, inputs[1].clone(), salt)?;
+        // export public values
+        let layout = InstanceLayout::Shot;
+        for (cell, input) in [
+            (hashed_state.cell(), PublicInput::Digest),
+            (inputs[2].cell(), PublicInput::Shot),
+            (inputs[3].cell(), PublicInput::Hit),
+            (inputs[4].cell(), PublicInput::Nonce),
+        ] {
+            layouter.constrain_instance(cell, self.config.instance, layout.row(input))?;
+        }
+        Ok(())
+    }
+
+    /**
+     * Synthesize a proof that every cell in a region of the board is water
+     * @dev the region is fired as one multi-bit shot through the same running sum as synthesize;
+     *      instead of a single shot with a public hit, the hit count must be 0
+     *
+     * @param board - the board state in BinaryValue form for bits-> integer functions
+     * @param cells - (x, y) coordinates of every cell in the region
+     * @param salt - per-game salt the board is committed under (game id exported to salt.row)
+     *              or None for the unsalted digest
+     * @return - Ok if synthesis executes successfully, Error::Synthesis if the region is empty
+     *           (a vacuous statement) or has a cell off the board
+     */
+    pub fn prove_region_miss(
+        &self,
+        mut layouter: impl Layouter<F>,
+        board: BinaryValue,
+        cells: &[(u8, u8)],
+        salt: Option<BoardSalt<F>>,
+    ) -> Result<(), Error> {
+        // reject regions serialize_cells cannot serialize or that prove nothing
+        if cells.is_empty() || cells.iter().any(|&(x, y)| x >= 10 || y >= 10) {
+            return Err(Error::Synthesis);
+        }
+        // compute values to witness
+        let region = serialize_cells(cells);
+        let bits = [
+            board.bitfield::<F, BOARD_SIZE>(),
+            region.bitfield::<F, BOARD_SIZE>(),
+        ];
+        let trace = compute_shot_trace::<F>(board, region);
+        // load inputs as advice
+        let inputs = layouter.assign_region(
+            || "load private region miss advice values",
+            |mut region_cells| {
+                let board_state = region_cells.assign_advice(
+                    || "assign board state",
+                    self.config.input,
+                    0,
+                    || Value::known(F::from_u128(board.lower_u128())),
+                )?;
+                let region_commitment = region_cells.assign_advice(
+                    || "assign region commitment",
+                    self.config.input,
+                    1,
+                    || Value::known(F::from_u128(region.lower_u128())),
+                )?;
+                Ok([board_state, region_commitment])
+            },
+        )?;
+        // decompose board_state and region commitment into constrained bits
+        let assigned_bits = self.decompose(&mut layouter, inputs.clone(), bits)?;
+        // synthesize running sum and constrain no region cell hits the board
+        let [_, hit_sum] = self.running_sums(&mut layouter, assigned_bits, trace)?;
+        self.region_miss_output(&mut layouter, hit_sum)?;
+        // hash board state
+        let hashed_state = self.hash_board(&mut layouter, inputs[0].clone(), salt)?;
         // export public values
-        layouter.constrain_instance(hashed_state.cell(), self.config.instance, 0)?;
-        layouter.constrain_instance(inputs[2].cell(), self.config.instance, 1)?;
-        layouter.constrain_instance(inputs[3].cell(), self.config.instance, 2)?;
+        let layout = InstanceLayout::RegionMiss;
+        for (cell, input) in [
+            (hashed_state.cell(), PublicInput::Digest),
+            (inputs[1].cell(), PublicInput::Region),
+        ] {
+            layouter.constrain_instance(cell, self.config.instance, layout.row(input))?;
+        }
         Ok(())
     }
 }
@@ -361,7 +499,8 @@ impl<S: Spec<F, 3, 2>, F: FieldExt> ShotInstructions<S, F> for ShotChip<S, F> {
         board_commitment: F,
         shot_commitment: F,
         hit: F,
-    ) -> Result<[AssignedCell<F, F>; 4], Error> {
+        nonce: F,
+    ) -> Result<[AssignedCell<F, F>; 5], Error> {
         Ok(layouter.assign_region(
             || "load private ShotChip advice values",
             |mut region| {
@@ -389,8 +528,14 @@ impl<S: Spec<F, 3, 2>, F: FieldExt> ShotInstructions<S, F> for ShotChip<S, F> {
                     3,
                     || Value::known(hit),
                 )?;
+                let nonce = region.assign_advice(
+                    || "assign turn nonce",
+                    self.config.input,
+                    4,
+                    || Value::known(nonce),
+                )?;
                 self.config.selectors[0].enable(&mut region, 3)?;
-                Ok([board_state, board_commitment, shot_commitment, hit])
+                Ok([board_state, board_commitment, shot_commitment, hit, nonce])
             },
         )?)
     }
@@ -401,19 +546,13 @@ impl<S: Spec<F, 3, 2>, F: FieldExt> ShotInstructions<S, F> for ShotChip<S, F> {
         num: [AssignedCell<F, F>; 2],
         bits: [[F; BOARD_SIZE]; 2],
     ) -> Result<[[AssignedCell<F, F>; BOARD_SIZE]; 2], Error> {
-        // decompose board state
-        let chip = Num2BitsChip::<F, BOARD_SIZE>::new(num[0].clone(), bits[0]);
-        let board_state = chip.synthesize(
-            self.config.num2bits[0],
-            layouter.namespace(|| "board_state num2bits"),
-        )?;
-        // decompose shot commitment
-        let chip = Num2BitsChip::<F, BOARD_SIZE>::new(num[1].clone(), bits[1]);
-        let shot_commitment = chip.synthesize(
-            self.config.num2bits[1],
-            layouter.namespace(|| "shot_commitment bits2num"),
-        )?;
-        Ok([board_state, shot_commitment])
+        decompose_board_values(
+            layouter,
+            self.config.num2bits,
+            num,
+            bits,
+            ["board_state", "shot_commitment"],
+        )
     }
 
     fn running_sums(
@@ -507,16 +646,39 @@ impl<S: Spec<F, 3, 2>, F: FieldExt> ShotInstructions<S, F> for ShotChip<S, F> {
         )?)
     }
 
+    fn region_miss_output(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        hit_sum: AssignedCell<F, F>,
+    ) -> Result<(), Error> {
+        layouter.assign_region(
+            || "region miss output check",
+            |mut region| {
+                hit_sum.copy_advice(
+                    || "permute board hits by region count",
+                    &mut region,
+                    self.config.advice[2],
+                    0,
+                )?;
+                self.config.selectors[3].enable(&mut region, 0)?;
+                Ok(())
+            },
+        )
+    }
+
     fn hash_board(
         &self,
         layouter: &mut impl Layouter<F>,
         preimage: AssignedCell<F, F>,
+        salt: Option<BoardSalt<F>>,
     ) -> Result<AssignedCell<F, F>, Error> {
-        // // input word
-        let chip = Pow5Chip::construct(self.config.poseidon.clone());
-
-        let hasher =
-            Hash::<_, _, S, ConstantLength<1>, 3, 2>::init(chip, layouter.namespace(|| "hasher"))?;
-        hasher.hash(layouter.namespace(|| "hash"), [preimage])
+        commit_board_state::<S, F>(
+            self.config.poseidon.clone(),
+            layouter,
+            self.config.advice[0],
+            self.config.instance,
+            preimage,
+            salt,
+        )
     }
 }
@@ -0,0 +1,116 @@
+use {
+    crate::{chips::placement::AssignedBits, utils::board::BOARD_SIZE},
+    halo2_proofs::{
+        arithmetic::FieldExt,
+        circuit::{Chip, Layouter, Region},
+        plonk::{Advice, Column, ConstraintSystem, Constraints, Error, Expression, Selector},
+        poly::Rotation,
+    },
+    std::marker::PhantomData,
+};
+
+/**
+ * Storage required to constrain one bit decomposition to be a subset of another
+ * @dev expects both decompositions to already be constrained boolean (ex: by num2bits)
+ *
+ * @param subset - column storing bits that must also be flipped in superset
+ * @param superset - column storing bits that may be flipped
+ * @param selector - toggles subset constraint on a row
+ */
+#[derive(Clone, Copy, Debug)]
+pub struct SubsetConfig<F: FieldExt> {
+    pub subset: Column<Advice>,
+    pub superset: Column<Advice>,
+    pub selector: Selector,
+    _marker: PhantomData<F>,
+}
+
+pub struct SubsetChip<F: FieldExt> {
+    config: SubsetConfig<F>,
+}
+
+impl<F: FieldExt> Chip<F> for SubsetChip<F> {
+    type Config = SubsetConfig<F>;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+impl<F: FieldExt> SubsetChip<F> {
+    pub fn new(config: SubsetConfig<F>) -> Self {
+        SubsetChip { config }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        subset: Column<Advice>,
+        superset: Column<Advice>,
+    ) -> SubsetConfig<F> {
+        // define selectors
+        let selector = meta.selector();
+
+        meta.create_gate("bit subset", |meta| {
+            // if subset bit is flipped, superset bit must be flipped
+            let one = Expression::Constant(F::one());
+            let subset_bit = meta.query_advice(subset, Rotation::cur());
+            let superset_bit = meta.query_advice(superset, Rotation::cur());
+            let selector = meta.query_selector(selector);
+            Constraints::with_selector(
+                selector,
+                [(
+                    "Subset bit flipped in superset",
+                    subset_bit * (one - superset_bit),
+                )],
+            )
+        });
+
+        SubsetConfig {
+            subset,
+            superset,
+            selector,
+            _marker: PhantomData,
+        }
+    }
+
+    /**
+     * Constrain every flipped bit of subset to also be flipped in superset
+     *
+     * @param subset - assigned bits of the decomposition that must be contained
+     * @param superset - assigned bits of the decomposition that must contain subset
+     * @return - Ok if synthesis executes successfully
+     */
+    pub fn synthesize(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        subset: AssignedBits<F>,
+        superset: AssignedBits<F>,
+    ) -> Result<(), Error> {
+        layouter.assign_region(
+            || "constrain bit subset",
+            |mut region: Region<F>| {
+                for i in 0..BOARD_SIZE {
+                    subset[i].copy_advice(
+                        || format!("copy subset bit {}", i),
+                        &mut region,
+                        self.config.subset,
+                        i,
+                    )?;
+                    superset[i].copy_advice(
+                        || format!("copy superset bit {}", i),
+                        &mut region,
+                        self.config.superset,
+                        i,
+                    )?;
+                    self.config.selector.enable(&mut region, i)?;
+                }
+                Ok(())
+            },
+        )
+    }
+}
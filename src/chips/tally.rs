@@ -0,0 +1,192 @@
+use {
+    crate::{
+        chips::{
+            board::BoardSalt,
+            shot::{compute_shot_trace, ShotChip, ShotConfig, ShotInstructions},
+        },
+        utils::{
+            binary::BinaryValue,
+            board::BOARD_SIZE,
+            instance::{InstanceLayout, PublicInput},
+        },
+    },
+    halo2_gadgets::poseidon::primitives::Spec,
+    halo2_proofs::{
+        arithmetic::FieldExt,
+        circuit::{AssignedCell, Chip, Layouter, Value},
+        plonk::{ConstraintSystem, Error},
+    },
+};
+
+/**
+ * Storage for a proof of how many shots fired have hit a committed board
+ * @dev a tally is the hit count of ShotChip's running sum with every shot fired as one multi-bit
+ *      shot, so the chip is laid out on ShotChip's columns, num2bits, poseidon and gates
+ *
+ * @param shot - ShotChip config whose running sum row gate counts the hits
+ */
+#[derive(Clone, Debug)]
+pub struct TallyConfig<F: FieldExt> {
+    pub shot: ShotConfig<F>,
+}
+
+/**
+ * Chip for proving the number of hits landed on a committed board
+ *    * decompose board state and shots fired into bits
+ *    * ShotChip's running sum over board bit * shot bit counts the cells both occupied and shot
+ *    * prove public commitment is the poseidon hash of the board state
+ *
+ * @dev the hit count is the final running sum cell, so a verifier supplying any other count fails
+ */
+pub struct TallyChip<S: Spec<F, 3, 2>, F: FieldExt> {
+    config: TallyConfig<F>,
+    shot: ShotChip<S, F>,
+}
+
+impl<S: Spec<F, 3, 2>, F: FieldExt> Chip<F> for TallyChip<S, F> {
+    type Config = TallyConfig<F>;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+pub trait TallyInstructions<S: Spec<F, 3, 2>, F: FieldExt> {
+    /**
+     * Load the private advice inputs into the chip
+     *
+     * @param board_state - 100 bit number to decompose to serialized board state
+     * @param shots - 100 bit number to decompose to serialized shots fired at the board
+     * @return - reference to assigned cells of each input in order above
+     */
+    fn load_advice(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        board_state: F,
+        shots: F,
+    ) -> Result<[AssignedCell<F, F>; 2], Error>;
+
+    /**
+     * Count the cells flipped in both the board state and the shots fired
+     * @dev decomposes both values and runs them through ShotChip's running sum
+     *
+     * @param inputs - assigned [board_state, shots]
+     * @param board - the private board state in BinaryValue form
+     * @param shots - every shot fired at the board in BinaryValue form
+     * @return - assigned cell storing the total hit count
+     */
+    fn tally_hits(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        inputs: [AssignedCell<F, F>; 2],
+        board: BinaryValue,
+        shots: BinaryValue,
+    ) -> Result<AssignedCell<F, F>, Error>;
+}
+
+impl<S: Spec<F, 3, 2>, F: FieldExt> TallyChip<S, F> {
+    pub fn new(config: TallyConfig<F>) -> Self {
+        TallyChip {
+            shot: ShotChip::new(config.shot.clone()),
+            config,
+        }
+    }
+
+    /**
+     * Configure the computation space of the circuit & return TallyConfig
+     */
+    pub fn configure(meta: &mut ConstraintSystem<F>) -> TallyConfig<F> {
+        TallyConfig {
+            shot: ShotChip::<S, F>::configure(meta),
+        }
+    }
+
+    /**
+     * Synthesize a proof of the number of shots fired that hit a committed board
+     *
+     * @param board - the private board state in BinaryValue form
+     * @param shots - every shot fired at the board (serialized like board state) in BinaryValue form
+     * @param salt - per-game salt the board is committed under (game id exported to salt.row)
+     *              or None for the unsalted digest
+     * @return - Ok if synthesis executes successfully
+     */
+    pub fn synthesize(
+        &self,
+        mut layouter: impl Layouter<F>,
+        board: BinaryValue,
+        shots: BinaryValue,
+        salt: Option<BoardSalt<F>>,
+    ) -> Result<(), Error> {
+        // compute values to witness
+        let board_state = F::from_u128(board.lower_u128());
+        let shots_commitment = F::from_u128(shots.lower_u128());
+        // load inputs as advice
+        let inputs = self.load_advice(&mut layouter, board_state, shots_commitment)?;
+        // count the cells both occupied and shot
+        let hits = self.tally_hits(&mut layouter, inputs.clone(), board, shots)?;
+        // hash board state
+        let hashed_state = self
+            .shot
+            .hash_board(&mut layouter, inputs[0].clone(), salt)?;
+        // export public values
+        let layout = InstanceLayout::Tally;
+        for (cell, input) in [
+            (hashed_state.cell(), PublicInput::Digest),
+            (inputs[1].cell(), PublicInput::Shots),
+            (hits.cell(), PublicInput::Hits),
+        ] {
+            layouter.constrain_instance(cell, self.config.shot.instance, layout.row(input))?;
+        }
+        Ok(())
+    }
+}
+
+impl<S: Spec<F, 3, 2>, F: FieldExt> TallyInstructions<S, F> for TallyChip<S, F> {
+    fn load_advice(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        board_state: F,
+        shots: F,
+    ) -> Result<[AssignedCell<F, F>; 2], Error> {
+        layouter.assign_region(
+            || "load private TallyChip advice values",
+            |mut region| {
+                let board_state = region.assign_advice(
+                    || "assign board state",
+                    self.config.shot.input,
+                    0,
+                    || Value::known(board_state),
+                )?;
+                let shots = region.assign_advice(
+                    || "assign shots fired",
+                    self.config.shot.input,
+                    1,
+                    || Value::known(shots),
+                )?;
+                Ok([board_state, shots])
+            },
+        )
+    }
+
+    fn tally_hits(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        inputs: [AssignedCell<F, F>; 2],
+        board: BinaryValue,
+        shots: BinaryValue,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        let bits = [
+            board.bitfield::<F, BOARD_SIZE>(),
+            shots.bitfield::<F, BOARD_SIZE>(),
+        ];
+        let assigned_bits = self.shot.decompose(layouter, inputs, bits)?;
+        let trace = compute_shot_trace::<F>(board, shots);
+        let [_, hits] = self.shot.running_sums(layouter, assigned_bits, trace)?;
+        Ok(hits)
+    }
+}
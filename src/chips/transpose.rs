@@ -102,46 +102,44 @@ impl<F: FieldExt> TransposeChip<F> {
         bits: [F; BOARD_SIZE],
         placements: [[AssignedCell<F, F>; BOARD_SIZE]; 10],
     ) -> Result<[AssignedCell<F, F>; BOARD_SIZE], Error> {
-        Ok(layouter
-            .assign_region(
-                || "Transpose ship commitments",
-                |mut region: Region<F>| {
-                    // permute from bits2num chips
-                    for col in 0..10 {
-                        for row in 0..BOARD_SIZE {
-                            let transposed_index = if col % 2 == 1 {
-                                row % 10 * 10 + row / 10
-                            } else {
-                                row
-                            };
-                            let orientation = if col % 2 == 1 {
-                                "vertical"
-                            } else {
-                                "horizontal"
-                            };
-                            placements[col][transposed_index].clone().copy_advice(
-                                || format!("permute {} ship {} bit {}", orientation, col / 2, row),
-                                &mut region,
-                                self.config.permuted_bits[col],
-                                row,
-                            )?;
-                        }
-                    }
-                    // assign transposed commitment
-                    let mut assigned = Vec::<AssignedCell<F, F>>::new();
+        layouter.assign_region(
+            || "Transpose ship commitments",
+            |mut region: Region<F>| {
+                // permute from bits2num chips
+                for col in 0..10 {
                     for row in 0..BOARD_SIZE {
-                        assigned.push(region.assign_advice(
-                            || format!("assign tranposed bit {}", row),
-                            self.config.transposed_bits,
+                        let transposed_index = if col % 2 == 1 {
+                            row % 10 * 10 + row / 10
+                        } else {
+                            row
+                        };
+                        let orientation = if col % 2 == 1 {
+                            "vertical"
+                        } else {
+                            "horizontal"
+                        };
+                        placements[col][transposed_index].clone().copy_advice(
+                            || format!("permute {} ship {} bit {}", orientation, col / 2, row),
+                            &mut region,
+                            self.config.permuted_bits[col],
                             row,
-                            || Value::known(bits[row].clone()),
-                        )?);
-                        // toggle transposed row constraint
-                        self.config.selector.enable(&mut region, row)?;
+                        )?;
                     }
-                    Ok(assigned.try_into().unwrap())
-                },
-            )
-            .unwrap())
+                }
+                // assign transposed commitment
+                let mut assigned = Vec::<AssignedCell<F, F>>::new();
+                for row in 0..BOARD_SIZE {
+                    assigned.push(region.assign_advice(
+                        || format!("assign tranposed bit {}", row),
+                        self.config.transposed_bits,
+                        row,
+                        || Value::known(bits[row].clone()),
+                    )?);
+                    // toggle transposed row constraint
+                    self.config.selector.enable(&mut region, row)?;
+                }
+                Ok(assigned.try_into().unwrap())
+            },
+        )
     }
 }
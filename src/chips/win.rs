@@ -0,0 +1,370 @@
+use {
+    crate::{
+        chips::{
+            bitify::{BitifyConfig, Num2BitsChip},
+            board::{commit_board_state, BoardSalt},
+            placement::AssignedBits,
+            shot::decompose_board_values,
+        },
+        utils::{
+            binary::BinaryValue,
+            board::BOARD_SIZE,
+            instance::{InstanceLayout, PublicInput},
+        },
+    },
+    halo2_gadgets::poseidon::{primitives::Spec, Pow5Chip, Pow5Config},
+    halo2_proofs::{
+        arithmetic::FieldExt,
+        circuit::{AssignedCell, Chip, Layouter, Region, Value},
+        plonk::{
+            Advice, Column, ConstraintSystem, Constraints, Error, Expression, Fixed, Instance,
+            Selector,
+        },
+        poly::Rotation,
+    },
+    std::marker::PhantomData,
+};
+
+/**
+ * Storage for a proof that every ship cell on a committed board has been shot
+ *
+ * @param num2bits - num2bits config for board state and shots fired
+ * @param poseidon - poseidon config for hashing the board state into the board commitment
+ * @param input - advice column storing the private board state and shots fired
+ * @param advice - advice columns shared throughout instructions/ chips/ regions
+ * @param fixed - fixed columns for constant values
+ * @param selector - toggles the win running product constraint on a row
+ */
+#[derive(Clone, Debug)]
+pub struct WinConfig<F: FieldExt> {
+    pub num2bits: [BitifyConfig; 2],
+    pub poseidon: Pow5Config<F, 3, 2>,
+    pub input: Column<Advice>,
+    pub advice: [Column<Advice>; 4],
+    pub instance: Column<Instance>,
+    pub fixed: [Column<Fixed>; 6],
+    pub selector: Selector,
+    _marker: PhantomData<F>,
+}
+
+/**
+ * Chip for proving whether a game has been won against a committed board
+ *    * compute a running product of (1 - board bit * (1 - shot bit)) over every cell
+ *    * prove the public win value is the final running product
+ *    * prove public commitment is the poseidon hash of the board state
+ *
+ * @dev the product is 1 only if all ship cells (17) have been hit, and 0 as soon as one is missed
+ */
+pub struct WinChip<S: Spec<F, 3, 2>, F: FieldExt> {
+    config: WinConfig<F>,
+    _marker: PhantomData<S>,
+}
+
+impl<S: Spec<F, 3, 2>, F: FieldExt> Chip<F> for WinChip<S, F> {
+    type Config = WinConfig<F>;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+pub trait WinInstructions<S: Spec<F, 3, 2>, F: FieldExt> {
+    /**
+     * Load the private advice inputs into the chip
+     *
+     * @param board_state - 100 bit number to decompose to serialized board state
+     * @param shots - 100 bit number to decompose to serialized shots fired at the board
+     * @return - reference to assigned cells of each input in order above
+     */
+    fn load_advice(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        board_state: F,
+        shots: F,
+    ) -> Result<[AssignedCell<F, F>; 2], Error>;
+
+    /**
+     * Decompose board_state, shots into 100 bits each
+     * @dev order in arrays: [board_state, shots]
+     *
+     * @param num - assignments to board state/ shots values
+     * @param bits - unassigned binary decomposition of assigned values
+     * @return - assignments to decomposed bits ([board_state, shots])
+     */
+    fn decompose(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        num: [AssignedCell<F, F>; 2],
+        bits: [[F; BOARD_SIZE]; 2],
+    ) -> Result<[AssignedBits<F>; 2], Error>;
+
+    /**
+     * Compute whether every board bit is flipped in the shots fired as a running product
+     * @dev row 0 is the constant 1, row i + 1 multiplies in (1 - board bit i * (1 - shot bit i))
+     *
+     * @param board - assigned bits of the board state
+     * @param shots - assigned bits of the shots fired
+     * @return - assigned final running product (1 if every ship cell was hit, 0 otherwise)
+     */
+    fn win_product(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        board: AssignedBits<F>,
+        shots: AssignedBits<F>,
+    ) -> Result<AssignedCell<F, F>, Error>;
+
+    /**
+     * Commit to the private board state (see chips::board::commit_board_state)
+     *
+     * @param preimage - the private board state
+     * @param salt - per-game salt to commit to the board under, or None for the unsalted digest
+     * @return - assigned cell storing the commitment to the board state
+     */
+    fn hash_board(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        preimage: AssignedCell<F, F>,
+        salt: Option<BoardSalt<F>>,
+    ) -> Result<AssignedCell<F, F>, Error>;
+}
+
+impl<S: Spec<F, 3, 2>, F: FieldExt> WinChip<S, F> {
+    pub fn new(config: WinConfig<F>) -> Self {
+        WinChip {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    /**
+     * Configure the computation space of the circuit & return WinConfig
+     */
+    pub fn configure(meta: &mut ConstraintSystem<F>) -> WinConfig<F> {
+        // define advice
+        let mut advice = Vec::<Column<Advice>>::new();
+        for _ in 0..4 {
+            let col = meta.advice_column();
+            meta.enable_equality(col);
+            advice.push(col);
+        }
+        let advice: [Column<Advice>; 4] = advice.try_into().unwrap();
+        let input = meta.advice_column();
+        meta.enable_equality(input);
+
+        // define fixed
+        let mut fixed = Vec::<Column<Fixed>>::new();
+        for _ in 0..6 {
+            fixed.push(meta.fixed_column());
+        }
+        // poseidon rc_a: fixed[3..6]
+        // poseidon rc_b: fixed[0..3]
+        // fixed[0] has constant enabled
+        let fixed: [Column<Fixed>; 6] = fixed.try_into().unwrap();
+        meta.enable_constant(fixed[0]);
+
+        // define instance
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        // define num2bits chips
+        let mut num2bits = Vec::<BitifyConfig>::new();
+        for _ in 0..2 {
+            num2bits.push(Num2BitsChip::<_, BOARD_SIZE>::configure(
+                meta, advice[0], advice[1], advice[2], fixed[0],
+            ));
+        }
+        let num2bits: [BitifyConfig; 2] = num2bits.try_into().unwrap();
+
+        // define win running product gate
+        let selector = meta.selector();
+        meta.create_gate("win running product", |meta| {
+            // query cells used in gate
+            let board = meta.query_advice(advice[0], Rotation::cur());
+            let shot = meta.query_advice(advice[1], Rotation::cur());
+            let prev = meta.query_advice(advice[2], Rotation::prev());
+            let product = meta.query_advice(advice[2], Rotation::cur());
+            let one = Expression::Constant(F::one());
+            // constrain using selector
+            // - product = prev product * (1 - board bit * (1 - shot bit))
+            // - a ship cell (board bit 1) that was not shot (shot bit 0) zeroes the product
+            let selector = meta.query_selector(selector);
+            Constraints::with_selector(
+                selector,
+                [(
+                    "running product of hit ship cells",
+                    prev * (one.clone() - board * (one - shot)) - product,
+                )],
+            )
+        });
+
+        // define poseidon hash chip
+        let poseidon = Pow5Chip::<F, 3, 2>::configure::<S>(
+            meta,
+            [advice[0], advice[1], advice[2]],
+            advice[3],
+            [fixed[3], fixed[4], fixed[5]],
+            [fixed[0], fixed[1], fixed[2]],
+        );
+
+        WinConfig {
+            num2bits,
+            poseidon,
+            input,
+            advice,
+            instance,
+            fixed,
+            selector,
+            _marker: PhantomData,
+        }
+    }
+
+    /**
+     * Synthesize a proof of whether every ship cell on a committed board has been shot
+     *
+     * @param board - the private board state in BinaryValue form
+     * @param shots - every shot fired at the board (serialized like board state) in BinaryValue form
+     * @param salt - per-game salt the board is committed under (game id exported to salt.row)
+     *              or None for the unsalted digest
+     * @return - Ok if synthesis executes successfully
+     */
+    pub fn synthesize(
+        &self,
+        mut layouter: impl Layouter<F>,
+        board: BinaryValue,
+        shots: BinaryValue,
+        salt: Option<BoardSalt<F>>,
+    ) -> Result<(), Error> {
+        // compute values to witness
+        let board_state = F::from_u128(board.lower_u128());
+        let shots_commitment = F::from_u128(shots.lower_u128());
+        let bits = [
+            board.bitfield::<F, BOARD_SIZE>(),
+            shots.bitfield::<F, BOARD_SIZE>(),
+        ];
+        // load inputs as advice
+        let inputs = self.load_advice(&mut layouter, board_state, shots_commitment)?;
+        // decompose board state and shots into constrained bits
+        let [board_bits, shot_bits] = self.decompose(&mut layouter, inputs.clone(), bits)?;
+        // compute whether every board bit was shot
+        let win = self.win_product(&mut layouter, board_bits, shot_bits)?;
+        // hash board state
+        let hashed_state = self.hash_board(&mut layouter, inputs[0].clone(), salt)?;
+        // export public values
+        let layout = InstanceLayout::Win;
+        for (cell, input) in [
+            (hashed_state.cell(), PublicInput::Digest),
+            (inputs[1].cell(), PublicInput::Shots),
+            (win.cell(), PublicInput::Win),
+        ] {
+            layouter.constrain_instance(cell, self.config.instance, layout.row(input))?;
+        }
+        Ok(())
+    }
+}
+
+impl<S: Spec<F, 3, 2>, F: FieldExt> WinInstructions<S, F> for WinChip<S, F> {
+    fn load_advice(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        board_state: F,
+        shots: F,
+    ) -> Result<[AssignedCell<F, F>; 2], Error> {
+        layouter.assign_region(
+            || "load private WinChip advice values",
+            |mut region| {
+                let board_state = region.assign_advice(
+                    || "assign board state",
+                    self.config.input,
+                    0,
+                    || Value::known(board_state),
+                )?;
+                let shots = region.assign_advice(
+                    || "assign shots fired",
+                    self.config.input,
+                    1,
+                    || Value::known(shots),
+                )?;
+                Ok([board_state, shots])
+            },
+        )
+    }
+
+    fn decompose(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        num: [AssignedCell<F, F>; 2],
+        bits: [[F; BOARD_SIZE]; 2],
+    ) -> Result<[AssignedBits<F>; 2], Error> {
+        decompose_board_values(
+            layouter,
+            self.config.num2bits,
+            num,
+            bits,
+            ["board_state", "shots"],
+        )
+    }
+
+    fn win_product(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        board: AssignedBits<F>,
+        shots: AssignedBits<F>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        layouter.assign_region(
+            || "win running product",
+            |mut region: Region<F>| {
+                // start the product at 1 so Rotation::prev() on the first bit row is constrained
+                let mut product = region.assign_advice_from_constant(
+                    || "initial win product",
+                    self.config.advice[2],
+                    0,
+                    F::one(),
+                )?;
+                for i in 0..BOARD_SIZE {
+                    let bit = board[i].copy_advice(
+                        || format!("copy board bit {}", i),
+                        &mut region,
+                        self.config.advice[0],
+                        i + 1,
+                    )?;
+                    let shot = shots[i].copy_advice(
+                        || format!("copy shot bit {}", i),
+                        &mut region,
+                        self.config.advice[1],
+                        i + 1,
+                    )?;
+                    let unhit = bit.value().copied() * (Value::known(F::one()) - shot.value());
+                    product = region.assign_advice(
+                        || format!("win product {}", i),
+                        self.config.advice[2],
+                        i + 1,
+                        || product.value().copied() * (Value::known(F::one()) - unhit),
+                    )?;
+                    self.config.selector.enable(&mut region, i + 1)?;
+                }
+                Ok(product)
+            },
+        )
+    }
+
+    fn hash_board(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        preimage: AssignedCell<F, F>,
+        salt: Option<BoardSalt<F>>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        commit_board_state::<S, F>(
+            self.config.poseidon.clone(),
+            layouter,
+            self.config.advice[0],
+            self.config.instance,
+            preimage,
+            salt,
+        )
+    }
+}
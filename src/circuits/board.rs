@@ -1,7 +1,13 @@
 use {
     crate::{
-        chips::board::{BoardChip, BoardConfig},
-        utils::binary::BinaryValue,
+        chips::board::{BoardChip, BoardConfig, BoardSalt},
+        utils::{
+            binary::BinaryValue,
+            board::{BoardBuilder, PlacementError},
+            instance::{InstanceLayout, PublicInput},
+            layout,
+            ship::DEFAULT_WITNESS_OPTIONS,
+        },
     },
     halo2_gadgets::poseidon::primitives::Spec,
     halo2_proofs::{
@@ -13,7 +19,7 @@ use {
 };
 
 #[derive(Debug, Clone, Copy)]
-struct BoardCircuit<S: Spec<F, 3, 2>, F: FieldExt> {
+pub struct BoardCircuit<S: Spec<F, 3, 2>, F: FieldExt> {
     pub ship_commitments: [BinaryValue; 10],
     pub board: BinaryValue,
     _field: PhantomData<F>,
@@ -35,7 +41,8 @@ impl<S: Spec<F, 3, 2>, F: FieldExt> Circuit<F> for BoardCircuit<S, F> {
 
     fn synthesize(&self, config: Self::Config, layouter: impl Layouter<F>) -> Result<(), Error> {
         let chip = BoardChip::<S, F>::new(config);
-        chip.synthesize(layouter, self.ship_commitments, self.board)
+        let row = InstanceLayout::Board.row(PublicInput::Digest);
+        chip.synthesize(layouter, self.ship_commitments, self.board, row)
     }
 }
 
@@ -55,6 +62,93 @@ impl<S: Spec<F, 3, 2>, F: FieldExt> BoardCircuit<S, F> {
             _spec: PhantomData,
         }
     }
+
+    /**
+     * Construct a board circuit from a board built ship by ship
+     * @dev the placement gadgets are computed by the board chip from the ship commitments
+     *
+     * @param builder - the builder every ship was added to
+     * @return - instantiated BoardCircuit, or the ship types not yet added to the builder
+     */
+    pub fn from_builder(builder: BoardBuilder) -> Result<BoardCircuit<S, F>, PlacementError> {
+        let board = builder.build()?;
+        Ok(BoardCircuit::new(
+            board.witness(DEFAULT_WITNESS_OPTIONS),
+            board.state(DEFAULT_WITNESS_OPTIONS),
+        ))
+    }
+
+    /**
+     * Return the smallest k such that the circuit's layout fits in 2^k rows
+     * @dev lays the circuit out without witnessing it and adds the rows halo2 reserves for
+     *      blinding factors
+     *
+     * @return - minimum k to prove this circuit with, or the error laying it out
+     */
+    pub fn min_k(&self) -> Result<u32, Error> {
+        layout::min_k(self, |config: &BoardConfig<F>| vec![config.fixed[0]])
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SaltedBoardCircuit<S: Spec<F, 3, 2>, F: FieldExt> {
+    pub ship_commitments: [BinaryValue; 10],
+    pub board: BinaryValue,
+    pub secret: F,
+    pub game_id: F,
+    _spec: PhantomData<S>,
+}
+
+impl<S: Spec<F, 3, 2>, F: FieldExt> Circuit<F> for SaltedBoardCircuit<S, F> {
+    type Config = BoardConfig<F>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        SaltedBoardCircuit::new(self.ship_commitments, self.board, self.secret, self.game_id)
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        BoardChip::<S, F>::configure(meta)
+    }
+
+    fn synthesize(&self, config: Self::Config, layouter: impl Layouter<F>) -> Result<(), Error> {
+        let chip = BoardChip::<S, F>::new(config);
+        let layout = InstanceLayout::SaltedBoard;
+        let salt = BoardSalt {
+            secret: self.secret,
+            game_id: self.game_id,
+            row: layout.row(PublicInput::GameId),
+        };
+        let row = layout.row(PublicInput::Digest);
+        chip.synthesize_salted(layouter, self.ship_commitments, self.board, salt, row)
+    }
+}
+
+impl<S: Spec<F, 3, 2>, F: FieldExt> SaltedBoardCircuit<S, F> {
+    /**
+     * Construct a board circuit committing to the board under a per-game salt
+     * @dev the public digest is prover::commit_board(board, secret, game_id)
+     *
+     * @param ship_commitments - 10x private ship commitments indicating a horizontal or vertical placement
+     * @param board - private board state
+     * @param secret - the player's private secret, reused across games
+     * @param game_id - public identifier of the game
+     * @return - instantiated SaltedBoardCircuit object
+     */
+    pub fn new(
+        ship_commitments: [BinaryValue; 10],
+        board: BinaryValue,
+        secret: F,
+        game_id: F,
+    ) -> SaltedBoardCircuit<S, F> {
+        SaltedBoardCircuit {
+            ship_commitments,
+            board,
+            secret,
+            game_id,
+            _spec: PhantomData,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -62,17 +156,32 @@ mod test {
 
     use {
         super::*,
-        crate::utils::{
-            board::Board,
-            deck::Deck,
-            ship::{WitnessOption, DEFAULT_WITNESS_OPTIONS},
+        crate::{
+            prover::{
+                board_digest, commit_board, translate_failure, translate_layout_failure,
+                GameValidationError, BOARD_K,
+            },
+            test_utils::{
+                assert_fails_with, assert_valid, assert_valid_with_instances, empirical_min_k,
+            },
+            utils::{
+                board::{adversarial, random_valid_fleet, Board},
+                deck::Deck,
+                layout::LayoutRecorder,
+                ship::{Ship, ShipType, WitnessOption},
+            },
         },
         halo2_gadgets::poseidon::primitives::{ConstantLength, Hash as Poseidon, P128Pow5T3},
+        halo2_proofs::{
+            circuit::{Layouter, SimpleFloorPlanner},
+            plonk::{Circuit, ConstraintSystem, Error},
+        },
         halo2_proofs::{
             dev::{FailureLocation, MockProver, VerifyFailure},
             pasta::Fp,
             plonk::Any,
         },
+        proptest::test_runner::{RngAlgorithm, TestRng},
     };
 
     #[test]
@@ -95,9 +204,59 @@ mod test {
             board.witness(DEFAULT_WITNESS_OPTIONS),
             board.state(DEFAULT_WITNESS_OPTIONS),
         );
-        let prover = MockProver::run(12, &circuit, vec![vec![board_commitment]]).unwrap();
-        // expect proof success
-        assert_eq!(prover.verify(), Ok(()));
+        // expect proof success, and only with the board commitment as the public instance
+        assert_valid_with_instances(&circuit, 12, vec![vec![board_commitment]]);
+    }
+
+    #[test]
+    fn valid_from_builder() {
+        let mut builder = BoardBuilder::new();
+        for ship in [
+            Ship::new(ShipType::Destroyer, 6, 1, false),
+            Ship::new(ShipType::Carrier, 3, 3, true),
+            Ship::new(ShipType::Submarine, 0, 5, true),
+            Ship::new(ShipType::Battleship, 5, 4, false),
+            Ship::new(ShipType::Cruiser, 0, 1, false),
+        ] {
+            builder.add_ship(ship).unwrap();
+        }
+        let board = builder.build().unwrap();
+        let circuit = BoardCircuit::<P128Pow5T3, Fp>::from_builder(builder).unwrap();
+        assert_eq!(circuit.board, board.state(DEFAULT_WITNESS_OPTIONS));
+        let board_commitment = Poseidon::<_, P128Pow5T3, ConstantLength<1>, 3, 2>::init()
+            .hash([Fp::from_u128(circuit.board.lower_u128())]);
+        assert_valid_with_instances(&circuit, 12, vec![vec![board_commitment]]);
+        // an unfinished builder cannot produce a circuit
+        assert_eq!(
+            BoardCircuit::<P128Pow5T3, Fp>::from_builder(BoardBuilder::new()).unwrap_err(),
+            PlacementError::Incomplete(BoardBuilder::new().missing())
+        );
+    }
+
+    #[test]
+    fn row_budget() {
+        // keeps the documented circuit size honest as constraints are added to the board circuit
+        let board = Board::from(&Deck::default());
+        let circuit = BoardCircuit::<P128Pow5T3, Fp>::new(
+            board.witness(DEFAULT_WITNESS_OPTIONS),
+            board.state(DEFAULT_WITNESS_OPTIONS),
+        );
+        let min_k = circuit.min_k().unwrap();
+        assert!(
+            min_k <= BOARD_K,
+            "board circuit needs 2^{} rows but BOARD_K = {}: raise BOARD_K to at least {}",
+            min_k,
+            BOARD_K,
+            min_k
+        );
+        // the row count plus blinding rows is exact: one k smaller no longer synthesizes
+        assert_eq!(
+            min_k,
+            empirical_min_k(
+                &circuit,
+                vec![vec![board_digest(board.state(DEFAULT_WITNESS_OPTIONS))]]
+            )
+        );
     }
 
     #[test]
@@ -120,9 +279,8 @@ mod test {
             board.witness(DEFAULT_WITNESS_OPTIONS),
             board.state(DEFAULT_WITNESS_OPTIONS),
         );
-        let prover = MockProver::run(12, &circuit, vec![vec![board_commitment]]).unwrap();
         // expect proof success
-        assert_eq!(prover.verify(), Ok(()));
+        assert_valid(&circuit, 12, vec![vec![board_commitment]]);
     }
 
     #[test]
@@ -152,11 +310,12 @@ mod test {
             shot_commitments,
             board.state(DEFAULT_WITNESS_OPTIONS),
         );
-        let prover = MockProver::run(12, &circuit, vec![vec![board_commitment]]).unwrap();
         // expected failure constraint: either horizontal or vertical placement is 0
-        assert_eq!(
-            prover.verify(),
-            Err(vec![VerifyFailure::ConstraintNotSatisfied {
+        assert_fails_with(
+            &circuit,
+            12,
+            vec![vec![board_commitment]],
+            vec![VerifyFailure::ConstraintNotSatisfied {
                 constraint: (
                     (40, "Commitment orientation H OR V == 0 constraint").into(),
                     0,
@@ -175,9 +334,9 @@ mod test {
                     (
                         ((Any::Advice, 1).into(), 0).into(),
                         String::from("0x3c00000000"),
-                    )
-                ]
-            },])
+                    ),
+                ],
+            }],
         );
     }
 
@@ -204,11 +363,12 @@ mod test {
             shot_commitments,
             board.state(DEFAULT_WITNESS_OPTIONS),
         );
-        let prover = MockProver::run(12, &circuit, vec![vec![board_commitment]]).unwrap();
         // expect proof failure
-        assert_eq!(
-            prover.verify(),
-            Err(vec![
+        assert_fails_with(
+            &circuit,
+            12,
+            vec![vec![board_commitment]],
+            vec![
                 // expect 5 bits, counts 0 bits
                 VerifyFailure::ConstraintNotSatisfied {
                     constraint: (
@@ -221,23 +381,28 @@ mod test {
                         region: (13, "constrain running sum output").into(),
                         offset: 0,
                     },
-                    cell_values: vec![(((Any::Advice, 1).into(), 0).into(), String::from("0"),),]
+                    cell_values: vec![(((Any::Advice, 1).into(), 0).into(), String::from("0"))],
                 },
                 // expects one full (true, true, true, true, true) 5-bit window, counts none
                 VerifyFailure::ConstraintNotSatisfied {
                     constraint: (
                         (15, "running sum constraints").into(),
-                        1,
-                        "One full bit window",
+                        2,
+                        "One full horizontal bit window",
                     )
                         .into(),
                     location: FailureLocation::InRegion {
                         region: (13, "constrain running sum output").into(),
                         offset: 0,
                     },
-                    cell_values: vec![(((Any::Advice, 2).into(), 0).into(), String::from("0"),),]
-                }
-            ])
+                    cell_values: vec![
+                        (((Any::Advice, 0).into(), 0).into(), String::from("0")),
+                        (((Any::Advice, 2).into(), 0).into(), String::from("0")),
+                    ],
+                },
+                // board occupies 12 cells instead of the fleet's 17
+                tonnage_failure("0xc"),
+            ],
         );
     }
 
@@ -266,23 +431,27 @@ mod test {
         // construct BoardValidity circuit
         let circuit =
             BoardCircuit::<P128Pow5T3, Fp>::new(shot_commitments, board.state(witness_options));
-        let prover = MockProver::run(12, &circuit, vec![vec![board_commitment]]).unwrap();
         // expected failure constraint: cannot find a full ship placement bit window
-        assert_eq!(
-            prover.verify(),
-            Err(vec![VerifyFailure::ConstraintNotSatisfied {
+        assert_fails_with(
+            &circuit,
+            12,
+            vec![vec![board_commitment]],
+            vec![VerifyFailure::ConstraintNotSatisfied {
                 constraint: (
                     (15, "running sum constraints").into(),
-                    1,
-                    "One full bit window",
+                    3,
+                    "One full vertical bit window",
                 )
                     .into(),
                 location: FailureLocation::InRegion {
                     region: (13, "constrain running sum output").into(),
                     offset: 0,
                 },
-                cell_values: vec![(((Any::Advice, 2).into(), 0).into(), String::from("0"),),]
-            }])
+                cell_values: vec![
+                    (((Any::Advice, 0).into(), 0).into(), String::from("1")),
+                    (((Any::Advice, 2).into(), 0).into(), String::from("0")),
+                ],
+            }],
         );
     }
 
@@ -311,23 +480,28 @@ mod test {
         // construct BoardValidity circuit
         let circuit =
             BoardCircuit::<P128Pow5T3, Fp>::new(shot_commitments, board.state(witness_options));
-        let prover = MockProver::run(12, &circuit, vec![vec![board_commitment]]).unwrap();
         // expected failure constraint: either horizontal or vertical placement is 0
-        assert_eq!(
-            prover.verify(),
-            Err(vec![VerifyFailure::ConstraintNotSatisfied {
-                constraint: (
-                    (15, "running sum constraints").into(),
-                    0,
-                    "Placed ship of correct length",
-                )
-                    .into(),
-                location: FailureLocation::InRegion {
-                    region: (13, "constrain running sum output").into(),
-                    offset: 0,
+        assert_fails_with(
+            &circuit,
+            12,
+            vec![vec![board_commitment]],
+            vec![
+                VerifyFailure::ConstraintNotSatisfied {
+                    constraint: (
+                        (15, "running sum constraints").into(),
+                        0,
+                        "Placed ship of correct length",
+                    )
+                        .into(),
+                    location: FailureLocation::InRegion {
+                        region: (13, "constrain running sum output").into(),
+                        offset: 0,
+                    },
+                    cell_values: vec![(((Any::Advice, 1).into(), 0).into(), String::from("0x6"))],
                 },
-                cell_values: vec![(((Any::Advice, 1).into(), 0).into(), String::from("0x6"),),]
-            }])
+                // board occupies 18 cells instead of the fleet's 17
+                tonnage_failure("0x12"),
+            ],
         );
     }
 
@@ -356,11 +530,12 @@ mod test {
         // construct BoardValidity circuit
         let circuit =
             BoardCircuit::<P128Pow5T3, Fp>::new(shot_commitments, board.state(witness_options));
-        let prover = MockProver::run(12, &circuit, vec![vec![board_commitment]]).unwrap();
         // expected failure constraint: too many bits; too many full bit windows
-        assert_eq!(
-            prover.verify(),
-            Err(vec![
+        assert_fails_with(
+            &circuit,
+            12,
+            vec![vec![board_commitment]],
+            vec![
                 // counted 5 bits for battleship placement chip expecting 4 bits
                 VerifyFailure::ConstraintNotSatisfied {
                     constraint: (
@@ -373,24 +548,29 @@ mod test {
                         region: (16, "constrain running sum output").into(),
                         offset: 0,
                     },
-                    cell_values: vec![(((Any::Advice, 1).into(), 0).into(), String::from("0x5"),),]
+                    cell_values: vec![(((Any::Advice, 1).into(), 0).into(), String::from("0x5"))],
                 },
                 // counted 2 full bit windows for battleship placement chip expecting 1 full bit window
                 // full window at 54, 64, 74, 84 expected, full window at 64, 74, 84, 94 not expected
                 VerifyFailure::ConstraintNotSatisfied {
                     constraint: (
                         (20, "running sum constraints").into(),
-                        1,
-                        "One full bit window",
+                        2,
+                        "One full horizontal bit window",
                     )
                         .into(),
                     location: FailureLocation::InRegion {
                         region: (16, "constrain running sum output").into(),
                         offset: 0,
                     },
-                    cell_values: vec![(((Any::Advice, 2).into(), 0).into(), String::from("0x2"),),]
-                }
-            ])
+                    cell_values: vec![
+                        (((Any::Advice, 0).into(), 0).into(), String::from("0")),
+                        (((Any::Advice, 2).into(), 0).into(), String::from("0x2")),
+                    ],
+                },
+                // board occupies 18 cells instead of the fleet's 17
+                tonnage_failure("0x12"),
+            ],
         );
     }
 
@@ -419,11 +599,12 @@ mod test {
         // construct BoardValidity circuit
         let circuit =
             BoardCircuit::<P128Pow5T3, Fp>::new(shot_commitments, board.state(witness_options));
-        let prover = MockProver::run(12, &circuit, vec![vec![board_commitment]]).unwrap();
         // expected failure constraint: too many bits; too many full bit windows
-        assert_eq!(
-            prover.verify(),
-            Err(vec![
+        assert_fails_with(
+            &circuit,
+            12,
+            vec![vec![board_commitment]],
+            vec![
                 // counted 1 bits for destroyer placement, expecting 2
                 VerifyFailure::ConstraintNotSatisfied {
                     constraint: (
@@ -436,23 +617,28 @@ mod test {
                         region: (25, "constrain running sum output").into(),
                         offset: 0,
                     },
-                    cell_values: vec![(((Any::Advice, 1).into(), 0).into(), String::from("1"),),]
+                    cell_values: vec![(((Any::Advice, 1).into(), 0).into(), String::from("1"))],
                 },
                 // counted 0 full bit windows, expecting 1
                 VerifyFailure::ConstraintNotSatisfied {
                     constraint: (
                         (35, "running sum constraints").into(),
-                        1,
-                        "One full bit window",
+                        3,
+                        "One full vertical bit window",
                     )
                         .into(),
                     location: FailureLocation::InRegion {
                         region: (25, "constrain running sum output").into(),
                         offset: 0,
                     },
-                    cell_values: vec![(((Any::Advice, 2).into(), 0).into(), String::from("0"),),]
-                }
-            ])
+                    cell_values: vec![
+                        (((Any::Advice, 0).into(), 0).into(), String::from("1")),
+                        (((Any::Advice, 2).into(), 0).into(), String::from("0")),
+                    ],
+                },
+                // board occupies 16 cells instead of the fleet's 17
+                tonnage_failure("0x10"),
+            ],
         );
     }
 
@@ -478,23 +664,27 @@ mod test {
             board.witness(DEFAULT_WITNESS_OPTIONS),
             board.state(DEFAULT_WITNESS_OPTIONS),
         );
-        let prover = MockProver::run(12, &circuit, vec![vec![board_commitment]]).unwrap();
         // expected failure constraint: no full bit window found since consecutive bits are not in the same row
-        assert_eq!(
-            prover.verify(),
-            Err(vec![VerifyFailure::ConstraintNotSatisfied {
+        assert_fails_with(
+            &circuit,
+            12,
+            vec![vec![board_commitment]],
+            vec![VerifyFailure::ConstraintNotSatisfied {
                 constraint: (
                     (25, "running sum constraints").into(),
-                    1,
-                    "One full bit window",
+                    2,
+                    "One full horizontal bit window",
                 )
                     .into(),
                 location: FailureLocation::InRegion {
                     region: (19, "constrain running sum output").into(),
                     offset: 0,
                 },
-                cell_values: vec![(((Any::Advice, 2).into(), 0).into(), String::from("0"),),]
-            }])
+                cell_values: vec![
+                    (((Any::Advice, 0).into(), 0).into(), String::from("0")),
+                    (((Any::Advice, 2).into(), 0).into(), String::from("0")),
+                ],
+            }],
         );
     }
 
@@ -520,23 +710,27 @@ mod test {
             board.witness(DEFAULT_WITNESS_OPTIONS),
             board.state(DEFAULT_WITNESS_OPTIONS),
         );
-        let prover = MockProver::run(12, &circuit, vec![vec![board_commitment]]).unwrap();
         // expected failure constraint: no full bit window found since consecutive bits are not in the same row
-        assert_eq!(
-            prover.verify(),
-            Err(vec![VerifyFailure::ConstraintNotSatisfied {
+        assert_fails_with(
+            &circuit,
+            12,
+            vec![vec![board_commitment]],
+            vec![VerifyFailure::ConstraintNotSatisfied {
                 constraint: (
                     (15, "running sum constraints").into(),
-                    1,
-                    "One full bit window",
+                    3,
+                    "One full vertical bit window",
                 )
                     .into(),
                 location: FailureLocation::InRegion {
                     region: (13, "constrain running sum output").into(),
                     offset: 0,
                 },
-                cell_values: vec![(((Any::Advice, 2).into(), 0).into(), String::from("0"),),]
-            }])
+                cell_values: vec![
+                    (((Any::Advice, 0).into(), 0).into(), String::from("1")),
+                    (((Any::Advice, 2).into(), 0).into(), String::from("0")),
+                ],
+            }],
         );
     }
 
@@ -563,10 +757,11 @@ mod test {
             board.state(DEFAULT_WITNESS_OPTIONS),
         );
         // expected failure constraint: more than 2 bits found in a transpose row, sum of all commitment bits in row != transposed commitment bit
-        let prover = MockProver::run(12, &circuit, vec![vec![board_commitment]]).unwrap();
-        assert_eq!(
-            prover.verify(),
-            Err(vec![
+        assert_fails_with(
+            &circuit,
+            12,
+            vec![vec![board_commitment]],
+            vec![
                 // sum of all bits in commitment row != transposed commitment bit
                 //      this is constrained to be binary, so it is impossible to not be 0 or 1
                 //      or else bits2num throws constraint error instead
@@ -583,18 +778,18 @@ mod test {
                         offset: 16,
                     },
                     cell_values: vec![
-                        (((Any::Advice, 0).into(), 0).into(), String::from("0"),),
-                        (((Any::Advice, 1).into(), 0).into(), String::from("0"),),
-                        (((Any::Advice, 2).into(), 0).into(), String::from("0"),),
-                        (((Any::Advice, 3).into(), 0).into(), String::from("0"),),
-                        (((Any::Advice, 4).into(), 0).into(), String::from("1"),),
-                        (((Any::Advice, 5).into(), 0).into(), String::from("0"),),
-                        (((Any::Advice, 6).into(), 0).into(), String::from("0"),),
-                        (((Any::Advice, 7).into(), 0).into(), String::from("0"),),
-                        (((Any::Advice, 8).into(), 0).into(), String::from("1"),),
-                        (((Any::Advice, 9).into(), 0).into(), String::from("0"),),
-                        (((Any::Advice, 10).into(), 0).into(), String::from("1"),)
-                    ]
+                        (((Any::Advice, 0).into(), 0).into(), String::from("0")),
+                        (((Any::Advice, 1).into(), 0).into(), String::from("0")),
+                        (((Any::Advice, 2).into(), 0).into(), String::from("0")),
+                        (((Any::Advice, 3).into(), 0).into(), String::from("0")),
+                        (((Any::Advice, 4).into(), 0).into(), String::from("1")),
+                        (((Any::Advice, 5).into(), 0).into(), String::from("0")),
+                        (((Any::Advice, 6).into(), 0).into(), String::from("0")),
+                        (((Any::Advice, 7).into(), 0).into(), String::from("0")),
+                        (((Any::Advice, 8).into(), 0).into(), String::from("1")),
+                        (((Any::Advice, 9).into(), 0).into(), String::from("0")),
+                        (((Any::Advice, 10).into(), 0).into(), String::from("1")),
+                    ],
                 },
                 // fail constraint: sum of all bits in commitment row != 0 or 1
                 VerifyFailure::ConstraintNotSatisfied {
@@ -609,22 +804,55 @@ mod test {
                         offset: 16,
                     },
                     cell_values: vec![
-                        (((Any::Advice, 0).into(), 0).into(), String::from("0"),),
-                        (((Any::Advice, 1).into(), 0).into(), String::from("0"),),
-                        (((Any::Advice, 2).into(), 0).into(), String::from("0"),),
-                        (((Any::Advice, 3).into(), 0).into(), String::from("0"),),
-                        (((Any::Advice, 4).into(), 0).into(), String::from("1"),),
-                        (((Any::Advice, 5).into(), 0).into(), String::from("0"),),
-                        (((Any::Advice, 6).into(), 0).into(), String::from("0"),),
-                        (((Any::Advice, 7).into(), 0).into(), String::from("0"),),
-                        (((Any::Advice, 8).into(), 0).into(), String::from("1"),),
-                        (((Any::Advice, 9).into(), 0).into(), String::from("0"),),
-                    ]
-                }
-            ])
+                        (((Any::Advice, 0).into(), 0).into(), String::from("0")),
+                        (((Any::Advice, 1).into(), 0).into(), String::from("0")),
+                        (((Any::Advice, 2).into(), 0).into(), String::from("0")),
+                        (((Any::Advice, 3).into(), 0).into(), String::from("0")),
+                        (((Any::Advice, 4).into(), 0).into(), String::from("1")),
+                        (((Any::Advice, 5).into(), 0).into(), String::from("0")),
+                        (((Any::Advice, 6).into(), 0).into(), String::from("0")),
+                        (((Any::Advice, 7).into(), 0).into(), String::from("0")),
+                        (((Any::Advice, 8).into(), 0).into(), String::from("1")),
+                        (((Any::Advice, 9).into(), 0).into(), String::from("0")),
+                    ],
+                },
+                // board occupies 16 cells instead of the fleet's 17
+                tonnage_failure("0x10"),
+            ],
         );
     }
 
+    #[test]
+    fn invalid_duplicate_coordinates() {
+        // construct battleship board pattern #1 with modification
+        // place submarine at the exact coordinates of the cruiser (both span 3 cells)
+        let board = Board::from(&Deck::from([
+            Some((3, 3, true)),
+            Some((5, 4, false)),
+            Some((0, 1, false)),
+            Some((0, 1, false)),
+            Some((6, 1, false)),
+        ]));
+        let board_commitment =
+            Poseidon::<_, P128Pow5T3, ConstantLength<1>, 3, 2>::init().hash([Fp::from_u128(
+                board.state(DEFAULT_WITNESS_OPTIONS).lower_u128(),
+            )]);
+        let circuit = BoardCircuit::<P128Pow5T3, Fp>::new(
+            board.witness(DEFAULT_WITNESS_OPTIONS),
+            board.state(DEFAULT_WITNESS_OPTIONS),
+        );
+        // every failure is attributed to the overlap rather than to either ship placement
+        let prover = MockProver::run(12, &circuit, vec![vec![board_commitment]]).unwrap();
+        let failures = prover.verify().unwrap_err();
+        assert!(failures.iter().all(|failure| matches!(
+            translate_failure(failure),
+            GameValidationError::Overlap | GameValidationError::WrongTonnage
+        )));
+        assert!(failures
+            .iter()
+            .any(|failure| translate_failure(failure) == GameValidationError::Overlap));
+    }
+
     #[test]
     fn invalid_collision_transposed() {
         // @notice: transposed means the placement commitment would be valid if
@@ -651,10 +879,11 @@ mod test {
             board.state(DEFAULT_WITNESS_OPTIONS),
         );
         // expected failure constraint: more than 2 bits found in a transpose row, sum of all commitment bits in row != transposed commitment bit
-        let prover = MockProver::run(12, &circuit, vec![vec![board_commitment]]).unwrap();
-        assert_eq!(
-            prover.verify(),
-            Err(vec![
+        assert_fails_with(
+            &circuit,
+            12,
+            vec![vec![board_commitment]],
+            vec![
                 // sum of all bits in commitment row != transposed commitment bit
                 //      this is constrained to be binary, so it is impossible to not be 0 or 1
                 //      or else bits2num throws constraint error instead
@@ -671,18 +900,18 @@ mod test {
                         offset: 46,
                     },
                     cell_values: vec![
-                        (((Any::Advice, 0).into(), 0).into(), String::from("1"),),
-                        (((Any::Advice, 1).into(), 0).into(), String::from("0"),),
-                        (((Any::Advice, 2).into(), 0).into(), String::from("0"),),
-                        (((Any::Advice, 3).into(), 0).into(), String::from("0"),),
-                        (((Any::Advice, 4).into(), 0).into(), String::from("0"),),
-                        (((Any::Advice, 5).into(), 0).into(), String::from("0"),),
-                        (((Any::Advice, 6).into(), 0).into(), String::from("0"),),
-                        (((Any::Advice, 7).into(), 0).into(), String::from("0"),),
-                        (((Any::Advice, 8).into(), 0).into(), String::from("0"),),
-                        (((Any::Advice, 9).into(), 0).into(), String::from("1"),),
-                        (((Any::Advice, 10).into(), 0).into(), String::from("1"),)
-                    ]
+                        (((Any::Advice, 0).into(), 0).into(), String::from("1")),
+                        (((Any::Advice, 1).into(), 0).into(), String::from("0")),
+                        (((Any::Advice, 2).into(), 0).into(), String::from("0")),
+                        (((Any::Advice, 3).into(), 0).into(), String::from("0")),
+                        (((Any::Advice, 4).into(), 0).into(), String::from("0")),
+                        (((Any::Advice, 5).into(), 0).into(), String::from("0")),
+                        (((Any::Advice, 6).into(), 0).into(), String::from("0")),
+                        (((Any::Advice, 7).into(), 0).into(), String::from("0")),
+                        (((Any::Advice, 8).into(), 0).into(), String::from("0")),
+                        (((Any::Advice, 9).into(), 0).into(), String::from("1")),
+                        (((Any::Advice, 10).into(), 0).into(), String::from("1")),
+                    ],
                 },
                 // fail constraint: sum of all bits in commitment row != 0 or 1
                 VerifyFailure::ConstraintNotSatisfied {
@@ -697,19 +926,21 @@ mod test {
                         offset: 46,
                     },
                     cell_values: vec![
-                        (((Any::Advice, 0).into(), 0).into(), String::from("1"),),
-                        (((Any::Advice, 1).into(), 0).into(), String::from("0"),),
-                        (((Any::Advice, 2).into(), 0).into(), String::from("0"),),
-                        (((Any::Advice, 3).into(), 0).into(), String::from("0"),),
-                        (((Any::Advice, 4).into(), 0).into(), String::from("0"),),
-                        (((Any::Advice, 5).into(), 0).into(), String::from("0"),),
-                        (((Any::Advice, 6).into(), 0).into(), String::from("0"),),
-                        (((Any::Advice, 7).into(), 0).into(), String::from("0"),),
-                        (((Any::Advice, 8).into(), 0).into(), String::from("0"),),
-                        (((Any::Advice, 9).into(), 0).into(), String::from("1"),),
-                    ]
-                }
-            ])
+                        (((Any::Advice, 0).into(), 0).into(), String::from("1")),
+                        (((Any::Advice, 1).into(), 0).into(), String::from("0")),
+                        (((Any::Advice, 2).into(), 0).into(), String::from("0")),
+                        (((Any::Advice, 3).into(), 0).into(), String::from("0")),
+                        (((Any::Advice, 4).into(), 0).into(), String::from("0")),
+                        (((Any::Advice, 5).into(), 0).into(), String::from("0")),
+                        (((Any::Advice, 6).into(), 0).into(), String::from("0")),
+                        (((Any::Advice, 7).into(), 0).into(), String::from("0")),
+                        (((Any::Advice, 8).into(), 0).into(), String::from("0")),
+                        (((Any::Advice, 9).into(), 0).into(), String::from("1")),
+                    ],
+                },
+                // board occupies 16 cells instead of the fleet's 17
+                tonnage_failure("0x10"),
+            ],
         );
     }
 
@@ -734,23 +965,24 @@ mod test {
             board.witness(DEFAULT_WITNESS_OPTIONS),
             board.state(DEFAULT_WITNESS_OPTIONS),
         );
-        let prover = MockProver::run(12, &circuit, vec![vec![board_commitment]]).unwrap();
         // expect a permutation failure when the computed board hash does not match the advice given to the circuit
-        assert_eq!(
-            prover.verify(),
-            Err(vec![
+        assert_fails_with(
+            &circuit,
+            12,
+            vec![vec![board_commitment]],
+            vec![
                 VerifyFailure::Permutation {
                     column: (Any::Advice, 0).into(),
                     location: FailureLocation::InRegion {
-                        region: (30, "permute state").into(),
-                        offset: 36
-                    }
+                        region: (31, "permute state").into(),
+                        offset: 36,
+                    },
                 },
                 VerifyFailure::Permutation {
                     column: (Any::Instance, 0).into(),
-                    location: FailureLocation::OutsideRegion { row: 0 }
-                }
-            ])
+                    location: FailureLocation::OutsideRegion { row: 0 },
+                },
+            ],
         );
     }
 
@@ -777,27 +1009,392 @@ mod test {
             board.state(DEFAULT_WITNESS_OPTIONS),
         );
         // add one to the public board commitment to make it invalid
-        let prover =
-            MockProver::run(12, &circuit, vec![vec![board_commitment + Fp::one()]]).unwrap();
         // expect a permutation failure when the computed board hash does not match the advice given to the circuit
-        assert_eq!(
-            prover.verify(),
-            Err(vec![
+        assert_fails_with(
+            &circuit,
+            12,
+            vec![vec![board_commitment + Fp::one()]],
+            vec![
                 VerifyFailure::Permutation {
                     column: (Any::Advice, 0).into(),
                     location: FailureLocation::InRegion {
-                        region: (30, "permute state").into(),
-                        offset: 36
-                    }
+                        region: (31, "permute state").into(),
+                        offset: 36,
+                    },
                 },
                 VerifyFailure::Permutation {
                     column: (Any::Instance, 0).into(),
-                    location: FailureLocation::OutsideRegion { row: 0 }
-                }
-            ])
+                    location: FailureLocation::OutsideRegion { row: 0 },
+                },
+            ],
         );
     }
 
+    /**
+     * Expected failure when the board's occupied cell count does not equal the fleet tonnage
+     *
+     * @param sum - the hex string of the board's popcount in the tonnage running sum
+     * @return - the tonnage constraint failure reported by the mock prover
+     */
+    fn tonnage_failure(sum: &str) -> VerifyFailure {
+        VerifyFailure::ConstraintNotSatisfied {
+            constraint: (
+                (42, "board tonnage constraint").into(),
+                0,
+                "Board occupies fleet tonnage",
+            )
+                .into(),
+            location: FailureLocation::InRegion {
+                region: (32, "constrain board tonnage").into(),
+                offset: 100,
+            },
+            cell_values: vec![(((Any::Advice, 1).into(), 0).into(), String::from(sum))],
+        }
+    }
+
+    /**
+     * Mock prove a board built from a deck with default witness options
+     *
+     * @param deck - the fleet to place on the board
+     * @return - the result of verifying the board proof against the board's commitment
+     */
+    fn prove_deck(deck: &Deck) -> Result<(), Vec<VerifyFailure>> {
+        let board = Board::from(deck);
+        let state = board.state(DEFAULT_WITNESS_OPTIONS);
+        let board_commitment = Poseidon::<_, P128Pow5T3, ConstantLength<1>, 3, 2>::init()
+            .hash([Fp::from_u128(state.lower_u128())]);
+        let circuit =
+            BoardCircuit::<P128Pow5T3, Fp>::new(board.witness(DEFAULT_WITNESS_OPTIONS), state);
+        let prover = MockProver::run(12, &circuit, vec![vec![board_commitment]]).unwrap();
+        prover.verify()
+    }
+
+    #[test]
+    fn valid_random_fleets() {
+        // deterministic seed so any fleet the circuit rejects is reproducible
+        let mut rng = TestRng::deterministic_rng(RngAlgorithm::ChaCha);
+        for _ in 0..3 {
+            assert_eq!(prove_deck(&random_valid_fleet(&mut rng)), Ok(()));
+        }
+    }
+
+    #[test]
+    fn valid_tonnage() {
+        // the classic fleet occupies 5 + 4 + 3 + 3 + 2 cells
+        assert_eq!(Deck::expected_tonnage(), 17);
+        let state = Board::from(&Deck::default()).state(DEFAULT_WITNESS_OPTIONS);
+        assert_eq!(state.value.count_ones(), Deck::expected_tonnage());
+        assert_eq!(prove_deck(&Deck::default()), Ok(()));
+    }
+
+    #[test]
+    fn adversarial_overlapping_fleet() {
+        // destroyer at (1, 0) shares cells (1, 0) and (2, 0) with the carrier at (0, 0)
+        // each collision fails both transpose row constraints with carrier H (advice 0) and
+        // destroyer H (advice 8) bits flipped in the same row
+        let mut failures = [1, 2]
+            .iter()
+            .flat_map(|offset| {
+                [
+                    (0, "Constrain trace value integrity", 11),
+                    (1, "Constrain transposition of bit", 10),
+                ]
+                .map(|(index, name, columns)| {
+                    VerifyFailure::ConstraintNotSatisfied {
+                        constraint: ((36, "transpose row constraint").into(), index, name).into(),
+                        location: FailureLocation::InRegion {
+                            region: (26, "Transpose ship commitments").into(),
+                            offset: *offset,
+                        },
+                        cell_values: (0..columns)
+                            .map(|column| {
+                                let flipped = column == 0 || column == 8 || column == 10;
+                                (
+                                    ((Any::Advice, column).into(), 0).into(),
+                                    String::from(if flipped { "1" } else { "0" }),
+                                )
+                            })
+                            .collect(),
+                    }
+                })
+            })
+            .collect::<Vec<VerifyFailure>>();
+        // overlapping cells are counted once so the board occupies 15 of the fleet's 17 cells
+        failures.push(tonnage_failure("0xf"));
+        assert_eq!(prove_deck(&adversarial::overlapping_fleet()), Err(failures));
+    }
+
+    #[test]
+    fn adversarial_off_board_fleet() {
+        // destroyer wraps from row 6 to row 7 (horizontal) or column 6 to column 7 (vertical)
+        for (orientation, index, name) in [
+            (false, 2, "One full horizontal bit window"),
+            (true, 3, "One full vertical bit window"),
+        ] {
+            assert_eq!(
+                prove_deck(&adversarial::off_board_fleet(orientation)),
+                Err(vec![VerifyFailure::ConstraintNotSatisfied {
+                    constraint: ((35, "running sum constraints").into(), index, name).into(),
+                    location: FailureLocation::InRegion {
+                        region: (25, "constrain running sum output").into(),
+                        offset: 0,
+                    },
+                    cell_values: vec![
+                        (
+                            ((Any::Advice, 0).into(), 0).into(),
+                            String::from(if orientation { "1" } else { "0" }),
+                        ),
+                        (((Any::Advice, 2).into(), 0).into(), String::from("0"),),
+                    ]
+                }])
+            );
+        }
+    }
+
+    #[test]
+    fn adversarial_out_of_range_fleet() {
+        // the destroyer commitment (H2 = commitment 8, V2 = commitment 9) sets bits 100 and 101,
+        // which the 100 bit num2bits decomposition cannot recompose. the truncated bits leave no
+        // destroyer placed, so its placement and the board tonnage fail with it
+        for (field, commitment) in [(adversarial::Field::X, 8), (adversarial::Field::Y, 9)] {
+            let running_sum = || FailureLocation::InRegion {
+                region: (25, "constrain running sum output").into(),
+                offset: 0,
+            };
+            assert_eq!(
+                prove_deck(&adversarial::out_of_range_fleet(field)),
+                Err(vec![
+                    VerifyFailure::ConstraintNotSatisfied {
+                        constraint: (
+                            (35, "running sum constraints").into(),
+                            0,
+                            "Placed ship of correct length"
+                        )
+                            .into(),
+                        location: running_sum(),
+                        cell_values: vec![(((Any::Advice, 1).into(), 0).into(), String::from("0"))],
+                    },
+                    VerifyFailure::ConstraintNotSatisfied {
+                        constraint: (
+                            (35, "running sum constraints").into(),
+                            2,
+                            "One full horizontal bit window"
+                        )
+                            .into(),
+                        location: running_sum(),
+                        cell_values: vec![
+                            (((Any::Advice, 0).into(), 0).into(), String::from("0")),
+                            (((Any::Advice, 2).into(), 0).into(), String::from("0")),
+                        ],
+                    },
+                    tonnage_failure("0xf"),
+                    VerifyFailure::Permutation {
+                        column: (Any::Advice, 1).into(),
+                        location: FailureLocation::InRegion {
+                            region: (commitment + 1, "num2bits").into(),
+                            offset: 100,
+                        },
+                    },
+                    VerifyFailure::Permutation {
+                        column: (Any::Advice, commitment).into(),
+                        location: FailureLocation::InRegion {
+                            region: (0, "load ship placements").into(),
+                            offset: 0,
+                        },
+                    },
+                ])
+            );
+        }
+    }
+
+    // board circuit configured with the variant rule that ships may not touch
+    struct NoTouchBoardCircuit(BoardCircuit<P128Pow5T3, Fp>);
+
+    impl Circuit<Fp> for NoTouchBoardCircuit {
+        type Config = BoardConfig<Fp>;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            NoTouchBoardCircuit(self.0.without_witnesses())
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            BoardChip::<P128Pow5T3, Fp>::configure_no_touch(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            self.0.synthesize(config, layouter)
+        }
+    }
+
+    #[test]
+    fn no_touch_variant() {
+        // destroyer at (5, 3) lies directly above the battleship at (5, 4) without overlapping
+        let spaced = [Some((6, 1, false)), Some((5, 3, false))].map(|destroyer| {
+            let board = Board::from(&Deck::from([
+                Some((3, 3, true)),
+                Some((5, 4, false)),
+                Some((0, 1, false)),
+                Some((0, 5, true)),
+                destroyer,
+            ]));
+            let state = board.state(DEFAULT_WITNESS_OPTIONS);
+            let digest = Poseidon::<_, P128Pow5T3, ConstantLength<1>, 3, 2>::init()
+                .hash([Fp::from_u128(state.lower_u128())]);
+            let circuit =
+                BoardCircuit::<P128Pow5T3, Fp>::new(board.witness(DEFAULT_WITNESS_OPTIONS), state);
+            (circuit, vec![vec![digest]])
+        });
+        // both fleets are valid under the classic rules
+        for (circuit, instance) in spaced.iter() {
+            assert_valid(circuit, 12, instance.clone());
+        }
+        let [(valid, valid_instance), (touching, touching_instance)] = spaced;
+        assert_valid(&NoTouchBoardCircuit(valid), 12, valid_instance);
+        // only the no touch rule is violated by the touching fleet
+        let prover =
+            MockProver::run(12, &NoTouchBoardCircuit(touching), touching_instance).unwrap();
+        let failures = prover.verify().unwrap_err();
+        assert_eq!(failures.len(), 5);
+        for failure in failures {
+            assert!(matches!(
+                failure,
+                VerifyFailure::ConstraintNotSatisfied { location: FailureLocation::InRegion { region, .. }, .. }
+                    if region == (27, "no touch ship footprints").into()
+            ));
+        }
+    }
+
+    // board circuit placing ships by their coordinates instead of by commitments
+    struct FootprintBoardCircuit {
+        deck: Deck,
+    }
+
+    impl Circuit<Fp> for FootprintBoardCircuit {
+        type Config = BoardConfig<Fp>;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            FootprintBoardCircuit { deck: self.deck }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            BoardChip::<P128Pow5T3, Fp>::configure_footprint(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let board = Board::from(&self.deck).state(DEFAULT_WITNESS_OPTIONS);
+            let row = InstanceLayout::Board.row(PublicInput::Digest);
+            BoardChip::<P128Pow5T3, Fp>::new(config)
+                .synthesize_deck(layouter, self.deck, board, row)
+        }
+    }
+
+    #[test]
+    fn salted_commitment_per_game() {
+        let board = Board::from(&Deck::default());
+        let witness = board.witness(DEFAULT_WITNESS_OPTIONS);
+        let state = board.state(DEFAULT_WITNESS_OPTIONS);
+        let secret = Fp::from(0xba771e);
+        // the same layout commits differently every game, and never to the unsalted digest
+        let digests = [1, 2].map(|game| commit_board(state, secret, Fp::from(game)));
+        assert_ne!(digests[0], digests[1]);
+        assert!(!digests.contains(&board_digest(state)));
+        for (game, digest) in [1, 2].into_iter().zip(digests) {
+            let circuit =
+                SaltedBoardCircuit::<P128Pow5T3, Fp>::new(witness, state, secret, Fp::from(game));
+            let instance = InstanceLayout::SaltedBoard.assemble(|input| match input {
+                PublicInput::Digest => digest,
+                _ => Fp::from(game),
+            });
+            assert_valid_with_instances(&circuit, BOARD_K, vec![instance.clone()]);
+            assert!(empirical_min_k(&circuit, vec![instance]) <= BOARD_K);
+        }
+        // a digest from another game, or under another secret, is not derived by the circuit
+        let circuit =
+            SaltedBoardCircuit::<P128Pow5T3, Fp>::new(witness, state, secret, Fp::from(2));
+        for digest in [digests[0], commit_board(state, Fp::from(1), Fp::from(2))] {
+            let prover =
+                MockProver::run(BOARD_K, &circuit, vec![vec![digest, Fp::from(2)]]).unwrap();
+            assert!(prover.verify().is_err());
+        }
+    }
+
+    #[test]
+    fn footprint_variant() {
+        let mut rng = TestRng::deterministic_rng(RngAlgorithm::ChaCha);
+        let decks = [
+            Deck::default(),
+            Deck::from([
+                Some((3, 3, true)),
+                Some((5, 4, false)),
+                Some((0, 1, false)),
+                Some((0, 5, true)),
+                Some((6, 1, false)),
+            ]),
+            random_valid_fleet(&mut rng),
+        ];
+        // the board bits combined in-circuit from coordinates commit to the off-circuit board
+        for deck in decks {
+            let digest = board_digest(Board::from(&deck).state(DEFAULT_WITNESS_OPTIONS));
+            assert_valid_with_instances(&FootprintBoardCircuit { deck }, 12, vec![vec![digest]]);
+        }
+        // overlapping and incomplete fleets are still rejected
+        for deck in [
+            Deck::from([
+                Some((3, 3, true)),
+                Some((5, 4, false)),
+                Some((0, 1, false)),
+                Some((0, 1, false)),
+                Some((6, 1, false)),
+            ]),
+            Deck::from([
+                Some((3, 3, true)),
+                Some((5, 4, false)),
+                Some((0, 1, false)),
+                None,
+                Some((6, 1, false)),
+            ]),
+        ] {
+            let digest = board_digest(Board::from(&deck).state(DEFAULT_WITNESS_OPTIONS));
+            let prover =
+                MockProver::run(12, &FootprintBoardCircuit { deck }, vec![vec![digest]]).unwrap();
+            assert!(prover.verify().is_err());
+        }
+        // the footprint regions shift the placements, so failures are traced with this layout
+        let deck = Deck::from([
+            Some((3, 3, true)),
+            Some((5, 4, false)),
+            Some((0, 1, false)),
+            None,
+            Some((6, 1, false)),
+        ]);
+        let circuit = FootprintBoardCircuit { deck };
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let config = FootprintBoardCircuit::configure(&mut meta);
+        let mut layout = LayoutRecorder::default();
+        layout
+            .synthesize(&circuit, config.clone(), vec![config.fixed[0]])
+            .unwrap();
+        let digest = board_digest(Board::from(&deck).state(DEFAULT_WITNESS_OPTIONS));
+        let prover = MockProver::run(12, &circuit, vec![vec![digest]]).unwrap();
+        let errors = prover
+            .verify()
+            .unwrap_err()
+            .iter()
+            .map(|failure| translate_layout_failure(&layout, failure))
+            .collect::<Vec<_>>();
+        assert!(errors.contains(&GameValidationError::WrongLength(Some(3))));
+    }
+
     // #[test]
     // fn print_circuit() {
     //     use plotters::prelude::*;
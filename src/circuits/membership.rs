@@ -0,0 +1,293 @@
+use {
+    crate::{
+        chips::{
+            board::BoardSalt,
+            membership::{PlacementMembershipChip, PlacementMembershipConfig},
+            placement::{ShipLen, ValidShipLen},
+        },
+        utils::{
+            binary::BinaryValue,
+            instance::{InstanceLayout, PublicInput},
+        },
+    },
+    halo2_gadgets::poseidon::primitives::Spec,
+    halo2_proofs::{
+        arithmetic::FieldExt,
+        circuit::{Layouter, SimpleFloorPlanner},
+        plonk::{Circuit, ConstraintSystem, Error},
+    },
+    std::marker::PhantomData,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct MembershipCircuit<S: Spec<F, 3, 2>, F: FieldExt, const L: usize>
+where
+    ShipLen<L>: ValidShipLen,
+{
+    pub board: BinaryValue,
+    pub ship: [BinaryValue; 2],
+    pub salt: Option<BoardSalt<F>>,
+    _field: PhantomData<F>,
+    _spec: PhantomData<S>,
+}
+
+impl<S: Spec<F, 3, 2>, F: FieldExt, const L: usize> Circuit<F> for MembershipCircuit<S, F, L>
+where
+    ShipLen<L>: ValidShipLen,
+{
+    type Config = PlacementMembershipConfig<F, L>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        MembershipCircuit {
+            salt: self.salt,
+            ..MembershipCircuit::new(self.board, self.ship)
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        PlacementMembershipChip::<S, F, L>::configure(meta)
+    }
+
+    fn synthesize(&self, config: Self::Config, layouter: impl Layouter<F>) -> Result<(), Error> {
+        PlacementMembershipChip::<S, F, L>::new(config)
+            .synthesize(layouter, self.board, self.ship, self.salt)
+    }
+}
+
+impl<S: Spec<F, 3, 2>, F: FieldExt, const L: usize> MembershipCircuit<S, F, L>
+where
+    ShipLen<L>: ValidShipLen,
+{
+    /**
+     * Construct a new membership circuit proving a committed board holds a ship of length L
+     *
+     * @param board - private board state
+     * @param ship - private [H, V] commitments of the ship (see Ship::witness)
+     * @return - instantiated MembershipCircuit object
+     */
+    pub fn new(board: BinaryValue, ship: [BinaryValue; 2]) -> MembershipCircuit<S, F, L> {
+        MembershipCircuit {
+            board,
+            ship,
+            salt: None,
+            _field: PhantomData,
+            _spec: PhantomData,
+        }
+    }
+
+    /**
+     * Prove against a board committed under a per-game salt (see SaltedBoardCircuit)
+     * @dev the public digest becomes prover::commit_board(board, secret, game_id) and the game id
+     *      is appended to the instance column (InstanceLayout::Membership.salted())
+     *
+     * @param secret - the player's private secret, reused across games
+     * @param game_id - public identifier of the game
+     * @return - the circuit checking the salted board commitment
+     */
+    pub fn salted(mut self, secret: F, game_id: F) -> Self {
+        let row = InstanceLayout::Membership.salted().row(PublicInput::GameId);
+        self.salt = Some(BoardSalt {
+            secret,
+            game_id,
+            row,
+        });
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use {
+        super::*,
+        crate::{
+            prover::{board_digest, commit_board},
+            test_utils::assert_valid_with_instances,
+            utils::{
+                board::Board,
+                deck::Deck,
+                ship::{Ship, ShipType, WitnessOption, DEFAULT_WITNESS_OPTIONS},
+            },
+        },
+        halo2_gadgets::poseidon::primitives::P128Pow5T3,
+        halo2_proofs::{
+            dev::{FailureLocation, MockProver, VerifyFailure},
+            pasta::Fp,
+            plonk::Any,
+        },
+    };
+
+    const CIRCUIT_SIZE: u32 = 10; // 2^CIRCUIT_SIZE rows used in circuit
+
+    /**
+     * Return the state of a board with a fixed valid fleet
+     */
+    fn board_state() -> BinaryValue {
+        Board::from(&Deck::from([
+            Some((3, 3, true)),
+            Some((5, 4, false)),
+            Some((0, 1, false)),
+            Some((0, 5, true)),
+            Some((6, 1, false)),
+        ]))
+        .state(DEFAULT_WITNESS_OPTIONS)
+    }
+
+    /**
+     * Mock prove a board holds a ship of length L against the board's public commitment
+     *
+     * @param ship - private [H, V] ship commitments
+     * @return - the result of verifying the membership proof
+     */
+    fn prove_membership<const L: usize>(ship: [BinaryValue; 2]) -> Result<(), Vec<VerifyFailure>>
+    where
+        ShipLen<L>: ValidShipLen,
+    {
+        let state = board_state();
+        let circuit = MembershipCircuit::<P128Pow5T3, Fp, L>::new(state, ship);
+        let prover = MockProver::run(CIRCUIT_SIZE, &circuit, vec![vec![board_digest(state)]]);
+        prover.unwrap().verify()
+    }
+
+    /**
+     * Return the failures of a placement that is not a single ship of the proven length
+     *
+     * @param bit_count - the number of placement bits counted by the running sum
+     * @return - failures of the ship length and full bit window constraints
+     */
+    fn placement_failures(bit_count: &str) -> Vec<VerifyFailure> {
+        let location = || FailureLocation::InRegion {
+            region: (6, "constrain running sum output").into(),
+            offset: 0,
+        };
+        vec![
+            VerifyFailure::ConstraintNotSatisfied {
+                constraint: (
+                    (8, "running sum constraints").into(),
+                    0,
+                    "Placed ship of correct length",
+                )
+                    .into(),
+                location: location(),
+                cell_values: vec![(((Any::Advice, 1).into(), 0).into(), String::from(bit_count))],
+            },
+            VerifyFailure::ConstraintNotSatisfied {
+                constraint: (
+                    (8, "running sum constraints").into(),
+                    2,
+                    "One full horizontal bit window",
+                )
+                    .into(),
+                location: location(),
+                cell_values: vec![
+                    (((Any::Advice, 0).into(), 0).into(), String::from("0")),
+                    (((Any::Advice, 2).into(), 0).into(), String::from("0")),
+                ],
+            },
+        ]
+    }
+
+    #[test]
+    fn valid_membership() {
+        // the vertical carrier at (3, 3) and the horizontal battleship at (5, 4)
+        let carrier = Ship::new(ShipType::Carrier, 3, 3, true).witness(WitnessOption::Default);
+        assert_eq!(prove_membership::<5>(carrier), Ok(()));
+        let battleship =
+            Ship::new(ShipType::Battleship, 5, 4, false).witness(WitnessOption::Default);
+        assert_eq!(prove_membership::<4>(battleship), Ok(()));
+    }
+
+    #[test]
+    fn valid_salted_membership() {
+        let state = board_state();
+        let (secret, game_id) = (Fp::from(0xbee5), Fp::from(42));
+        let carrier = Ship::new(ShipType::Carrier, 3, 3, true).witness(WitnessOption::Default);
+        let circuit =
+            MembershipCircuit::<P128Pow5T3, Fp, 5>::new(state, carrier).salted(secret, game_id);
+        let instance = InstanceLayout::SaltedMembership.assemble(|input| match input {
+            PublicInput::Digest => commit_board(state, secret, game_id),
+            PublicInput::GameId => game_id,
+            _ => unreachable!(),
+        });
+        assert_valid_with_instances(&circuit, CIRCUIT_SIZE, vec![instance]);
+    }
+
+    #[test]
+    fn invalid_membership() {
+        // claim a horizontal carrier at (3, 3) that was never placed
+        let ship = Ship::new(ShipType::Carrier, 3, 3, false).witness(WitnessOption::Default);
+        // cell 33 is shared with the vertical carrier, cells 34-37 are empty on the board
+        let failures = (34..38)
+            .map(|offset| VerifyFailure::ConstraintNotSatisfied {
+                constraint: (
+                    (3, "bit subset").into(),
+                    0,
+                    "Subset bit flipped in superset",
+                )
+                    .into(),
+                location: FailureLocation::InRegion {
+                    region: (7, "constrain bit subset").into(),
+                    offset,
+                },
+                cell_values: vec![
+                    (((Any::Advice, 0).into(), 0).into(), String::from("1")),
+                    (((Any::Advice, 1).into(), 0).into(), String::from("0")),
+                ],
+            })
+            .collect::<Vec<VerifyFailure>>();
+        assert_eq!(prove_membership::<5>(ship), Err(failures));
+    }
+
+    #[test]
+    fn invalid_split_orientation() {
+        // H bit 60 and V bit 61 sum to a straight run of 2, but are checked against board cells
+        // (0, 6) (submarine) and (6, 1) (destroyer) rather than a single placed ship
+        let mut horizontal = BinaryValue::empty();
+        horizontal.value.set(60, true);
+        let mut vertical = BinaryValue::empty();
+        vertical.value.set(61, true);
+        assert_eq!(
+            prove_membership::<2>([horizontal, vertical]),
+            Err(vec![VerifyFailure::ConstraintNotSatisfied {
+                constraint: (
+                    (12, "Commitment orientation H OR V == 0 constraint").into(),
+                    0,
+                    "Ship H OR V == 0",
+                )
+                    .into(),
+                location: FailureLocation::InRegion {
+                    region: (0, "load private PlacementMembershipChip advice values").into(),
+                    offset: 1,
+                },
+                cell_values: vec![
+                    (
+                        ((Any::Advice, 4).into(), 0).into(),
+                        String::from("0x1000000000000000")
+                    ),
+                    (
+                        ((Any::Advice, 4).into(), 1).into(),
+                        String::from("0x2000000000000000")
+                    ),
+                ],
+            }])
+        );
+    }
+
+    #[test]
+    fn invalid_empty_ship() {
+        // an empty ship is a subset of every board but is not a ship of length 5
+        let empty = [BinaryValue::empty(); 2];
+        assert_eq!(prove_membership::<5>(empty), Err(placement_failures("0")));
+    }
+
+    #[test]
+    fn invalid_ship_length() {
+        // the destroyer is on the board but is not a ship of length 3
+        let destroyer = Ship::new(ShipType::Destroyer, 6, 1, false).witness(WitnessOption::Default);
+        assert_eq!(
+            prove_membership::<3>(destroyer),
+            Err(placement_failures("0x2"))
+        );
+    }
+}
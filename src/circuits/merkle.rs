@@ -0,0 +1,244 @@
+use {
+    crate::{
+        chips::merkle::{MerkleMembershipChip, MerkleMembershipConfig},
+        utils::binary::BinaryValue,
+    },
+    halo2_gadgets::poseidon::primitives::Spec,
+    halo2_proofs::{
+        arithmetic::FieldExt,
+        circuit::{Layouter, SimpleFloorPlanner},
+        plonk::{Circuit, ConstraintSystem, Error},
+    },
+    std::marker::PhantomData,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct MerkleMembershipCircuit<S: Spec<F, 3, 2>, F: FieldExt, const DEPTH: usize> {
+    pub board: BinaryValue,
+    pub secret: F,
+    pub game_id: F,
+    pub index: usize,
+    pub siblings: [F; DEPTH],
+    _field: PhantomData<F>,
+    _spec: PhantomData<S>,
+}
+
+impl<S: Spec<F, 3, 2>, F: FieldExt, const DEPTH: usize> Circuit<F>
+    for MerkleMembershipCircuit<S, F, DEPTH>
+{
+    type Config = MerkleMembershipConfig<F>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        MerkleMembershipCircuit::new(
+            self.board,
+            self.secret,
+            self.game_id,
+            self.index,
+            self.siblings,
+        )
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        MerkleMembershipChip::<S, F, DEPTH>::configure(meta)
+    }
+
+    fn synthesize(&self, config: Self::Config, layouter: impl Layouter<F>) -> Result<(), Error> {
+        MerkleMembershipChip::<S, F, DEPTH>::new(config).synthesize(
+            layouter,
+            self.board,
+            self.secret,
+            self.game_id,
+            self.index,
+            self.siblings,
+        )
+    }
+}
+
+impl<S: Spec<F, 3, 2>, F: FieldExt, const DEPTH: usize> MerkleMembershipCircuit<S, F, DEPTH> {
+    /**
+     * Construct a new circuit proving a board is on an allowlist committed to by a Merkle root
+     *
+     * @dev the public digest is prover::commit_board(board, secret, game_id), not the leaf
+     *
+     * @param board - private board state
+     * @param secret - the player's private secret, reused across games
+     * @param game_id - public identifier of the game
+     * @param index - private position of the board's commitment in the allowlist
+     * @param siblings - private Merkle path of the board's commitment (see merkle_path)
+     * @return - instantiated MerkleMembershipCircuit object
+     */
+    pub fn new(
+        board: BinaryValue,
+        secret: F,
+        game_id: F,
+        index: usize,
+        siblings: [F; DEPTH],
+    ) -> MerkleMembershipCircuit<S, F, DEPTH> {
+        MerkleMembershipCircuit {
+            board,
+            secret,
+            game_id,
+            index,
+            siblings,
+            _field: PhantomData,
+            _spec: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use {
+        super::*,
+        crate::{
+            chips::merkle::{merkle_path, merkle_root},
+            prover::{board_digest, commit_board},
+            test_utils::{assert_fails_with, assert_valid},
+            utils::{
+                board::Board,
+                deck::Deck,
+                instance::{InstanceLayout, PublicInput},
+                ship::DEFAULT_WITNESS_OPTIONS,
+            },
+        },
+        halo2_gadgets::poseidon::primitives::{ConstantLength, Hash as Poseidon, P128Pow5T3},
+        halo2_proofs::{
+            dev::{FailureLocation, VerifyFailure},
+            pasta::Fp,
+            plonk::Any,
+        },
+    };
+
+    const CIRCUIT_SIZE: u32 = 9; // 2^CIRCUIT_SIZE rows used in circuit
+    const DEPTH: usize = 2; // allowlist of up to 4 boards
+    const GAME_ID: u64 = 7;
+
+    fn secret() -> Fp {
+        Fp::from(0xbee5)
+    }
+
+    // public inputs of a proof for a board hashed up to a root
+    fn public_inputs(board: BinaryValue, root: Fp) -> Vec<Fp> {
+        InstanceLayout::MerkleMembership.assemble(|input| match input {
+            PublicInput::Digest => commit_board(board, secret(), Fp::from(GAME_ID)),
+            PublicInput::GameId => Fp::from(GAME_ID),
+            _ => root,
+        })
+    }
+
+    // boards of the curated allowlist
+    fn allowlist() -> Vec<BinaryValue> {
+        [
+            Deck::default(),
+            Deck::from([
+                Some((3, 3, true)),
+                Some((5, 4, false)),
+                Some((0, 1, false)),
+                Some((0, 5, true)),
+                Some((6, 1, false)),
+            ]),
+            Deck::from([
+                Some((3, 4, false)),
+                Some((9, 6, true)),
+                Some((0, 0, false)),
+                Some((0, 2, true)),
+                Some((6, 0, false)),
+            ]),
+        ]
+        .iter()
+        .map(|deck| Board::from(deck).state(DEFAULT_WITNESS_OPTIONS))
+        .collect()
+    }
+
+    // failures of a path that does not hash up to the exported root (the leaf still matches)
+    fn root_failures() -> Vec<VerifyFailure> {
+        vec![
+            VerifyFailure::Permutation {
+                column: (Any::Advice, 0).into(),
+                location: FailureLocation::InRegion {
+                    region: (18, "permute state").into(),
+                    offset: 36,
+                },
+            },
+            VerifyFailure::Permutation {
+                column: (Any::Instance, 0).into(),
+                location: FailureLocation::OutsideRegion {
+                    row: InstanceLayout::MerkleMembership.row(PublicInput::Root),
+                },
+            },
+        ]
+    }
+
+    #[test]
+    fn board_in_allowlist() {
+        let leaves = allowlist()
+            .into_iter()
+            .map(board_digest)
+            .collect::<Vec<Fp>>();
+        let root = merkle_root::<P128Pow5T3, Fp, DEPTH>(&leaves);
+        for (index, board) in allowlist().into_iter().enumerate() {
+            let siblings = merkle_path::<P128Pow5T3, Fp, DEPTH>(&leaves, index);
+            let circuit = MerkleMembershipCircuit::<P128Pow5T3, Fp, DEPTH>::new(
+                board,
+                secret(),
+                Fp::from(GAME_ID),
+                index,
+                siblings,
+            );
+            let instance = public_inputs(board, root);
+            // the public digest is salted, so it cannot be matched against the allowlist
+            assert!(!leaves.contains(&instance[0]));
+            assert_valid(&circuit, CIRCUIT_SIZE, vec![instance]);
+        }
+    }
+
+    #[test]
+    fn merkle_node_domain() {
+        // a node over [left, right] never equals the salted commitment hash of the same pair
+        let [left, right] = [Fp::from(1), Fp::from(2)];
+        let node = merkle_root::<P128Pow5T3, Fp, 1>(&[left, right]);
+        let pair = Poseidon::<_, P128Pow5T3, ConstantLength<2>, 3, 2>::init().hash([left, right]);
+        assert_ne!(node, pair);
+    }
+
+    #[test]
+    fn board_not_in_allowlist() {
+        let leaves = allowlist()
+            .into_iter()
+            .map(board_digest)
+            .collect::<Vec<Fp>>();
+        let root = merkle_root::<P128Pow5T3, Fp, DEPTH>(&leaves);
+        let board = Board::from(&Deck::from([
+            Some((0, 0, true)),
+            Some((2, 0, true)),
+            Some((4, 0, true)),
+            Some((6, 0, true)),
+            Some((8, 0, true)),
+        ]))
+        .state(DEFAULT_WITNESS_OPTIONS);
+        // claim the board sits in the empty leaf using the path of that leaf
+        let siblings = merkle_path::<P128Pow5T3, Fp, DEPTH>(&leaves, 3);
+        let circuit = MerkleMembershipCircuit::<P128Pow5T3, Fp, DEPTH>::new(
+            board,
+            secret(),
+            Fp::from(GAME_ID),
+            3,
+            siblings,
+        );
+        let instance = public_inputs(board, root);
+        assert_fails_with(&circuit, CIRCUIT_SIZE, vec![instance], root_failures());
+        // an allowlisted board hashed up the path of another leaf does not reach the root either
+        let siblings = merkle_path::<P128Pow5T3, Fp, DEPTH>(&leaves, 1);
+        let circuit = MerkleMembershipCircuit::<P128Pow5T3, Fp, DEPTH>::new(
+            allowlist()[0],
+            secret(),
+            Fp::from(GAME_ID),
+            1,
+            siblings,
+        );
+        let instance = public_inputs(allowlist()[0], root);
+        assert_fails_with(&circuit, CIRCUIT_SIZE, vec![instance], root_failures());
+    }
+}
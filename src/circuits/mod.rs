@@ -1,2 +1,8 @@
 pub mod board;
-pub mod shot;
\ No newline at end of file
+pub mod shot;
+pub mod membership;
+pub mod merkle;
+pub mod win;
+pub mod multi_board;
+pub mod reveal;
+pub mod tally;
\ No newline at end of file
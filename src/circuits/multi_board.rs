@@ -0,0 +1,172 @@
+use {
+    crate::{
+        chips::board::{BoardChip, BoardConfig},
+        utils::{
+            binary::BinaryValue,
+            instance::{InstanceLayout, PublicInput},
+        },
+    },
+    halo2_gadgets::poseidon::primitives::Spec,
+    halo2_proofs::{
+        arithmetic::FieldExt,
+        circuit::{Layouter, SimpleFloorPlanner},
+        plonk::{Circuit, ConstraintSystem, Error},
+    },
+    std::marker::PhantomData,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct MultiBoardCircuit<S: Spec<F, 3, 2>, F: FieldExt, const P: usize> {
+    pub ship_commitments: [[BinaryValue; 10]; P],
+    pub boards: [BinaryValue; P],
+    _field: PhantomData<F>,
+    _spec: PhantomData<S>,
+}
+
+impl<S: Spec<F, 3, 2>, F: FieldExt, const P: usize> Circuit<F> for MultiBoardCircuit<S, F, P> {
+    type Config = BoardConfig<F>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        MultiBoardCircuit::new(self.ship_commitments, self.boards)
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        BoardChip::<S, F>::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        // every board shares the same columns and gates, laid out one after another
+        let chip = BoardChip::<S, F>::new(config);
+        for i in 0..P {
+            chip.synthesize(
+                layouter.namespace(|| format!("board {}", i)),
+                self.ship_commitments[i],
+                self.boards[i],
+                InstanceLayout::Board.stacked_row(i, PublicInput::Digest),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl<S: Spec<F, 3, 2>, F: FieldExt, const P: usize> MultiBoardCircuit<S, F, P> {
+    /**
+     * Construct a circuit proving P independent boards are valid
+     * @dev board i exports its commitment to InstanceLayout::Board.stacked_row(i, Digest); the
+     *      proof fails if any board is invalid
+     *
+     * @param ship_commitments - the 10 ship commitments for each board
+     * @param boards - the board state for each board
+     * @return - instantiated MultiBoardCircuit object
+     */
+    pub fn new(
+        ship_commitments: [[BinaryValue; 10]; P],
+        boards: [BinaryValue; P],
+    ) -> MultiBoardCircuit<S, F, P> {
+        MultiBoardCircuit {
+            ship_commitments,
+            boards,
+            _field: PhantomData,
+            _spec: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        crate::{
+            prover::board_digest,
+            test_utils::{assert_fails_with, assert_valid_with_instances},
+            utils::{
+                board::Board,
+                deck::Deck,
+                ship::{WitnessOption, DEFAULT_WITNESS_OPTIONS},
+            },
+        },
+        halo2_gadgets::poseidon::primitives::P128Pow5T3,
+        halo2_proofs::{
+            dev::{FailureLocation, VerifyFailure},
+            pasta::Fp,
+            plonk::Any,
+        },
+    };
+
+    const CIRCUIT_SIZE: u32 = 13; // two boards overflow the 2^12 rows a single board fits in
+
+    /**
+     * Build the circuit and public commitments for two boards
+     *
+     * @param options - witness options used to build the ship commitments of each board
+     * @return - the circuit proving both boards and the instance column of their commitments
+     */
+    fn two_boards(
+        options: [[WitnessOption; 5]; 2],
+    ) -> (MultiBoardCircuit<P128Pow5T3, Fp, 2>, Vec<Fp>) {
+        let boards = [
+            Board::from(&Deck::default()),
+            Board::from(&Deck::from([
+                Some((3, 3, true)),
+                Some((5, 4, false)),
+                Some((0, 1, false)),
+                Some((0, 5, true)),
+                Some((6, 1, false)),
+            ])),
+        ];
+        let states = [0, 1].map(|i| boards[i].state(options[i]));
+        let circuit = MultiBoardCircuit::<P128Pow5T3, Fp, 2>::new(
+            [0, 1].map(|i| boards[i].witness(options[i])),
+            states,
+        );
+        let instance = InstanceLayout::Board.assemble_stacked(2, |i, _| board_digest(states[i]));
+        (circuit, instance)
+    }
+
+    #[test]
+    fn valid_two_boards() {
+        let (circuit, instance) = two_boards([DEFAULT_WITNESS_OPTIONS; 2]);
+        assert_valid_with_instances(&circuit, CIRCUIT_SIZE, vec![instance]);
+    }
+
+    #[test]
+    fn invalid_second_board() {
+        // the second player's carrier is witnessed as both horizontal and vertical
+        let mut options = [DEFAULT_WITNESS_OPTIONS; 2];
+        options[1][0] = WitnessOption::DualPlacement;
+        let (circuit, instance) = two_boards(options);
+        // the first board verifies; the second fails in its own copy of the board regions
+        assert_fails_with(
+            &circuit,
+            CIRCUIT_SIZE,
+            vec![instance],
+            vec![VerifyFailure::ConstraintNotSatisfied {
+                constraint: (
+                    (40, "Commitment orientation H OR V == 0 constraint").into(),
+                    0,
+                    "Aircraft Carrier H OR V == 0",
+                )
+                    .into(),
+                location: FailureLocation::InRegion {
+                    region: (33, "load ship placements").into(),
+                    offset: 0,
+                },
+                cell_values: vec![
+                    (
+                        ((Any::Advice, 0).into(), 0).into(),
+                        String::from("0x200000000"),
+                    ),
+                    (
+                        ((Any::Advice, 1).into(), 0).into(),
+                        String::from("0x3c00000000"),
+                    ),
+                ],
+            }],
+        );
+    }
+}
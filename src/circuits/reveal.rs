@@ -0,0 +1,183 @@
+use {
+    crate::{
+        chips::{
+            board::BoardSalt,
+            reveal::{RevealChip, RevealConfig},
+        },
+        utils::{
+            binary::BinaryValue,
+            instance::{InstanceLayout, PublicInput},
+        },
+    },
+    halo2_gadgets::poseidon::primitives::Spec,
+    halo2_proofs::{
+        arithmetic::FieldExt,
+        circuit::{Layouter, SimpleFloorPlanner},
+        plonk::{Circuit, ConstraintSystem, Error},
+    },
+    std::marker::PhantomData,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct RevealCircuit<S: Spec<F, 3, 2>, F: FieldExt> {
+    pub board: BinaryValue,
+    pub salt: Option<BoardSalt<F>>,
+    _field: PhantomData<F>,
+    _spec: PhantomData<S>,
+}
+
+impl<S: Spec<F, 3, 2>, F: FieldExt> Circuit<F> for RevealCircuit<S, F> {
+    type Config = RevealConfig<F>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        RevealCircuit {
+            salt: self.salt,
+            ..RevealCircuit::new(self.board)
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        RevealChip::<S, F>::configure(meta)
+    }
+
+    fn synthesize(&self, config: Self::Config, layouter: impl Layouter<F>) -> Result<(), Error> {
+        RevealChip::<S, F>::new(config).synthesize(layouter, self.board, self.salt)
+    }
+}
+
+impl<S: Spec<F, 3, 2>, F: FieldExt> RevealCircuit<S, F> {
+    /**
+     * Construct a new reveal circuit opening a board commitment at the end of a game
+     *
+     * @param board - board state being revealed (public)
+     * @return - instantiated RevealCircuit object
+     */
+    pub fn new(board: BinaryValue) -> RevealCircuit<S, F> {
+        RevealCircuit {
+            board,
+            salt: None,
+            _field: PhantomData,
+            _spec: PhantomData,
+        }
+    }
+
+    /**
+     * Prove against a board committed under a per-game salt (see SaltedBoardCircuit)
+     * @dev the public digest becomes prover::commit_board(board, secret, game_id) and the game id
+     *      is appended to the instance column (InstanceLayout::Reveal.salted())
+     *
+     * @param secret - the player's private secret, reused across games
+     * @param game_id - public identifier of the game
+     * @return - the circuit checking the salted board commitment
+     */
+    pub fn salted(mut self, secret: F, game_id: F) -> Self {
+        let row = InstanceLayout::Reveal.salted().row(PublicInput::GameId);
+        self.salt = Some(BoardSalt {
+            secret,
+            game_id,
+            row,
+        });
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use {
+        super::*,
+        crate::{
+            prover::{board_digest, commit_board},
+            test_utils::{assert_fails_with, assert_valid, assert_valid_with_instances},
+            utils::{board::Board, deck::Deck, ship::DEFAULT_WITNESS_OPTIONS},
+        },
+        halo2_gadgets::poseidon::primitives::P128Pow5T3,
+        halo2_proofs::{
+            dev::{FailureLocation, VerifyFailure},
+            pasta::Fp,
+            plonk::Any,
+        },
+    };
+
+    /**
+     * Assemble the public inputs of a reveal proof
+     *
+     * @param digest - the board commitment published at the start of the game
+     * @param board - the board state claimed at the end of the game
+     * @return - instance column values in reveal layout order
+     */
+    fn reveal_instance(digest: Fp, board: BinaryValue) -> Vec<Fp> {
+        InstanceLayout::Reveal.assemble(|input| match input {
+            PublicInput::Digest => digest,
+            PublicInput::Board => Fp::from_u128(board.lower_u128()),
+            _ => unreachable!(),
+        })
+    }
+
+    #[test]
+    fn valid_reveal() {
+        let state = Board::from(&Deck::from([
+            Some((3, 3, true)),
+            Some((5, 4, false)),
+            Some((0, 1, false)),
+            Some((0, 5, true)),
+            Some((6, 1, false)),
+        ]))
+        .state(DEFAULT_WITNESS_OPTIONS);
+        let circuit = RevealCircuit::<P128Pow5T3, Fp>::new(state);
+        let instance = reveal_instance(board_digest(state), state);
+        assert_valid(&circuit, 7, vec![instance]);
+    }
+
+    #[test]
+    fn valid_salted_reveal() {
+        let state = Board::from(&Deck::default()).state(DEFAULT_WITNESS_OPTIONS);
+        let (secret, game_id) = (Fp::from(0xbee5), Fp::from(42));
+        let circuit = RevealCircuit::<P128Pow5T3, Fp>::new(state).salted(secret, game_id);
+        let instance = InstanceLayout::SaltedReveal.assemble(|input| match input {
+            PublicInput::Digest => commit_board(state, secret, game_id),
+            PublicInput::Board => Fp::from_u128(state.lower_u128()),
+            PublicInput::GameId => game_id,
+            _ => unreachable!(),
+        });
+        assert_valid_with_instances(&circuit, 8, vec![instance]);
+    }
+
+    #[test]
+    fn invalid_reveal() {
+        let committed = Board::from(&Deck::from([
+            Some((3, 3, true)),
+            Some((5, 4, false)),
+            Some((0, 1, false)),
+            Some((0, 5, true)),
+            Some((6, 1, false)),
+        ]))
+        .state(DEFAULT_WITNESS_OPTIONS);
+        // reveal a different board than the one committed to at the start of the game
+        let revealed = Board::from(&Deck::default()).state(DEFAULT_WITNESS_OPTIONS);
+        let circuit = RevealCircuit::<P128Pow5T3, Fp>::new(revealed);
+        let instance = reveal_instance(board_digest(committed), revealed);
+        // only the digest fails to match: the squeezed hash and the committed digest instance
+        assert_fails_with(
+            &circuit,
+            7,
+            vec![instance],
+            vec![
+                VerifyFailure::Permutation {
+                    column: (Any::Advice, 0).into(),
+                    location: FailureLocation::InRegion {
+                        region: (4, "permute state").into(),
+                        offset: 36,
+                    },
+                },
+                VerifyFailure::Permutation {
+                    column: (Any::Instance, 0).into(),
+                    location: FailureLocation::OutsideRegion {
+                        row: InstanceLayout::Reveal.row(PublicInput::Digest),
+                    },
+                },
+            ],
+        );
+    }
+}
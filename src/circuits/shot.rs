@@ -1,7 +1,14 @@
 use {
     crate::{
-        chips::shot::{ShotChip, ShotConfig},
-        utils::binary::BinaryValue,
+        chips::{
+            board::BoardSalt,
+            shot::{ShotChip, ShotConfig},
+        },
+        utils::{
+            binary::BinaryValue,
+            instance::{InstanceLayout, PublicInput},
+            layout,
+        },
     },
     halo2_gadgets::poseidon::primitives::Spec,
     halo2_proofs::{
@@ -13,10 +20,12 @@ use {
 };
 
 #[derive(Debug, Clone, Copy)]
-struct ShotCircuit<S: Spec<F, 3, 2>, F: FieldExt> {
+pub struct ShotCircuit<S: Spec<F, 3, 2>, F: FieldExt> {
     pub board: BinaryValue,
     pub shot: BinaryValue,
     pub hit: BinaryValue,
+    pub nonce: F,
+    pub salt: Option<BoardSalt<F>>,
     _field: PhantomData<F>,
     _spec: PhantomData<S>,
 }
@@ -27,7 +36,10 @@ impl<S: Spec<F, 3, 2>, F: FieldExt> Circuit<F> for ShotCircuit<S, F> {
 
     fn without_witnesses(&self) -> Self {
         // @TODO FIX
-        ShotCircuit::new(self.board, self.shot, self.hit)
+        ShotCircuit {
+            salt: self.salt,
+            ..ShotCircuit::new(self.board, self.shot, self.hit, self.nonce)
+        }
     }
 
     fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
@@ -35,7 +47,9 @@ impl<S: Spec<F, 3, 2>, F: FieldExt> Circuit<F> for ShotCircuit<S, F> {
     }
 
     fn synthesize(&self, config: Self::Config, layouter: impl Layouter<F>) -> Result<(), Error> {
-        Ok(ShotChip::<S, F>::new(config).synthesize(layouter, self.board, self.shot, self.hit)?)
+        Ok(ShotChip::<S, F>::new(config).synthesize(
+            layouter, self.board, self.shot, self.hit, self.nonce, self.salt,
+        )?)
     }
 }
 
@@ -46,17 +60,127 @@ impl<S: Spec<F, 3, 2>, F: FieldExt> ShotCircuit<S, F> {
      * @param board - private board placement
      * @param shot - x, y coordinates serialized into a shot commitment
      * @param hit - assertion that the shot either hits or misses the decomposed board (constrained 0 or 1)
+     * @param nonce - turn nonce bound into the proof as a public input (replay protection)
      * @return - instantiated BoardCircuit object containing BoardGadget
      */
-    pub fn new(board: BinaryValue, shot: BinaryValue, hit: BinaryValue) -> ShotCircuit<S, F> {
+    pub fn new(
+        board: BinaryValue,
+        shot: BinaryValue,
+        hit: BinaryValue,
+        nonce: F,
+    ) -> ShotCircuit<S, F> {
         ShotCircuit {
             board,
             shot,
             hit,
+            nonce,
+            salt: None,
+            _field: PhantomData,
+            _spec: PhantomData,
+        }
+    }
+
+    /**
+     * Prove against a board committed under a per-game salt (see SaltedBoardCircuit)
+     * @dev the public digest becomes prover::commit_board(board, secret, game_id) and the game id
+     *      is appended to the instance column (InstanceLayout::Shot.salted())
+     *
+     * @param secret - the player's private secret, reused across games
+     * @param game_id - public identifier of the game
+     * @return - the circuit checking the salted board commitment
+     */
+    pub fn salted(mut self, secret: F, game_id: F) -> ShotCircuit<S, F> {
+        let row = InstanceLayout::Shot.salted().row(PublicInput::GameId);
+        self.salt = Some(BoardSalt {
+            secret,
+            game_id,
+            row,
+        });
+        self
+    }
+
+    /**
+     * Return the smallest k such that the circuit's layout fits in 2^k rows
+     * @dev lays the circuit out without witnessing it and adds the rows halo2 reserves for
+     *      blinding factors
+     *
+     * @return - minimum k to prove this circuit with, or the error laying it out
+     */
+    pub fn min_k(&self) -> Result<u32, Error> {
+        layout::min_k(self, |config: &ShotConfig<F>| vec![config.fixed[0]])
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RegionMissCircuit<S: Spec<F, 3, 2>, F: FieldExt> {
+    pub board: BinaryValue,
+    pub cells: Vec<(u8, u8)>,
+    pub salt: Option<BoardSalt<F>>,
+    _field: PhantomData<F>,
+    _spec: PhantomData<S>,
+}
+
+impl<S: Spec<F, 3, 2>, F: FieldExt> Circuit<F> for RegionMissCircuit<S, F> {
+    type Config = ShotConfig<F>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        RegionMissCircuit {
+            salt: self.salt,
+            ..RegionMissCircuit::new(self.board, self.cells.clone())
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        ShotChip::<S, F>::configure(meta)
+    }
+
+    fn synthesize(&self, config: Self::Config, layouter: impl Layouter<F>) -> Result<(), Error> {
+        ShotChip::<S, F>::new(config).prove_region_miss(
+            layouter,
+            self.board,
+            &self.cells,
+            self.salt,
+        )
+    }
+}
+
+impl<S: Spec<F, 3, 2>, F: FieldExt> RegionMissCircuit<S, F> {
+    /**
+     * Construct a new circuit proving a region of the board contains no ship
+     *
+     * @param board - private board placement
+     * @param cells - (x, y) coordinates of every cell in the region (serialized publicly)
+     * @return - instantiated RegionMissCircuit object
+     */
+    pub fn new(board: BinaryValue, cells: Vec<(u8, u8)>) -> RegionMissCircuit<S, F> {
+        RegionMissCircuit {
+            board,
+            cells,
+            salt: None,
             _field: PhantomData,
             _spec: PhantomData,
         }
     }
+
+    /**
+     * Prove against a board committed under a per-game salt (see SaltedBoardCircuit)
+     * @dev the public digest becomes prover::commit_board(board, secret, game_id) and the game id
+     *      is appended to the instance column (InstanceLayout::RegionMiss.salted())
+     *
+     * @param secret - the player's private secret, reused across games
+     * @param game_id - public identifier of the game
+     * @return - the circuit checking the salted board commitment
+     */
+    pub fn salted(mut self, secret: F, game_id: F) -> RegionMissCircuit<S, F> {
+        let row = InstanceLayout::RegionMiss.salted().row(PublicInput::GameId);
+        self.salt = Some(BoardSalt {
+            secret,
+            game_id,
+            row,
+        });
+        self
+    }
 }
 
 #[cfg(test)]
@@ -64,8 +188,16 @@ mod test {
 
     use {
         super::*,
-        crate::utils::{
-            binary::U256, board::Board, deck::Deck, ship::DEFAULT_WITNESS_OPTIONS, shot::serialize,
+        crate::{
+            prover::commit_board,
+            test_utils::assert_valid_with_instances,
+            utils::{
+                binary::U256,
+                board::Board,
+                deck::Deck,
+                ship::DEFAULT_WITNESS_OPTIONS,
+                shot::{serialize, serialize_cells},
+            },
         },
         halo2_gadgets::poseidon::primitives::{ConstantLength, Hash as Poseidon, P128Pow5T3},
         halo2_proofs::{
@@ -75,6 +207,8 @@ mod test {
         },
     };
 
+    const TURN_NONCE: u64 = 7; // turn nonce used by tests that do not exercise replay protection
+
     #[test]
     fn valid_hit_0() {
         // construct valid battleship board pattern 1
@@ -95,10 +229,15 @@ mod test {
             hashed,
             Fp::from_u128(shot.lower_u128()),
             Fp::from_u128(hit.lower_u128()),
+            Fp::from(TURN_NONCE),
         ];
         // construct BoardValidity circuit
-        let circuit =
-            ShotCircuit::<P128Pow5T3, Fp>::new(board.state(DEFAULT_WITNESS_OPTIONS), shot, hit);
+        let circuit = ShotCircuit::<P128Pow5T3, Fp>::new(
+            board.state(DEFAULT_WITNESS_OPTIONS),
+            shot,
+            hit,
+            Fp::from(TURN_NONCE),
+        );
         let prover = MockProver::run(9, &circuit, vec![public_inputs]);
         assert_eq!(prover.unwrap().verify(), Ok(()));
     }
@@ -123,10 +262,15 @@ mod test {
             hashed,
             Fp::from_u128(shot.lower_u128()),
             Fp::from_u128(hit.lower_u128()),
+            Fp::from(TURN_NONCE),
         ];
         // construct BoardValidity circuit
-        let circuit =
-            ShotCircuit::<P128Pow5T3, Fp>::new(board.state(DEFAULT_WITNESS_OPTIONS), shot, hit);
+        let circuit = ShotCircuit::<P128Pow5T3, Fp>::new(
+            board.state(DEFAULT_WITNESS_OPTIONS),
+            shot,
+            hit,
+            Fp::from(TURN_NONCE),
+        );
         let prover = MockProver::run(9, &circuit, vec![public_inputs]);
         assert_eq!(prover.unwrap().verify(), Ok(()));
     }
@@ -151,10 +295,15 @@ mod test {
             hashed,
             Fp::from_u128(shot.lower_u128()),
             Fp::from_u128(hit.lower_u128()),
+            Fp::from(TURN_NONCE),
         ];
         // construct BoardValidity circuit
-        let circuit =
-            ShotCircuit::<P128Pow5T3, Fp>::new(board.state(DEFAULT_WITNESS_OPTIONS), shot, hit);
+        let circuit = ShotCircuit::<P128Pow5T3, Fp>::new(
+            board.state(DEFAULT_WITNESS_OPTIONS),
+            shot,
+            hit,
+            Fp::from(TURN_NONCE),
+        );
         let prover = MockProver::run(9, &circuit, vec![public_inputs]);
         assert_eq!(prover.unwrap().verify(), Ok(()));
     }
@@ -183,14 +332,68 @@ mod test {
             hashed,
             Fp::from_u128(shot.lower_u128()),
             Fp::from_u128(hit.lower_u128()),
+            Fp::from(TURN_NONCE),
         ];
         // mock prove ShotCircuit
-        let circuit =
-            ShotCircuit::<P128Pow5T3, Fp>::new(board.state(DEFAULT_WITNESS_OPTIONS), shot, hit);
+        let circuit = ShotCircuit::<P128Pow5T3, Fp>::new(
+            board.state(DEFAULT_WITNESS_OPTIONS),
+            shot,
+            hit,
+            Fp::from(TURN_NONCE),
+        );
         let prover = MockProver::run(9, &circuit, vec![public_exports]);
         assert_eq!(prover.unwrap().verify(), Ok(()));
     }
 
+    #[test]
+    fn invalid_replayed_nonce() {
+        // construct valid battleship board pattern 1
+        let board = Board::from(&Deck::from([
+            Some((3, 3, true)),
+            Some((5, 4, false)),
+            Some((0, 1, false)),
+            Some((0, 5, true)),
+            Some((6, 1, false)),
+        ]));
+        let shot = serialize::<1>([3], [5]);
+        let hit = BinaryValue::from_u8(1);
+        let hashed =
+            Poseidon::<_, P128Pow5T3, ConstantLength<1>, 3, 2>::init().hash([Fp::from_u128(
+                board.state(DEFAULT_WITNESS_OPTIONS).lower_u128(),
+            )]);
+        // prove the shot for turn N but check it against turn N + 1
+        let public_exports = vec![
+            hashed,
+            Fp::from_u128(shot.lower_u128()),
+            Fp::from_u128(hit.lower_u128()),
+            Fp::from(TURN_NONCE + 1),
+        ];
+        let circuit = ShotCircuit::<P128Pow5T3, Fp>::new(
+            board.state(DEFAULT_WITNESS_OPTIONS),
+            shot,
+            hit,
+            Fp::from(TURN_NONCE),
+        );
+        let prover = MockProver::run(9, &circuit, vec![public_exports]).unwrap();
+        // expect the copy constraint between the nonce and public instance to fail
+        assert_eq!(
+            prover.verify(),
+            Err(vec![
+                VerifyFailure::Permutation {
+                    column: (Any::Advice, 4).into(),
+                    location: FailureLocation::InRegion {
+                        region: (0, "load private ShotChip advice values").into(),
+                        offset: 4
+                    }
+                },
+                VerifyFailure::Permutation {
+                    column: (Any::Instance, 0).into(),
+                    location: FailureLocation::OutsideRegion { row: 3 }
+                },
+            ])
+        );
+    }
+
     #[test]
     fn invalid_non_boolean_hit_assertion() {
         // construct battleship board pattern 2
@@ -215,10 +418,15 @@ mod test {
             hashed,
             Fp::from_u128(shot.lower_u128()),
             Fp::from_u128(hit.lower_u128()),
+            Fp::from(TURN_NONCE),
         ];
         // mock prove ShotCircuit
-        let circuit =
-            ShotCircuit::<P128Pow5T3, Fp>::new(board.state(DEFAULT_WITNESS_OPTIONS), shot, hit);
+        let circuit = ShotCircuit::<P128Pow5T3, Fp>::new(
+            board.state(DEFAULT_WITNESS_OPTIONS),
+            shot,
+            hit,
+            Fp::from(TURN_NONCE),
+        );
         let prover = MockProver::run(9, &circuit, vec![public_exports]).unwrap();
         // expect failure
         assert_eq!(
@@ -283,10 +491,15 @@ mod test {
             hashed,
             Fp::from_u128(shot.lower_u128()),
             Fp::from_u128(hit.lower_u128()),
+            Fp::from(TURN_NONCE),
         ];
         // mock prove ShotCircuit
-        let circuit =
-            ShotCircuit::<P128Pow5T3, Fp>::new(board.state(DEFAULT_WITNESS_OPTIONS), shot, hit);
+        let circuit = ShotCircuit::<P128Pow5T3, Fp>::new(
+            board.state(DEFAULT_WITNESS_OPTIONS),
+            shot,
+            hit,
+            Fp::from(TURN_NONCE),
+        );
         let prover = MockProver::run(9, &circuit, vec![public_exports]).unwrap();
         // expect failure
         assert_eq!(
@@ -335,10 +548,15 @@ mod test {
             hashed,
             Fp::from_u128(shot.lower_u128()),
             Fp::from_u128(hit.lower_u128()),
+            Fp::from(TURN_NONCE),
         ];
         // mock prove ShotCircuit
-        let circuit =
-            ShotCircuit::<P128Pow5T3, Fp>::new(board.state(DEFAULT_WITNESS_OPTIONS), shot, hit);
+        let circuit = ShotCircuit::<P128Pow5T3, Fp>::new(
+            board.state(DEFAULT_WITNESS_OPTIONS),
+            shot,
+            hit,
+            Fp::from(TURN_NONCE),
+        );
         let prover = MockProver::run(9, &circuit, vec![public_exports]).unwrap();
         // expect failure
         assert_eq!(
@@ -387,10 +605,15 @@ mod test {
             hashed,
             Fp::from_u128(shot.lower_u128()),
             Fp::from_u128(hit.lower_u128()),
+            Fp::from(TURN_NONCE),
         ];
         // mock prove ShotCircuit
-        let circuit =
-            ShotCircuit::<P128Pow5T3, Fp>::new(board.state(DEFAULT_WITNESS_OPTIONS), shot, hit);
+        let circuit = ShotCircuit::<P128Pow5T3, Fp>::new(
+            board.state(DEFAULT_WITNESS_OPTIONS),
+            shot,
+            hit,
+            Fp::from(TURN_NONCE),
+        );
         let prover = MockProver::run(9, &circuit, vec![public_exports]).unwrap();
         // expect failure
         assert_eq!(
@@ -436,10 +659,15 @@ mod test {
             hashed,
             Fp::from_u128(shot.lower_u128()),
             Fp::from_u128(hit.lower_u128()),
+            Fp::from(TURN_NONCE),
         ];
         // mock prove ShotCircuit
-        let circuit =
-            ShotCircuit::<P128Pow5T3, Fp>::new(board.state(DEFAULT_WITNESS_OPTIONS), shot, hit);
+        let circuit = ShotCircuit::<P128Pow5T3, Fp>::new(
+            board.state(DEFAULT_WITNESS_OPTIONS),
+            shot,
+            hit,
+            Fp::from(TURN_NONCE),
+        );
         let prover = MockProver::run(9, &circuit, vec![public_exports]).unwrap();
         // expect failure
         assert_eq!(
@@ -486,10 +714,15 @@ mod test {
             hashed,
             Fp::from_u128(shot.lower_u128()),
             Fp::from_u128(hit.lower_u128()),
+            Fp::from(TURN_NONCE),
         ];
         // mock prove ShotCircuit
-        let circuit =
-            ShotCircuit::<P128Pow5T3, Fp>::new(board.state(DEFAULT_WITNESS_OPTIONS), shot, hit);
+        let circuit = ShotCircuit::<P128Pow5T3, Fp>::new(
+            board.state(DEFAULT_WITNESS_OPTIONS),
+            shot,
+            hit,
+            Fp::from(TURN_NONCE),
+        );
         let prover = MockProver::run(9, &circuit, vec![public_exports]).unwrap();
         // expect failure
         assert_eq!(
@@ -554,10 +787,15 @@ mod test {
             hashed,
             Fp::from_u128(shot.lower_u128()),
             Fp::from_u128(hit.lower_u128()),
+            Fp::from(TURN_NONCE),
         ];
         // mock prove ShotCircuit
-        let circuit =
-            ShotCircuit::<P128Pow5T3, Fp>::new(board.state(DEFAULT_WITNESS_OPTIONS), shot, hit);
+        let circuit = ShotCircuit::<P128Pow5T3, Fp>::new(
+            board.state(DEFAULT_WITNESS_OPTIONS),
+            shot,
+            hit,
+            Fp::from(TURN_NONCE),
+        );
         let prover = MockProver::run(9, &circuit, vec![public_exports]).unwrap();
         // expect failure
         assert_eq!(
@@ -566,7 +804,7 @@ mod test {
                 VerifyFailure::Permutation {
                     column: (Any::Advice, 0).into(),
                     location: FailureLocation::InRegion {
-                        region: (7, "permute state").into(),
+                        region: (8, "permute state").into(),
                         offset: 36
                     }
                 },
@@ -603,10 +841,15 @@ mod test {
             hashed + Fp::one(),
             Fp::from_u128(shot.lower_u128()),
             Fp::from_u128(hit.lower_u128()),
+            Fp::from(TURN_NONCE),
         ];
         // mock prove ShotCircuit
-        let circuit =
-            ShotCircuit::<P128Pow5T3, Fp>::new(board.state(DEFAULT_WITNESS_OPTIONS), shot, hit);
+        let circuit = ShotCircuit::<P128Pow5T3, Fp>::new(
+            board.state(DEFAULT_WITNESS_OPTIONS),
+            shot,
+            hit,
+            Fp::from(TURN_NONCE),
+        );
         let prover = MockProver::run(9, &circuit, vec![public_exports]).unwrap();
         // expect failure
         assert_eq!(
@@ -615,7 +858,7 @@ mod test {
                 VerifyFailure::Permutation {
                     column: (Any::Advice, 0).into(),
                     location: FailureLocation::InRegion {
-                        region: (7, "permute state").into(),
+                        region: (8, "permute state").into(),
                         offset: 36
                     }
                 },
@@ -652,10 +895,15 @@ mod test {
             hashed,
             Fp::from_u128(shot.lower_u128()) + Fp::one(),
             Fp::from_u128(hit.lower_u128()),
+            Fp::from(TURN_NONCE),
         ];
         // mock prove ShotCircuit
-        let circuit =
-            ShotCircuit::<P128Pow5T3, Fp>::new(board.state(DEFAULT_WITNESS_OPTIONS), shot, hit);
+        let circuit = ShotCircuit::<P128Pow5T3, Fp>::new(
+            board.state(DEFAULT_WITNESS_OPTIONS),
+            shot,
+            hit,
+            Fp::from(TURN_NONCE),
+        );
         let prover = MockProver::run(9, &circuit, vec![public_exports]).unwrap();
         // expect failure
         assert_eq!(
@@ -701,10 +949,15 @@ mod test {
             hashed,
             Fp::from_u128(shot.lower_u128()),
             Fp::from_u128(hit.lower_u128()) + Fp::one(),
+            Fp::from(TURN_NONCE),
         ];
         // mock prove ShotCircuit
-        let circuit =
-            ShotCircuit::<P128Pow5T3, Fp>::new(board.state(DEFAULT_WITNESS_OPTIONS), shot, hit);
+        let circuit = ShotCircuit::<P128Pow5T3, Fp>::new(
+            board.state(DEFAULT_WITNESS_OPTIONS),
+            shot,
+            hit,
+            Fp::from(TURN_NONCE),
+        );
         let prover = MockProver::run(9, &circuit, vec![public_exports]).unwrap();
         // expect failure
         assert_eq!(
@@ -741,6 +994,88 @@ mod test {
         );
     }
 
+    /**
+     * Mock prove that a region of board pattern 1 contains no ship
+     *
+     * @param cells - (x, y) coordinates of every cell in the region
+     * @return - the result of verifying the region miss proof
+     */
+    fn prove_region_miss(cells: Vec<(u8, u8)>) -> Result<(), Vec<VerifyFailure>> {
+        let board = Board::from(&Deck::from([
+            Some((3, 3, true)),
+            Some((5, 4, false)),
+            Some((0, 1, false)),
+            Some((0, 5, true)),
+            Some((6, 1, false)),
+        ]))
+        .state(DEFAULT_WITNESS_OPTIONS);
+        let hashed = Poseidon::<_, P128Pow5T3, ConstantLength<1>, 3, 2>::init()
+            .hash([Fp::from_u128(board.lower_u128())]);
+        let public_inputs = vec![hashed, Fp::from_u128(serialize_cells(&cells).lower_u128())];
+        let circuit = RegionMissCircuit::<P128Pow5T3, Fp>::new(board, cells);
+        let prover = MockProver::run(9, &circuit, vec![public_inputs]).unwrap();
+        prover.verify()
+    }
+
+    #[test]
+    fn valid_region_miss() {
+        // 3 x 3 block of water in the top right corner
+        let cells = (7..10)
+            .flat_map(|x| (7..10).map(move |y| (x, y)))
+            .collect::<Vec<(u8, u8)>>();
+        assert_eq!(prove_region_miss(cells), Ok(()));
+    }
+
+    #[test]
+    fn valid_salted_region_miss() {
+        let board = Board::from(&Deck::default()).state(DEFAULT_WITNESS_OPTIONS);
+        let cells = vec![(9, 9), (8, 9)];
+        let (secret, game_id) = (Fp::from(0xbee5), Fp::from(42));
+        let region = Fp::from_u128(serialize_cells(&cells).lower_u128());
+        let circuit =
+            RegionMissCircuit::<P128Pow5T3, Fp>::new(board, cells).salted(secret, game_id);
+        let instance = InstanceLayout::SaltedRegionMiss.assemble(|input| match input {
+            PublicInput::Digest => commit_board(board, secret, game_id),
+            PublicInput::Region => region,
+            _ => game_id,
+        });
+        assert_valid_with_instances(&circuit, 9, vec![instance]);
+    }
+
+    #[test]
+    fn invalid_region_cells() {
+        // an empty region proves nothing and an off-board cell cannot be serialized
+        let board = Board::from(&Deck::default()).state(DEFAULT_WITNESS_OPTIONS);
+        for cells in [vec![], vec![(9, 9), (10, 0)], vec![(0, 26)]] {
+            let circuit = RegionMissCircuit::<P128Pow5T3, Fp>::new(board, cells.clone());
+            let result = MockProver::run(9, &circuit, vec![vec![Fp::zero(); 2]]);
+            assert!(matches!(result, Err(Error::Synthesis)), "cells {:?}", cells);
+        }
+    }
+
+    #[test]
+    fn invalid_region_miss() {
+        // row 3 from x: 0 to x: 4 crosses the vertical carrier at (3, 3)
+        let cells = (0..5).map(|x| (x, 3)).collect::<Vec<(u8, u8)>>();
+        assert_eq!(
+            prove_region_miss(cells),
+            Err(vec![VerifyFailure::ConstraintNotSatisfied {
+                // fail constraint: region contains 1 ship cell
+                constraint: (
+                    (8, "constrain region miss output").into(),
+                    0,
+                    "Region contains no ship cell"
+                )
+                    .into(),
+                location: FailureLocation::InRegion {
+                    region: (4, "region miss output check").into(),
+                    offset: 0
+                },
+                cell_values: vec![(((Any::Advice, 2).into(), 0).into(), String::from("1"))]
+            }])
+        );
+    }
+
     // #[test]
     // fn print_circuit() {
     //     use plotters::prelude::*;
@@ -754,7 +1089,7 @@ mod test {
     //     let shot = serialize::<1>([1], [6]);
     //     let hit = BinaryValue::from_u8(1);
     //     let circuit =
-    //         ShotCircuit::<P128Pow5T3, Fp>::new(board.state(DEFAULT_WITNESS_OPTIONS), shot, hit);
+    //         ShotCircuit::<P128Pow5T3, Fp>::new(board.state(DEFAULT_WITNESS_OPTIONS), shot, hit, Fp::from(TURN_NONCE));
     //     let root = BitMapBackend::new("src/shot/shot_layout.png", (1920, 1080)).into_drawing_area();
     //     root.fill(&WHITE).unwrap();
     //     let root = root
@@ -0,0 +1,191 @@
+use {
+    crate::{
+        chips::{
+            board::BoardSalt,
+            tally::{TallyChip, TallyConfig},
+        },
+        utils::{
+            binary::BinaryValue,
+            instance::{InstanceLayout, PublicInput},
+        },
+    },
+    halo2_gadgets::poseidon::primitives::Spec,
+    halo2_proofs::{
+        arithmetic::FieldExt,
+        circuit::{Layouter, SimpleFloorPlanner},
+        plonk::{Circuit, ConstraintSystem, Error},
+    },
+    std::marker::PhantomData,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct TallyCircuit<S: Spec<F, 3, 2>, F: FieldExt> {
+    pub board: BinaryValue,
+    pub shots: BinaryValue,
+    pub salt: Option<BoardSalt<F>>,
+    _field: PhantomData<F>,
+    _spec: PhantomData<S>,
+}
+
+impl<S: Spec<F, 3, 2>, F: FieldExt> Circuit<F> for TallyCircuit<S, F> {
+    type Config = TallyConfig<F>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        TallyCircuit {
+            salt: self.salt,
+            ..TallyCircuit::new(self.board, self.shots)
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        TallyChip::<S, F>::configure(meta)
+    }
+
+    fn synthesize(&self, config: Self::Config, layouter: impl Layouter<F>) -> Result<(), Error> {
+        TallyChip::<S, F>::new(config).synthesize(layouter, self.board, self.shots, self.salt)
+    }
+}
+
+impl<S: Spec<F, 3, 2>, F: FieldExt> TallyCircuit<S, F> {
+    /**
+     * Construct a new tally circuit proving how many shots fired hit a committed board
+     * @dev the hit count is not witnessed: it is computed in-circuit and checked against the instance
+     *
+     * @param board - private board state
+     * @param shots - every shot fired at the board serialized like the board state (public)
+     * @return - instantiated TallyCircuit object
+     */
+    pub fn new(board: BinaryValue, shots: BinaryValue) -> TallyCircuit<S, F> {
+        TallyCircuit {
+            board,
+            shots,
+            salt: None,
+            _field: PhantomData,
+            _spec: PhantomData,
+        }
+    }
+
+    /**
+     * Prove against a board committed under a per-game salt (see SaltedBoardCircuit)
+     * @dev the public digest becomes prover::commit_board(board, secret, game_id) and the game id
+     *      is appended to the instance column (InstanceLayout::Tally.salted())
+     *
+     * @param secret - the player's private secret, reused across games
+     * @param game_id - public identifier of the game
+     * @return - the circuit checking the salted board commitment
+     */
+    pub fn salted(mut self, secret: F, game_id: F) -> TallyCircuit<S, F> {
+        let row = InstanceLayout::Tally.salted().row(PublicInput::GameId);
+        self.salt = Some(BoardSalt {
+            secret,
+            game_id,
+            row,
+        });
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use {
+        super::*,
+        crate::{
+            prover::{board_digest, commit_board},
+            test_utils::assert_valid_with_instances,
+            utils::{
+                board::Board, deck::Deck, ship::DEFAULT_WITNESS_OPTIONS, shot::serialize_cells,
+            },
+        },
+        halo2_gadgets::poseidon::primitives::P128Pow5T3,
+        halo2_proofs::{
+            dev::{FailureLocation, MockProver, VerifyFailure},
+            pasta::Fp,
+            plonk::Any,
+        },
+    };
+
+    /**
+     * Assemble the public inputs of a tally proof
+     *
+     * @param board - private board state
+     * @param shots - every shot fired at the board
+     * @param hits - claimed number of hits
+     * @return - instance column values in tally layout order
+     */
+    fn tally_instance(board: BinaryValue, shots: BinaryValue, hits: u64) -> Vec<Fp> {
+        InstanceLayout::Tally.assemble(|input| match input {
+            PublicInput::Digest => board_digest(board),
+            PublicInput::Shots => Fp::from_u128(shots.lower_u128()),
+            PublicInput::Hits => Fp::from(hits),
+            _ => unreachable!(),
+        })
+    }
+
+    /**
+     * Return a board state and shots fired overlapping on exactly 3 cells
+     */
+    fn board_and_shots() -> (BinaryValue, BinaryValue) {
+        let board = Board::from(&Deck::from([
+            Some((3, 3, true)),
+            Some((5, 4, false)),
+            Some((0, 1, false)),
+            Some((0, 5, true)),
+            Some((6, 1, false)),
+        ]))
+        .state(DEFAULT_WITNESS_OPTIONS);
+        // hits at (3, 3), (0, 1), (6, 1); misses at (9, 9), (0, 0)
+        let shots = serialize_cells(&[(3, 3), (9, 9), (0, 1), (0, 0), (6, 1)]);
+        (board, shots)
+    }
+
+    #[test]
+    fn valid_tally() {
+        let (board, shots) = board_and_shots();
+        assert_eq!((board & shots).value.count_ones(), 3);
+        let circuit = TallyCircuit::<P128Pow5T3, Fp>::new(board, shots);
+        let prover = MockProver::run(9, &circuit, vec![tally_instance(board, shots, 3)]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    #[test]
+    fn valid_salted_tally() {
+        let (board, shots) = board_and_shots();
+        let (secret, game_id) = (Fp::from(0xbee5), Fp::from(42));
+        let circuit = TallyCircuit::<P128Pow5T3, Fp>::new(board, shots).salted(secret, game_id);
+        let unsalted = tally_instance(board, shots, 3);
+        let instance = InstanceLayout::SaltedTally.assemble(|input| match input {
+            PublicInput::Digest => commit_board(board, secret, game_id),
+            PublicInput::GameId => game_id,
+            input => unsalted[InstanceLayout::Tally.row(input)],
+        });
+        assert_valid_with_instances(&circuit, 9, vec![instance]);
+    }
+
+    #[test]
+    fn invalid_tally() {
+        let (board, shots) = board_and_shots();
+        let circuit = TallyCircuit::<P128Pow5T3, Fp>::new(board, shots);
+        let prover = MockProver::run(9, &circuit, vec![tally_instance(board, shots, 4)]).unwrap();
+        // only the final running count fails to match the claimed hit count instance
+        assert_eq!(
+            prover.verify(),
+            Err(vec![
+                VerifyFailure::Permutation {
+                    column: (Any::Advice, 3).into(),
+                    location: FailureLocation::InRegion {
+                        region: (3, "shot running sum").into(),
+                        offset: 100,
+                    },
+                },
+                VerifyFailure::Permutation {
+                    column: (Any::Instance, 0).into(),
+                    location: FailureLocation::OutsideRegion {
+                        row: InstanceLayout::Tally.row(PublicInput::Hits),
+                    },
+                },
+            ])
+        );
+    }
+}
@@ -0,0 +1,237 @@
+use {
+    crate::{
+        chips::{
+            board::BoardSalt,
+            win::{WinChip, WinConfig},
+        },
+        utils::{
+            binary::BinaryValue,
+            instance::{InstanceLayout, PublicInput},
+        },
+    },
+    halo2_gadgets::poseidon::primitives::Spec,
+    halo2_proofs::{
+        arithmetic::FieldExt,
+        circuit::{Layouter, SimpleFloorPlanner},
+        plonk::{Circuit, ConstraintSystem, Error},
+    },
+    std::marker::PhantomData,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct WinCircuit<S: Spec<F, 3, 2>, F: FieldExt> {
+    pub board: BinaryValue,
+    pub shots: BinaryValue,
+    pub salt: Option<BoardSalt<F>>,
+    _field: PhantomData<F>,
+    _spec: PhantomData<S>,
+}
+
+impl<S: Spec<F, 3, 2>, F: FieldExt> Circuit<F> for WinCircuit<S, F> {
+    type Config = WinConfig<F>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        WinCircuit {
+            salt: self.salt,
+            ..WinCircuit::new(self.board, self.shots)
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        WinChip::<S, F>::configure(meta)
+    }
+
+    fn synthesize(&self, config: Self::Config, layouter: impl Layouter<F>) -> Result<(), Error> {
+        WinChip::<S, F>::new(config).synthesize(layouter, self.board, self.shots, self.salt)
+    }
+}
+
+impl<S: Spec<F, 3, 2>, F: FieldExt> WinCircuit<S, F> {
+    /**
+     * Construct a new win circuit proving whether every ship cell on a committed board was shot
+     *
+     * @param board - private board state
+     * @param shots - every shot fired at the board serialized like the board state (public)
+     * @return - instantiated WinCircuit object
+     */
+    pub fn new(board: BinaryValue, shots: BinaryValue) -> WinCircuit<S, F> {
+        WinCircuit {
+            board,
+            shots,
+            salt: None,
+            _field: PhantomData,
+            _spec: PhantomData,
+        }
+    }
+
+    /**
+     * Prove against a board committed under a per-game salt (see SaltedBoardCircuit)
+     * @dev the public digest becomes prover::commit_board(board, secret, game_id) and the game id
+     *      is appended to the instance column (InstanceLayout::Win.salted())
+     *
+     * @param secret - the player's private secret, reused across games
+     * @param game_id - public identifier of the game
+     * @return - the circuit checking the salted board commitment
+     */
+    pub fn salted(mut self, secret: F, game_id: F) -> WinCircuit<S, F> {
+        let row = InstanceLayout::Win.salted().row(PublicInput::GameId);
+        self.salt = Some(BoardSalt {
+            secret,
+            game_id,
+            row,
+        });
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use {
+        super::*,
+        crate::{
+            test_utils::{assert_fails_with, assert_valid},
+            utils::{
+                board::{replay_shots, sink_all_shots, Board},
+                deck::Deck,
+                ship::DEFAULT_WITNESS_OPTIONS,
+            },
+        },
+        halo2_gadgets::poseidon::primitives::{ConstantLength, Hash as Poseidon, P128Pow5T3},
+        halo2_proofs::{
+            dev::{FailureLocation, VerifyFailure},
+            pasta::Fp,
+            plonk::Any,
+        },
+    };
+
+    /**
+     * Return the public inputs for a win proof
+     *
+     * @param board - private board state
+     * @param shots - every shot fired at the board
+     * @param win - public win value
+     * @return - [board commitment, shots, win]
+     */
+    fn public_inputs(board: BinaryValue, shots: BinaryValue, win: u8) -> Vec<Fp> {
+        let hashed = Poseidon::<_, P128Pow5T3, ConstantLength<1>, 3, 2>::init()
+            .hash([Fp::from_u128(board.lower_u128())]);
+        vec![
+            hashed,
+            Fp::from_u128(shots.lower_u128()),
+            Fp::from(win as u64),
+        ]
+    }
+
+    /**
+     * Return the state of a board with a fixed valid fleet
+     */
+    fn board_state() -> BinaryValue {
+        Board::from(&Deck::from([
+            Some((3, 3, true)),
+            Some((5, 4, false)),
+            Some((0, 1, false)),
+            Some((0, 5, true)),
+            Some((6, 1, false)),
+        ]))
+        .state(DEFAULT_WITNESS_OPTIONS)
+    }
+
+    // failures of a proof whose computed win value does not match the exported win value
+    fn win_failures() -> Vec<VerifyFailure> {
+        vec![
+            VerifyFailure::Permutation {
+                column: (Any::Advice, 2).into(),
+                location: FailureLocation::InRegion {
+                    region: (3, "win running product").into(),
+                    offset: 100,
+                },
+            },
+            VerifyFailure::Permutation {
+                column: (Any::Instance, 0).into(),
+                location: FailureLocation::OutsideRegion {
+                    row: InstanceLayout::Win.row(PublicInput::Win),
+                },
+            },
+        ]
+    }
+
+    #[test]
+    fn valid_win() {
+        let board = board_state();
+        // every ship cell hit, plus a few misses
+        let mut shots = board;
+        for i in [0, 42, 99] {
+            shots.value.set(i, true);
+        }
+        let circuit = WinCircuit::<P128Pow5T3, Fp>::new(board, shots);
+        assert_valid(&circuit, 9, vec![public_inputs(board, shots, 1)]);
+    }
+
+    #[test]
+    fn valid_win_replayed_shots() {
+        let board = board_state();
+        // shots in game order: misses and a repeated hit interleaved with every ship cell
+        let mut shots = vec![(9, 9), (3, 3)];
+        for (x, y) in sink_all_shots(board).into_iter().rev() {
+            shots.push((x as u8, y as u8));
+        }
+        shots.push((0, 0));
+        let fired = replay_shots(&shots).unwrap();
+        let circuit = WinCircuit::<P128Pow5T3, Fp>::new(board, fired);
+        assert_valid(&circuit, 9, vec![public_inputs(board, fired, 1)]);
+    }
+
+    #[test]
+    fn valid_no_win() {
+        let board = board_state();
+        // cell (3, 3) belongs to the carrier but was never shot
+        let mut shots = board;
+        shots.value.set(33, false);
+        let circuit = WinCircuit::<P128Pow5T3, Fp>::new(board, shots);
+        assert_valid(&circuit, 9, vec![public_inputs(board, shots, 0)]);
+    }
+
+    #[test]
+    fn invalid_win_missing_cell() {
+        let board = board_state();
+        // cell (3, 3) belongs to the carrier but was never shot
+        let mut shots = board;
+        shots.value.set(33, false);
+        let circuit = WinCircuit::<P128Pow5T3, Fp>::new(board, shots);
+        assert_fails_with(
+            &circuit,
+            9,
+            vec![public_inputs(board, shots, 1)],
+            win_failures(),
+        );
+    }
+
+    #[test]
+    fn invalid_no_win_fully_hit() {
+        let board = board_state();
+        // every ship cell was hit, so the board owner cannot publish no win
+        let shots = board;
+        let circuit = WinCircuit::<P128Pow5T3, Fp>::new(board, shots);
+        assert_fails_with(
+            &circuit,
+            9,
+            vec![public_inputs(board, shots, 0)],
+            win_failures(),
+        );
+    }
+
+    #[test]
+    fn invalid_non_boolean_win() {
+        let board = board_state();
+        let shots = board;
+        let circuit = WinCircuit::<P128Pow5T3, Fp>::new(board, shots);
+        assert_fails_with(
+            &circuit,
+            9,
+            vec![public_inputs(board, shots, 2)],
+            win_failures(),
+        );
+    }
+}
@@ -1,3 +1,6 @@
-mod utils;
-mod chips;
-mod circuits;
\ No newline at end of file
+pub mod utils;
+pub mod chips;
+pub mod circuits;
+pub mod prover;
+#[cfg(test)]
+pub mod test_utils;
\ No newline at end of file
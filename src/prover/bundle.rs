@@ -0,0 +1,67 @@
+use halo2_proofs::pasta::{group::ff::PrimeField, Fp};
+
+// circuit identifiers a bundle can be deserialized with
+pub const CIRCUIT_IDS: [&str; 1] = [super::BOARD_CIRCUIT_ID];
+
+/**
+ * A proof packaged with everything a verifier needs to check it
+ *
+ * @param proof - transcript bytes output by the prover
+ * @param digest - public board commitment the proof was made against
+ * @param k - log2 of the number of rows in the circuit the proof was made with
+ * @param circuit_id - identifier of the circuit the proof was made with
+ */
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProofBundle {
+    pub proof: Vec<u8>,
+    pub digest: Fp,
+    pub k: u32,
+    pub circuit_id: &'static str,
+}
+
+impl ProofBundle {
+    /**
+     * Serialize the bundle for transport
+     * @dev layout: k (u32 LE) | digest (32 byte repr) | circuit_id length (u8) | circuit_id | proof
+     *
+     * @return - serialized bundle bytes
+     */
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::<u8>::new();
+        bytes.extend_from_slice(&self.k.to_le_bytes());
+        bytes.extend_from_slice(&self.digest.to_repr());
+        bytes.push(self.circuit_id.len() as u8);
+        bytes.extend_from_slice(self.circuit_id.as_bytes());
+        bytes.extend_from_slice(&self.proof);
+        bytes
+    }
+
+    /**
+     * Deserialize a bundle serialized by to_bytes()
+     *
+     * @param bytes - serialized bundle bytes
+     * @return - the deserialized bundle, or a string explaining why deserialization failed
+     */
+    pub fn from_bytes(bytes: &[u8]) -> Result<ProofBundle, &'static str> {
+        if bytes.len() < 37 {
+            return Err("Bundle is too short!");
+        }
+        let k = u32::from_le_bytes(bytes[0..4].try_into().unwrap());
+        let digest = Option::from(Fp::from_repr(bytes[4..36].try_into().unwrap()))
+            .ok_or("Bundle digest is not a field element!")?;
+        let id_end = 37 + bytes[36] as usize;
+        if bytes.len() < id_end {
+            return Err("Bundle is too short!");
+        }
+        let circuit_id = CIRCUIT_IDS
+            .into_iter()
+            .find(|id| id.as_bytes() == &bytes[37..id_end])
+            .ok_or("Bundle circuit id is unknown!")?;
+        Ok(ProofBundle {
+            proof: bytes[id_end..].to_vec(),
+            digest,
+            k,
+            circuit_id,
+        })
+    }
+}
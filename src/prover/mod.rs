@@ -0,0 +1,1128 @@
+pub mod bundle;
+
+pub use halo2_proofs::dev::VerifyFailure;
+
+use {
+    crate::{
+        chips::board::placement_namespace,
+        circuits::{board::BoardCircuit, shot::ShotCircuit},
+        prover::bundle::ProofBundle,
+        utils::{
+            binary::BinaryValue,
+            board::{combine_commitments, Board, OverlapError},
+            deck::Deck,
+            instance::InstanceLayout,
+            layout::LayoutRecorder,
+            ship::{get_ship_name, ShipType, DEFAULT_WITNESS_OPTIONS},
+        },
+    },
+    halo2_gadgets::poseidon::primitives::{ConstantLength, Hash as Poseidon, P128Pow5T3},
+    halo2_proofs::{
+        arithmetic::FieldExt,
+        dev::{metadata, FailureLocation, MockProver},
+        pasta::{group::ff::PrimeField, EqAffine, Fp},
+        plonk::{
+            create_proof, keygen_pk, keygen_vk, verify_proof, Circuit, ConstraintSystem, Error,
+            ProvingKey, SingleVerifier, VerifyingKey,
+        },
+        poly::commitment::Params,
+        transcript::{Blake2bRead, Blake2bWrite, Challenge255},
+    },
+    rand_core::OsRng,
+    std::{fmt, sync::OnceLock},
+};
+
+pub const BOARD_K: u32 = 12; // 2^BOARD_K rows used in board circuit
+pub const BOARD_CIRCUIT_ID: &str = "board";
+
+/**
+ * Compute the public commitment to a board state
+ *
+ * @param state - the private board state
+ * @return - poseidon hash of the board state
+ */
+pub fn board_digest(state: BinaryValue) -> Fp {
+    Poseidon::<_, P128Pow5T3, ConstantLength<1>, 3, 2>::init()
+        .hash([Fp::from_u128(state.lower_u128())])
+}
+
+/**
+ * Derive the salt mixed into a board commitment for one game
+ * @dev matches the salt derived in-circuit by chips::poseidon::commit_salted
+ *
+ * @param secret - the player's private secret, reused across games
+ * @param game_id - public identifier of the game
+ * @return - poseidon hash of [secret, game_id]
+ */
+pub fn derive_salt(secret: Fp, game_id: Fp) -> Fp {
+    Poseidon::<_, P128Pow5T3, ConstantLength<2>, 3, 2>::init().hash([secret, game_id])
+}
+
+/**
+ * Compute the salted public commitment to a board state for one game
+ * @dev the same board state commits differently under every game_id, so a player reusing a
+ *      layout across games cannot be linked by their commitments
+ *
+ * @param state - the private board state
+ * @param secret - the player's private secret, reused across games
+ * @param game_id - public identifier of the game
+ * @return - poseidon hash of [board state, derive_salt(secret, game_id)]
+ */
+pub fn commit_board(state: BinaryValue, secret: Fp, game_id: Fp) -> Fp {
+    Poseidon::<_, P128Pow5T3, ConstantLength<2>, 3, 2>::init().hash([
+        Fp::from_u128(state.lower_u128()),
+        derive_salt(secret, game_id),
+    ])
+}
+
+/**
+ * Compute the public commitment to a board from the commitments of its ships' placement proofs
+ * @dev combines placements like the board chip (V transposed, no overlaps) so a verifier can check
+ *      five separately proven ships compose to the digest exported by a board proof
+ *
+ * @param commitments - [H, V] commitments exported by each placement proof in deck order
+ * @return - poseidon hash of the combined board state, or where two ships overlap
+ */
+pub fn aggregate_ship_commitments(commitments: &[[Fp; 2]; 5]) -> Result<Fp, OverlapError> {
+    let commitments =
+        commitments.map(|ship| ship.map(|value| BinaryValue::from_field_bytes(value.to_repr())));
+    combine_commitments(&commitments).map(board_digest)
+}
+
+/**
+ * Proofs a game is played with, all verified against one shared set of IPA params
+ * @dev placement is constrained inside the board circuit, so Board covers placement proofs
+ *
+ * @param Board - proof a committed board holds a legal fleet
+ * @param Shot - proof a shot hits or misses the committed board
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ProofKind {
+    Board,
+    Shot,
+}
+
+impl ProofKind {
+    /**
+     * Return the smallest k the proof's circuit can be proven with
+     * @dev the layout does not depend on the witness, so empty placeholder values are laid out
+     *
+     * @return - minimum k for the proof, or the error laying its circuit out
+     */
+    pub fn min_k(self) -> Result<u32, Error> {
+        match self {
+            ProofKind::Board => BoardCircuit::<P128Pow5T3, Fp>::new(
+                [BinaryValue::empty(); 10],
+                BinaryValue::empty(),
+            )
+            .min_k(),
+            ProofKind::Shot => ShotCircuit::<P128Pow5T3, Fp>::new(
+                BinaryValue::empty(),
+                BinaryValue::empty(),
+                BinaryValue::empty(),
+                Fp::zero(),
+            )
+            .min_k(),
+        }
+    }
+}
+
+/**
+ * Return the single SRS degree covering every proof a game configuration uses
+ * @dev IPA params are generated per k and params for k can prove any circuit that fits in 2^k
+ *      rows, so the largest min_k is enough. generating params beyond it only wastes time/ memory
+ *
+ * @param config - the proofs the game is played with
+ * @return - k to pass to Params::new (0 if no proofs are used), or the error laying out a proof
+ */
+pub fn required_params_k(config: &[ProofKind]) -> Result<u32, Error> {
+    config
+        .iter()
+        .map(|kind| kind.min_k())
+        .try_fold(0, |k, min_k| Ok(k.max(min_k?)))
+}
+
+/**
+ * Generate the proving key for the board circuit
+ * @dev the verifying key is available through pk.get_vk()
+ *
+ * @param params - IPA params generated for 2^BOARD_K rows
+ * @return - proving key for the board circuit
+ */
+pub fn board_keys(params: &Params<EqAffine>) -> Result<ProvingKey<EqAffine>, Error> {
+    let board = Board::from(&crate::utils::deck::Deck::default());
+    let circuit = BoardCircuit::<P128Pow5T3, Fp>::new(
+        board.witness(DEFAULT_WITNESS_OPTIONS),
+        board.state(DEFAULT_WITNESS_OPTIONS),
+    );
+    let vk = keygen_vk(params, &circuit)?;
+    keygen_pk(params, vk, &circuit)
+}
+
+/**
+ * Prove a board is valid and bundle the proof with its public commitment
+ *
+ * @param params - IPA params generated for 2^BOARD_K rows
+ * @param pk - proving key for the board circuit
+ * @param board - the private board to prove
+ * @return - bundle containing the proof and the public board commitment
+ */
+pub fn prove_board(
+    params: &Params<EqAffine>,
+    pk: &ProvingKey<EqAffine>,
+    board: &Board,
+) -> Result<ProofBundle, Error> {
+    prove_board_phases(params, pk, board, |_| {})
+}
+
+/**
+ * Coarse stages of proving a board, reported in order to a progress callback
+ * @dev halo2 exposes no hooks inside create_proof, so Proving covers every commitment/ evaluation round
+ *
+ * @param Witness - computing the board state, commitment and circuit witness
+ * @param Proving - running create_proof over the board circuit
+ * @param Finalizing - bundling the transcript into a proof
+ * @param Done - the proof bundle is ready
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ProvePhase {
+    Witness,
+    Proving,
+    Finalizing,
+    Done,
+}
+
+/**
+ * Prove a board is valid, reporting each phase as it starts
+ * @dev the callback is not called again after an error
+ *
+ * @param params - IPA params generated for 2^BOARD_K rows
+ * @param pk - proving key for the board circuit
+ * @param board - the private board to prove
+ * @param progress - called with each phase of proving in order
+ * @return - bundle containing the proof and the public board commitment
+ */
+#[cfg(feature = "progress")]
+pub fn prove_board_with_progress(
+    params: &Params<EqAffine>,
+    pk: &ProvingKey<EqAffine>,
+    board: &Board,
+    mut progress: impl FnMut(ProvePhase),
+) -> Result<ProofBundle, Error> {
+    let bundle = prove_board_phases(params, pk, board, &mut progress)?;
+    progress(ProvePhase::Done);
+    Ok(bundle)
+}
+
+/**
+ * Prove a board is valid, calling a hook as each phase before Done starts
+ *
+ * @param params - IPA params generated for 2^BOARD_K rows
+ * @param pk - proving key for the board circuit
+ * @param board - the private board to prove
+ * @param phase - called with each phase as it starts
+ * @return - bundle containing the proof and the public board commitment
+ */
+fn prove_board_phases(
+    params: &Params<EqAffine>,
+    pk: &ProvingKey<EqAffine>,
+    board: &Board,
+    mut phase: impl FnMut(ProvePhase),
+) -> Result<ProofBundle, Error> {
+    phase(ProvePhase::Witness);
+    let state = board.state(DEFAULT_WITNESS_OPTIONS);
+    let digest = board_digest(state);
+    let circuit =
+        BoardCircuit::<P128Pow5T3, Fp>::new(board.witness(DEFAULT_WITNESS_OPTIONS), state);
+    let instance = InstanceLayout::Board.assemble(|_| digest);
+    phase(ProvePhase::Proving);
+    let mut transcript = Blake2bWrite::<_, EqAffine, Challenge255<_>>::init(vec![]);
+    create_proof(
+        params,
+        pk,
+        &[circuit],
+        &[&[&instance]],
+        OsRng,
+        &mut transcript,
+    )?;
+    phase(ProvePhase::Finalizing);
+    Ok(ProofBundle {
+        proof: transcript.finalize(),
+        digest,
+        k: BOARD_K,
+        circuit_id: BOARD_CIRCUIT_ID,
+    })
+}
+
+/**
+ * Verify a bundled board proof against the public commitment it carries
+ *
+ * @param params - IPA params generated for 2^BOARD_K rows
+ * @param vk - verifying key for the board circuit
+ * @param bundle - the bundled proof to verify
+ * @return - Ok if the proof verifies, otherwise the verification error
+ */
+pub fn verify_board(
+    params: &Params<EqAffine>,
+    vk: &VerifyingKey<EqAffine>,
+    bundle: &ProofBundle,
+) -> Result<(), Error> {
+    // reject bundles made for a different circuit or circuit size
+    if bundle.circuit_id != BOARD_CIRCUIT_ID || bundle.k != BOARD_K {
+        return Err(Error::InvalidInstances);
+    }
+    let instance = InstanceLayout::Board.assemble(|_| bundle.digest);
+    let strategy = SingleVerifier::new(params);
+    let mut transcript = Blake2bRead::<_, EqAffine, Challenge255<_>>::init(&bundle.proof[..]);
+    verify_proof(params, vk, strategy, &[&[&instance]], &mut transcript)
+}
+
+/**
+ * Classification of a rejected board proof
+ *
+ * @param InstanceMismatch - the bundle was made for another circuit/ size, or its digest does not
+ *        commit to the supplied witness
+ * @param ConstraintUnsatisfied - the supplied witness breaks the listed board circuit constraints
+ * @param ProofMalformed - the proof bytes do not verify, and no witness explains why
+ */
+#[derive(Debug, PartialEq)]
+pub enum VerifyError {
+    InstanceMismatch,
+    ConstraintUnsatisfied(Vec<VerifyFailure>),
+    ProofMalformed,
+}
+
+/**
+ * Verify a bundled board proof, explaining why it was rejected
+ * @dev on failure, re-runs the witness (if available) through MockProver to find failed constraints
+ *
+ * @param params - IPA params generated for 2^BOARD_K rows
+ * @param vk - verifying key for the board circuit
+ * @param bundle - the bundled proof to verify
+ * @param witness - the board circuit the proof is believed to have been made with
+ * @return - Ok if the proof verifies, otherwise the classified reason it did not
+ */
+pub fn verify_board_detailed(
+    params: &Params<EqAffine>,
+    vk: &VerifyingKey<EqAffine>,
+    bundle: &ProofBundle,
+    witness: Option<&BoardCircuit<P128Pow5T3, Fp>>,
+) -> Result<(), VerifyError> {
+    match verify_board(params, vk, bundle) {
+        Ok(()) => return Ok(()),
+        Err(Error::InvalidInstances) => return Err(VerifyError::InstanceMismatch),
+        Err(_) => {}
+    };
+    let circuit = match witness {
+        Some(circuit) => circuit,
+        None => return Err(VerifyError::ProofMalformed),
+    };
+    if board_digest(circuit.board) != bundle.digest {
+        return Err(VerifyError::InstanceMismatch);
+    }
+    let instance = InstanceLayout::Board.assemble(|_| bundle.digest);
+    let prover = MockProver::run(BOARD_K, circuit, vec![instance])
+        .map_err(|_| VerifyError::ProofMalformed)?;
+    match prover.verify() {
+        Ok(()) => Err(VerifyError::ProofMalformed),
+        Err(failures) => Err(VerifyError::ConstraintUnsatisfied(failures)),
+    }
+}
+
+/**
+ * Player-facing reason a board failed the board circuit
+ * @dev ship is the index [0-4] of the ship in deck order
+ *
+ * @param DualOrientation - the ship has cells in both its horizontal and vertical commitment
+ * @param WrongLength - the ship does not occupy exactly its length in cells
+ * @param NotInLine - the ship's cells are not one unbroken line on the board (ex: it runs off the edge)
+ * @param Overlap - ships share a cell
+ * @param WrongTonnage - the board does not occupy exactly the fleet's cells (ex: ships overlap)
+ * @param CommitmentMismatch - the board does not hash to the public board commitment
+ * @param Unrecognized - a failure with no game meaning (the raw failure is kept for debugging)
+ */
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GameValidationError {
+    DualOrientation(usize),
+    WrongLength(Option<usize>),
+    NotInLine(Option<usize>),
+    Overlap,
+    WrongTonnage,
+    CommitmentMismatch,
+    Unrecognized(String),
+}
+
+impl fmt::Display for GameValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ship = |ship: &Option<usize>| match ship {
+            Some(ship) => format!("Ship {} ({})", ship, get_ship_name(*ship)),
+            None => String::from("A ship"),
+        };
+        match self {
+            GameValidationError::DualOrientation(index) => write!(
+                f,
+                "{} is placed both horizontally and vertically",
+                ship(&Some(*index))
+            ),
+            GameValidationError::WrongLength(index) => write!(
+                f,
+                "{} does not cover exactly its length in cells",
+                ship(index)
+            ),
+            GameValidationError::NotInLine(index) => write!(
+                f,
+                "{} is not a straight line on the board (it may extend off the board)",
+                ship(index)
+            ),
+            GameValidationError::Overlap => write!(f, "Ships overlap on the board"),
+            GameValidationError::WrongTonnage => write!(
+                f,
+                "Board does not cover exactly the {} cells of the fleet",
+                Deck::expected_tonnage()
+            ),
+            GameValidationError::CommitmentMismatch => {
+                write!(f, "Board does not match the public board commitment")
+            }
+            GameValidationError::Unrecognized(failure) => {
+                write!(f, "Board is invalid: {}", failure)
+            }
+        }
+    }
+}
+
+/**
+ * Ships of a fleet in deck order
+ */
+const FLEET: [ShipType; 5] = [
+    ShipType::Carrier,
+    ShipType::Battleship,
+    ShipType::Cruiser,
+    ShipType::Submarine,
+    ShipType::Destroyer,
+];
+
+/**
+ * Upper bound on the number of gates configured by any board circuit variant
+ */
+const MAX_BOARD_GATES: usize = 64;
+
+const PLACEMENT_GATE: &str = "running sum constraints";
+const TRANSPOSE_GATE: &str = "transpose row constraint";
+const TONNAGE_GATE: &str = "board tonnage constraint";
+const DUAL_ORIENTATION_GATE: &str = "Commitment orientation H OR V == 0 constraint";
+
+/**
+ * Board circuit constraints a player can break, as (gate name, index in gate, constraint name)
+ * @dev the dual orientation gate holds one constraint per ship in deck order
+ */
+const PLAYER_CONSTRAINTS: [(&str, usize, &str); 11] = [
+    (PLACEMENT_GATE, 0, "Placed ship of correct length"),
+    (PLACEMENT_GATE, 2, "One full horizontal bit window"),
+    (PLACEMENT_GATE, 3, "One full vertical bit window"),
+    (TRANSPOSE_GATE, 0, "Constrain trace value integrity"),
+    (TRANSPOSE_GATE, 1, "Constrain transposition of bit"),
+    (TONNAGE_GATE, 0, "Board occupies fleet tonnage"),
+    (DUAL_ORIENTATION_GATE, 0, "Aircraft Carrier H OR V == 0"),
+    (DUAL_ORIENTATION_GATE, 1, "Battleship H OR V == 0"),
+    (DUAL_ORIENTATION_GATE, 2, "Cruiser H OR V == 0"),
+    (DUAL_ORIENTATION_GATE, 3, "Submarine H OR V == 0"),
+    (DUAL_ORIENTATION_GATE, 4, "Destroyer H OR V == 0"),
+];
+
+/**
+ * Find the player constraint a constraint reported by the mock prover is
+ * @dev halo2 keeps constraint metadata private, so the failure is compared against each known
+ *      constraint at every gate index instead of being read back from its Display output
+ *
+ * @param constraint - constraint metadata from a VerifyFailure
+ * @return - the matching PLAYER_CONSTRAINTS entry, or None if a player cannot break it
+ */
+fn player_constraint(
+    constraint: &metadata::Constraint,
+) -> Option<(&'static str, usize, &'static str)> {
+    (0..MAX_BOARD_GATES).find_map(|gate| {
+        PLAYER_CONSTRAINTS
+            .iter()
+            .find(|(gate_name, index, name)| {
+                *constraint
+                    == metadata::Constraint::from((
+                        metadata::Gate::from((gate, *gate_name)),
+                        *index,
+                        *name,
+                    ))
+            })
+            .copied()
+    })
+}
+
+/**
+ * Return the ship whose placement a board circuit failure occurred in
+ * @dev the board chip synthesizes each ship's placement in its own placement_namespace
+ *
+ * @param layout - recorded layout of the circuit the failure was reported for
+ * @param location - location of the failure
+ * @return - ship index [0-4] in deck order, or None if not in a placement region
+ */
+fn placement_ship(layout: &LayoutRecorder<Fp>, location: &FailureLocation) -> Option<usize> {
+    let region = match location {
+        FailureLocation::InRegion { region, .. } => layout.region(region)?,
+        FailureLocation::OutsideRegion { .. } => return None,
+    };
+    FLEET
+        .iter()
+        .position(|ship_type| region.namespace.contains(&placement_namespace(*ship_type)))
+}
+
+/**
+ * Return the recorded layout of the board circuit
+ * @dev the layout does not depend on the witness, so it is recorded once from placeholder values
+ *
+ * @return - the layout, or None if the board circuit could not be laid out
+ */
+fn board_layout() -> Option<&'static LayoutRecorder<Fp>> {
+    static LAYOUT: OnceLock<Option<LayoutRecorder<Fp>>> = OnceLock::new();
+    LAYOUT
+        .get_or_init(|| {
+            let circuit = BoardCircuit::<P128Pow5T3, Fp>::new(
+                [BinaryValue::empty(); 10],
+                BinaryValue::empty(),
+            );
+            let mut meta = ConstraintSystem::<Fp>::default();
+            let config = BoardCircuit::<P128Pow5T3, Fp>::configure(&mut meta);
+            let constants = vec![config.fixed[0]];
+            let mut layout = LayoutRecorder::default();
+            layout.synthesize(&circuit, config, constants).ok()?;
+            Some(layout)
+        })
+        .as_ref()
+}
+
+/**
+ * Translate a board circuit failure into feedback a player can act on
+ *
+ * @param failure - a failure returned by mock proving the board circuit
+ * @return - the game rule the board breaks
+ */
+pub fn translate_failure(failure: &VerifyFailure) -> GameValidationError {
+    match board_layout() {
+        Some(layout) => translate_layout_failure(layout, failure),
+        None => GameValidationError::Unrecognized(failure.to_string()),
+    }
+}
+
+/**
+ * Translate a failure of any board circuit variant into feedback a player can act on
+ * @dev use for board chips configured with no touch or footprint checks, whose regions do not
+ *      line up with BoardCircuit's
+ *
+ * @param layout - recorded layout of the circuit (see LayoutRecorder::synthesize)
+ * @param failure - a failure returned by mock proving the circuit
+ * @return - the game rule the board breaks
+ */
+pub fn translate_layout_failure(
+    layout: &LayoutRecorder<Fp>,
+    failure: &VerifyFailure,
+) -> GameValidationError {
+    let (constraint, location) = match failure {
+        VerifyFailure::ConstraintNotSatisfied {
+            constraint,
+            location,
+            ..
+        } => (player_constraint(constraint), location),
+        VerifyFailure::Permutation { .. } => return GameValidationError::CommitmentMismatch,
+        _ => return GameValidationError::Unrecognized(failure.to_string()),
+    };
+    let ship = placement_ship(layout, location);
+    match constraint {
+        Some((DUAL_ORIENTATION_GATE, ship, _)) => GameValidationError::DualOrientation(ship),
+        Some((_, _, "Placed ship of correct length")) => GameValidationError::WrongLength(ship),
+        Some((_, _, "One full horizontal bit window" | "One full vertical bit window")) => {
+            GameValidationError::NotInLine(ship)
+        }
+        Some((_, _, "Constrain trace value integrity" | "Constrain transposition of bit")) => {
+            GameValidationError::Overlap
+        }
+        Some((_, _, "Board occupies fleet tonnage")) => GameValidationError::WrongTonnage,
+        _ => GameValidationError::Unrecognized(failure.to_string()),
+    }
+}
+
+/**
+ * Format the cell values of a failed constraint as "column=value" pairs in decimal
+ * @dev the mock prover prints values as hex field elements; values wider than 128 bits stay hex
+ *
+ * @param failure - a failure returned by mock proving a circuit
+ * @return - comma separated cell values, or an empty string if the failure carries none
+ */
+pub fn describe_cell_values(failure: &VerifyFailure) -> String {
+    let cell_values = match failure {
+        VerifyFailure::ConstraintNotSatisfied { cell_values, .. } => cell_values,
+        _ => return String::new(),
+    };
+    cell_values
+        .iter()
+        .map(|(cell, value)| {
+            let decimal = value
+                .strip_prefix("0x")
+                .and_then(|hex| u128::from_str_radix(hex, 16).ok())
+                .map_or(value.clone(), |value| value.to_string());
+            format!("{}={}", cell, decimal)
+        })
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/**
+ * Reason a circuit was rejected by mock_prove
+ *
+ * @param Synthesis - the circuit could not be synthesized (ex: it needs more than 2^k rows, see
+ *        BoardCircuit::min_k)
+ * @param ConstraintUnsatisfied - the circuit synthesized but breaks the listed constraints
+ */
+#[derive(Debug)]
+pub enum MockProveError {
+    Synthesis(Error),
+    ConstraintUnsatisfied(Vec<VerifyFailure>),
+}
+
+impl MockProveError {
+    /**
+     * Return the constraints the circuit failed
+     *
+     * @return - every failed constraint, or none if the circuit could not be synthesized
+     */
+    pub fn failures(&self) -> &[VerifyFailure] {
+        match self {
+            MockProveError::Synthesis(_) => &[],
+            MockProveError::ConstraintUnsatisfied(failures) => failures,
+        }
+    }
+}
+
+// halo2's Error does not implement PartialEq, so synthesis errors are compared by their Debug form
+impl PartialEq for MockProveError {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (MockProveError::Synthesis(a), MockProveError::Synthesis(b)) => {
+                format!("{:?}", a) == format!("{:?}", b)
+            }
+            (
+                MockProveError::ConstraintUnsatisfied(a),
+                MockProveError::ConstraintUnsatisfied(b),
+            ) => a == b,
+            _ => false,
+        }
+    }
+}
+
+/**
+ * Mock prove a circuit without importing halo2's dev tooling
+ *
+ * @param circuit - the circuit to mock prove, with witnesses assigned
+ * @param k - 2^k rows used in the circuit
+ * @param instances - public inputs for each instance column of the circuit
+ * @return - Ok if every constraint is satisfied, otherwise why the circuit was rejected
+ */
+pub fn mock_prove<C: Circuit<Fp>>(
+    circuit: &C,
+    k: u32,
+    instances: Vec<Vec<Fp>>,
+) -> Result<(), MockProveError> {
+    MockProver::run(k, circuit, instances)
+        .map_err(MockProveError::Synthesis)?
+        .verify()
+        .map_err(MockProveError::ConstraintUnsatisfied)
+}
+
+/**
+ * Bytes a proof and its public inputs take up, for budgeting calldata
+ *
+ * @param proof_bytes - length of the proof transcript
+ * @param instance_columns - number of instance columns the verifier is given
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ProofSize {
+    pub proof_bytes: usize,
+    pub instance_columns: usize,
+}
+
+/**
+ * Generate keys and a proof for a circuit and report the size of the proof
+ * @dev the constraint system does not expose its instance column count, so it is taken from the
+ *      instance passed in (create_proof rejects instance that does not match the circuit)
+ *
+ * @param params - IPA params generated for the circuit's number of rows
+ * @param circuit - the circuit to prove, with witnesses assigned
+ * @param instance - public inputs for each instance column of the circuit
+ * @return - size of the proof, or the error returned while generating keys or proving
+ */
+pub fn proof_size<C: Circuit<Fp>>(
+    params: &Params<EqAffine>,
+    circuit: C,
+    instance: &[&[Fp]],
+) -> Result<ProofSize, Error> {
+    let vk = keygen_vk(params, &circuit)?;
+    let pk = keygen_pk(params, vk, &circuit)?;
+    let mut transcript = Blake2bWrite::<_, EqAffine, Challenge255<_>>::init(vec![]);
+    create_proof(params, &pk, &[circuit], &[instance], OsRng, &mut transcript)?;
+    Ok(ProofSize {
+        proof_bytes: transcript.finalize().len(),
+        instance_columns: instance.len(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        crate::{
+            test_utils::{assert_valid, empirical_min_k},
+            utils::{
+                board::{adversarial, random_valid_fleet},
+                deck::Deck,
+                ship::WitnessOption,
+                shot::serialize,
+            },
+        },
+        halo2_proofs::plonk::Any,
+        proptest::test_runner::{RngAlgorithm, TestRng},
+        std::{collections::HashMap, sync::OnceLock},
+    };
+
+    /**
+     * Params and proving key shared across tests (keygen is slow)
+     */
+    fn setup() -> &'static (Params<EqAffine>, ProvingKey<EqAffine>) {
+        static SETUP: OnceLock<(Params<EqAffine>, ProvingKey<EqAffine>)> = OnceLock::new();
+        SETUP.get_or_init(|| {
+            let params = Params::<EqAffine>::new(BOARD_K);
+            let pk = board_keys(&params).unwrap();
+            (params, pk)
+        })
+    }
+
+    #[test]
+    #[ignore] // slow: run with `cargo test -- --ignored`
+    fn board_digest_no_collisions() {
+        // deterministic seed so any collision found is reproducible
+        let mut rng = TestRng::deterministic_rng(RngAlgorithm::ChaCha);
+        let mut digests = HashMap::<[u8; 32], u128>::new();
+        for _ in 0..10000 {
+            let state = Board::from(&random_valid_fleet(&mut rng)).state(DEFAULT_WITNESS_OPTIONS);
+            let digest = board_digest(state).to_repr();
+            // distinct boards must never share a digest (the same board may be sampled twice)
+            if let Some(existing) = digests.insert(digest, state.lower_u128()) {
+                assert_eq!(existing, state.lower_u128());
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "progress")]
+    fn progress_phases() {
+        let (params, pk) = setup();
+        let board = Board::from(&Deck::default());
+        let mut phases = vec![];
+        let bundle =
+            prove_board_with_progress(params, pk, &board, |phase| phases.push(phase)).unwrap();
+        assert_eq!(
+            phases,
+            vec![
+                ProvePhase::Witness,
+                ProvePhase::Proving,
+                ProvePhase::Finalizing,
+                ProvePhase::Done
+            ]
+        );
+        assert!(verify_board(params, pk.get_vk(), &bundle).is_ok());
+    }
+
+    #[test]
+    fn bundle_round_trip() {
+        let (params, pk) = setup();
+        let board = Board::from(&Deck::from([
+            Some((3, 3, true)),
+            Some((5, 4, false)),
+            Some((0, 1, false)),
+            Some((0, 5, true)),
+            Some((6, 1, false)),
+        ]));
+        let bundle = prove_board(params, pk, &board).unwrap();
+        assert_eq!(
+            bundle.digest,
+            board_digest(board.state(DEFAULT_WITNESS_OPTIONS))
+        );
+        // bundle survives serialization and still verifies
+        let bundle = ProofBundle::from_bytes(&bundle.to_bytes()).unwrap();
+        assert!(verify_board(params, pk.get_vk(), &bundle).is_ok());
+        // tampering with the digest breaks verification
+        let mut tampered = bundle.clone();
+        tampered.digest += Fp::one();
+        assert!(verify_board(params, pk.get_vk(), &tampered).is_err());
+        // bundles claiming a different circuit size are rejected
+        let mut tampered = bundle;
+        tampered.k = BOARD_K + 1;
+        assert!(verify_board(params, pk.get_vk(), &tampered).is_err());
+    }
+
+    #[test]
+    fn verify_board_detailed_classes() {
+        let (params, pk) = setup();
+        let board = Board::from(&Deck::default());
+        let circuit = BoardCircuit::<P128Pow5T3, Fp>::new(
+            board.witness(DEFAULT_WITNESS_OPTIONS),
+            board.state(DEFAULT_WITNESS_OPTIONS),
+        );
+        let bundle = prove_board(params, pk, &board).unwrap();
+        let verify = |bundle: &ProofBundle, witness: Option<&BoardCircuit<P128Pow5T3, Fp>>| {
+            verify_board_detailed(params, pk.get_vk(), bundle, witness)
+        };
+        assert_eq!(verify(&bundle, Some(&circuit)), Ok(()));
+        // digest does not commit to the witness
+        let mut tampered = bundle.clone();
+        tampered.digest += Fp::one();
+        assert_eq!(
+            verify(&tampered, Some(&circuit)),
+            Err(VerifyError::InstanceMismatch)
+        );
+        // bundle made for a different circuit size
+        let mut tampered = bundle.clone();
+        tampered.k = BOARD_K + 1;
+        assert_eq!(verify(&tampered, None), Err(VerifyError::InstanceMismatch));
+        // corrupted proof bytes for an honest witness
+        let mut tampered = bundle;
+        tampered.proof[64] ^= 1;
+        assert_eq!(
+            verify(&tampered, Some(&circuit)),
+            Err(VerifyError::ProofMalformed)
+        );
+        assert_eq!(verify(&tampered, None), Err(VerifyError::ProofMalformed));
+        // proof made with a witness that breaks the board rules
+        let board = Board::from(&adversarial::overlapping_fleet());
+        let circuit = BoardCircuit::<P128Pow5T3, Fp>::new(
+            board.witness(DEFAULT_WITNESS_OPTIONS),
+            board.state(DEFAULT_WITNESS_OPTIONS),
+        );
+        let bundle = prove_board(params, pk, &board).unwrap();
+        assert!(matches!(
+            verify(&bundle, Some(&circuit)),
+            Err(VerifyError::ConstraintUnsatisfied(failures)) if !failures.is_empty()
+        ));
+    }
+
+    #[test]
+    fn board_proof_size() {
+        let (params, _) = setup();
+        let board = Board::from(&Deck::default());
+        let state = board.state(DEFAULT_WITNESS_OPTIONS);
+        let circuit =
+            BoardCircuit::<P128Pow5T3, Fp>::new(board.witness(DEFAULT_WITNESS_OPTIONS), state);
+        let size = proof_size(params, circuit, &[&[board_digest(state)]]).unwrap();
+        assert_eq!(size.instance_columns, 1);
+        // baseline: 3904 bytes for IPA over pasta at k = 12
+        assert!((3500..4500).contains(&size.proof_bytes));
+    }
+
+    #[test]
+    fn bundle_from_bytes_unknown_circuit() {
+        let bundle = ProofBundle {
+            proof: vec![1, 2, 3],
+            digest: Fp::from(7),
+            k: BOARD_K,
+            circuit_id: BOARD_CIRCUIT_ID,
+        };
+        let mut bytes = bundle.to_bytes();
+        assert_eq!(ProofBundle::from_bytes(&bytes), Ok(bundle));
+        // corrupt the first byte of the circuit id
+        bytes[37] ^= 0xff;
+        assert_eq!(
+            ProofBundle::from_bytes(&bytes),
+            Err("Bundle circuit id is unknown!")
+        );
+    }
+
+    #[test]
+    fn aggregate_ship_commitments_matches_board_circuit() {
+        // public [H, V] instance of each ship's placement proof
+        let ship_instances = |witness: [BinaryValue; 10]| -> [[Fp; 2]; 5] {
+            core::array::from_fn(|i| {
+                [witness[i * 2], witness[i * 2 + 1]].map(|value| Fp::from_u128(value.lower_u128()))
+            })
+        };
+        let board = Board::from(&Deck::from([
+            Some((3, 3, true)),
+            Some((5, 4, false)),
+            Some((0, 1, false)),
+            Some((0, 5, true)),
+            Some((6, 1, false)),
+        ]));
+        let witness = board.witness(DEFAULT_WITNESS_OPTIONS);
+        let digest = aggregate_ship_commitments(&ship_instances(witness)).unwrap();
+        // the board circuit exports the same digest in-circuit
+        let circuit =
+            BoardCircuit::<P128Pow5T3, Fp>::new(witness, board.state(DEFAULT_WITNESS_OPTIONS));
+        let instance = InstanceLayout::Board.assemble(|_| digest);
+        let prover = MockProver::run(BOARD_K, &circuit, vec![instance]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+        // ships that overlap never compose to a board
+        let witness =
+            Board::from(&adversarial::overlapping_fleet()).witness(DEFAULT_WITNESS_OPTIONS);
+        assert!(aggregate_ship_commitments(&ship_instances(witness)).is_err());
+    }
+
+    #[test]
+    fn required_params_k_covers_each_proof() {
+        let k = required_params_k(&[ProofKind::Board, ProofKind::Shot]).unwrap();
+        assert_eq!(
+            k,
+            ProofKind::Board
+                .min_k()
+                .unwrap()
+                .max(ProofKind::Shot.min_k().unwrap())
+        );
+        assert_eq!(
+            required_params_k(&[ProofKind::Board]).unwrap(),
+            ProofKind::Board.min_k().unwrap()
+        );
+        assert_eq!(required_params_k(&[]).unwrap(), 0);
+        let board = Board::from(&Deck::from([
+            Some((3, 3, true)),
+            Some((5, 4, false)),
+            Some((0, 1, false)),
+            Some((0, 5, true)),
+            Some((6, 1, false)),
+        ]));
+        let state = board.state(DEFAULT_WITNESS_OPTIONS);
+        let digest = board_digest(state);
+        let board_circuit =
+            BoardCircuit::<P128Pow5T3, Fp>::new(board.witness(DEFAULT_WITNESS_OPTIONS), state);
+        let board_instance = vec![InstanceLayout::Board.assemble(|_| digest)];
+        // shot at (3, 5) hits the vertical carrier
+        let shot = serialize::<1>([3], [5]);
+        let nonce = Fp::from(7);
+        let shot_circuit =
+            ShotCircuit::<P128Pow5T3, Fp>::new(state, shot, BinaryValue::from_u8(1), nonce);
+        let shot_instance = vec![vec![
+            digest,
+            Fp::from_u128(shot.lower_u128()),
+            Fp::one(),
+            nonce,
+        ]];
+        // the shared k proves every circuit, and each proof alone needs exactly its own min_k
+        assert_valid(&board_circuit, k, board_instance.clone());
+        assert_valid(&shot_circuit, k, shot_instance.clone());
+        assert_eq!(
+            required_params_k(&[ProofKind::Board]).unwrap(),
+            empirical_min_k(&board_circuit, board_instance)
+        );
+        assert_eq!(
+            required_params_k(&[ProofKind::Shot]).unwrap(),
+            empirical_min_k(&shot_circuit, shot_instance)
+        );
+    }
+
+    #[test]
+    fn mock_prove_failures() {
+        let board = Board::from(&Deck::default());
+        let circuit = BoardCircuit::<P128Pow5T3, Fp>::new(
+            board.witness(DEFAULT_WITNESS_OPTIONS),
+            board.state(DEFAULT_WITNESS_OPTIONS),
+        );
+        let digest = board_digest(board.state(DEFAULT_WITNESS_OPTIONS));
+        assert_eq!(mock_prove(&circuit, BOARD_K, vec![vec![digest]]), Ok(()));
+        let error = mock_prove(&circuit, BOARD_K, vec![vec![digest + Fp::one()]]).unwrap_err();
+        assert!(matches!(error, MockProveError::ConstraintUnsatisfied(_)));
+        assert!(error
+            .failures()
+            .iter()
+            .all(|failure| matches!(failure, VerifyFailure::Permutation { .. })));
+        // a circuit that does not fit in 2^k rows is returned as an error instead of panicking
+        let error = mock_prove(&circuit, BOARD_K - 1, vec![vec![digest]]).unwrap_err();
+        assert!(matches!(error, MockProveError::Synthesis(_)));
+        assert!(error.failures().is_empty());
+    }
+
+    /**
+     * Build a constraint failure as reported by the mock prover for the board circuit
+     *
+     * @param gate - (index, name) of the gate the constraint belongs to
+     * @param constraint - (index in gate, name) of the failed constraint
+     * @param region - (index, name) of the region the constraint failed in
+     * @return - the failure with no cell values
+     */
+    fn constraint_failure(
+        gate: (usize, &'static str),
+        constraint: (usize, &'static str),
+        region: (usize, &str),
+    ) -> VerifyFailure {
+        VerifyFailure::ConstraintNotSatisfied {
+            constraint: (gate.into(), constraint.0, constraint.1).into(),
+            location: FailureLocation::InRegion {
+                region: region.into(),
+                offset: 0,
+            },
+            cell_values: vec![],
+        }
+    }
+
+    #[test]
+    fn translate_known_constraints() {
+        let placement = |constraint, region| {
+            constraint_failure(
+                (15, "running sum constraints"),
+                constraint,
+                (region, "constrain running sum output"),
+            )
+        };
+        let cases = [
+            (
+                constraint_failure(
+                    (40, "Commitment orientation H OR V == 0 constraint"),
+                    (2, "Cruiser H OR V == 0"),
+                    (0, "load ship placements"),
+                ),
+                GameValidationError::DualOrientation(2),
+            ),
+            (
+                placement((0, "Placed ship of correct length"), 16),
+                GameValidationError::WrongLength(Some(1)),
+            ),
+            (
+                placement((2, "One full horizontal bit window"), 19),
+                GameValidationError::NotInLine(Some(2)),
+            ),
+            (
+                placement((3, "One full vertical bit window"), 13),
+                GameValidationError::NotInLine(Some(0)),
+            ),
+            (
+                constraint_failure(
+                    (36, "transpose row constraint"),
+                    (0, "Constrain trace value integrity"),
+                    (26, "Transpose ship commitments"),
+                ),
+                GameValidationError::Overlap,
+            ),
+            (
+                constraint_failure(
+                    (36, "transpose row constraint"),
+                    (1, "Constrain transposition of bit"),
+                    (26, "Transpose ship commitments"),
+                ),
+                GameValidationError::Overlap,
+            ),
+            (
+                constraint_failure(
+                    (42, "board tonnage constraint"),
+                    (0, "Board occupies fleet tonnage"),
+                    (32, "constrain board tonnage"),
+                ),
+                GameValidationError::WrongTonnage,
+            ),
+            (
+                VerifyFailure::Permutation {
+                    column: (Any::Instance, 0).into(),
+                    location: FailureLocation::OutsideRegion { row: 0 },
+                },
+                GameValidationError::CommitmentMismatch,
+            ),
+        ];
+        for (failure, expected) in cases {
+            assert_eq!(translate_failure(&failure), expected, "{}", failure);
+        }
+        // a region outside every ship's placement namespace names no ship
+        assert_eq!(
+            translate_failure(&constraint_failure(
+                (15, "running sum constraints"),
+                (0, "Placed ship of correct length"),
+                (26, "Transpose ship commitments"),
+            )),
+            GameValidationError::WrongLength(None)
+        );
+        let unknown = constraint_failure((0, "num2bits"), (0, ""), (1, "num2bits"));
+        assert_eq!(
+            translate_failure(&unknown),
+            GameValidationError::Unrecognized(unknown.to_string())
+        );
+        assert_eq!(
+            GameValidationError::NotInLine(Some(2)).to_string(),
+            "Ship 2 (Cruiser) is not a straight line on the board (it may extend off the board)"
+        );
+    }
+
+    #[test]
+    fn translate_board_failures() {
+        let board = Board::from(&Deck::from([
+            Some((3, 3, true)),
+            Some((5, 4, false)),
+            Some((0, 1, false)),
+            Some((0, 5, true)),
+            Some((6, 1, false)),
+        ]));
+        let options = [
+            WitnessOption::Default,
+            WitnessOption::Oversized,
+            WitnessOption::Default,
+            WitnessOption::Default,
+            WitnessOption::Default,
+        ];
+        let circuit =
+            BoardCircuit::<P128Pow5T3, Fp>::new(board.witness(options), board.state(options));
+        let digest = board_digest(board.state(options));
+        let error = mock_prove(&circuit, BOARD_K, vec![vec![digest]]).unwrap_err();
+        let errors = error
+            .failures()
+            .iter()
+            .map(translate_failure)
+            .collect::<Vec<_>>();
+        // the extra battleship cell also breaks the fleet tonnage
+        assert_eq!(
+            errors,
+            vec![
+                GameValidationError::WrongLength(Some(1)),
+                GameValidationError::NotInLine(Some(1)),
+                GameValidationError::WrongTonnage,
+            ]
+        );
+    }
+
+    #[test]
+    fn describe_board_failure_cell_values() {
+        let board = Board::from(&Deck::from([
+            Some((3, 3, true)),
+            Some((5, 4, false)),
+            Some((0, 1, false)),
+            Some((0, 5, true)),
+            Some((6, 1, false)),
+        ]));
+        let options = [
+            WitnessOption::Default,
+            WitnessOption::Oversized,
+            WitnessOption::Default,
+            WitnessOption::Default,
+            WitnessOption::Default,
+        ];
+        let circuit =
+            BoardCircuit::<P128Pow5T3, Fp>::new(board.witness(options), board.state(options));
+        let digest = board_digest(board.state(options));
+        let error = mock_prove(&circuit, BOARD_K, vec![vec![digest]]).unwrap_err();
+        let failures = error.failures();
+        // battleship length and fleet tonnage print in decimal rather than as hex field elements
+        assert_eq!(
+            failures
+                .iter()
+                .map(describe_cell_values)
+                .collect::<Vec<_>>(),
+            vec![
+                "Column('Advice', 1)@0=5",
+                "Column('Advice', 0)@0=0, Column('Advice', 2)@0=2",
+                "Column('Advice', 1)@0=18",
+            ]
+        );
+        let permutation = VerifyFailure::Permutation {
+            column: (Any::Instance, 0).into(),
+            location: FailureLocation::OutsideRegion { row: 0 },
+        };
+        assert_eq!(describe_cell_values(&permutation), "");
+    }
+}
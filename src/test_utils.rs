@@ -0,0 +1,87 @@
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    dev::{MockProver, VerifyFailure},
+    plonk::{Circuit, Error},
+};
+
+/**
+ * Mock prove a circuit and assert every constraint is satisfied
+ *
+ * @param circuit - the circuit to mock prove
+ * @param k - 2^k rows used in the circuit
+ * @param instance - public instance column values
+ */
+pub fn assert_valid<F: FieldExt, C: Circuit<F>>(circuit: &C, k: u32, instance: Vec<Vec<F>>) {
+    let prover = MockProver::run(k, circuit, instance).unwrap();
+    assert_eq!(prover.verify(), Ok(()));
+}
+
+/**
+ * Mock prove a circuit and assert it is satisfied only by the expected instance values
+ * @dev fails if no instance values are supplied, or if changing any supplied value still verifies
+ *      (the value is not constrained by the circuit, so the test was not checking it)
+ *
+ * @param circuit - the circuit to mock prove
+ * @param k - 2^k rows used in the circuit
+ * @param instances - expected public instance column values (must not be empty)
+ */
+pub fn assert_valid_with_instances<F: FieldExt, C: Circuit<F>>(
+    circuit: &C,
+    k: u32,
+    instances: Vec<Vec<F>>,
+) {
+    assert!(
+        instances.iter().any(|column| !column.is_empty()),
+        "no instance values supplied to a circuit that exports public values"
+    );
+    assert_valid(circuit, k, instances.clone());
+    for (column, values) in instances.iter().enumerate() {
+        for row in 0..values.len() {
+            let mut wrong = instances.clone();
+            wrong[column][row] += F::one();
+            let prover = MockProver::run(k, circuit, wrong).unwrap();
+            assert!(
+                prover.verify().is_err(),
+                "instance column {} row {} is not constrained by the circuit",
+                column,
+                row
+            );
+        }
+    }
+}
+
+/**
+ * Mock prove a circuit and assert it fails with exactly the expected failures
+ *
+ * @param circuit - the circuit to mock prove
+ * @param k - 2^k rows used in the circuit
+ * @param instance - public instance column values
+ * @param expected - every failure the mock prover is expected to report, in order
+ */
+pub fn assert_fails_with<F: FieldExt, C: Circuit<F>>(
+    circuit: &C,
+    k: u32,
+    instance: Vec<Vec<F>>,
+    expected: Vec<VerifyFailure>,
+) {
+    let prover = MockProver::run(k, circuit, instance).unwrap();
+    assert_eq!(prover.verify(), Err(expected));
+}
+
+/**
+ * Return the smallest k a circuit can be mock proven with, found by trying increasing k
+ * @dev constraints are not checked, so the witness only needs to be assignable
+ *
+ * @param circuit - the circuit to mock prove
+ * @param instance - public instance column values
+ */
+pub fn empirical_min_k<F: FieldExt, C: Circuit<F>>(circuit: &C, instance: Vec<Vec<F>>) -> u32 {
+    (1..)
+        .find(|k| {
+            !matches!(
+                MockProver::run(*k, circuit, instance.clone()),
+                Err(Error::NotEnoughRowsAvailable { .. } | Error::InstanceTooLarge)
+            )
+        })
+        .unwrap()
+}
@@ -1,6 +1,105 @@
 use super::board::BOARD_SIZE;
 
-use {bitvec::prelude::*, halo2_proofs::arithmetic::FieldExt};
+use {
+    bitvec::prelude::*,
+    halo2_proofs::arithmetic::FieldExt,
+    std::{
+        any::{Any, TypeId},
+        collections::HashMap,
+        ops::{BitAnd, BitOr, BitXor},
+        sync::{Mutex, OnceLock},
+    },
+};
+
+/**
+ * Return the powers of two [2^0, 2^1, .., 2^255] on a given prime field
+ * @dev computed once per field and leaked so every later call is a lookup
+ *
+ * @param F - the prime field to compute powers of two in
+ * @return - table where index i stores 2^i
+ */
+pub fn pow2_table<F: FieldExt>() -> &'static [F; 256] {
+    static TABLES: OnceLock<Mutex<HashMap<TypeId, &'static (dyn Any + Send + Sync)>>> =
+        OnceLock::new();
+    let mut tables = TABLES.get_or_init(Default::default).lock().unwrap();
+    let table = *tables.entry(TypeId::of::<F>()).or_insert_with(|| {
+        let mut table = [F::one(); 256];
+        for i in 1..256 {
+            table[i] = table[i - 1].double();
+        }
+        Box::leak(Box::new(table))
+    });
+    table.downcast_ref::<[F; 256]>().unwrap()
+}
+
+pub const PACKED_BYTES: usize = BOARD_SIZE.div_ceil(8); // bytes needed to pack every board bit
+
+/**
+ * Error returned when packed board bytes cannot be unpacked
+ *
+ * @param Checksum - the checksum stored with the bytes does not match the bytes
+ * @param Padding - a bit past BOARD_SIZE is set in the last packed byte
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PackError {
+    Checksum { expected: u8, computed: u8 },
+    Padding,
+}
+
+/**
+ * Error returned when a string cannot be parsed into a BinaryValue
+ *
+ * @param Empty - the string has no digits (after any 0x prefix)
+ * @param InvalidDigit - the character at byte index is not a digit in the parsed radix
+ * @param Overflow - the number does not fit in 256 bits
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    Empty,
+    InvalidDigit { index: usize },
+    Overflow,
+}
+
+/**
+ * Parse digits of a radix into 256 bit little endian limbs
+ *
+ * @param digits - the digits to parse, most significant first
+ * @param radix - 10 or 16
+ * @param offset - byte index of the first digit in the original string (for error reporting)
+ * @return - the parsed value, or the first reason the digits were rejected
+ */
+fn parse_limbs(digits: &str, radix: u32, offset: usize) -> Result<BinaryValue, ParseError> {
+    if digits.is_empty() {
+        return Err(ParseError::Empty);
+    }
+    let mut limbs = [0u64; 4];
+    for (index, character) in digits.char_indices() {
+        let digit = character.to_digit(radix).ok_or(ParseError::InvalidDigit {
+            index: offset + index,
+        })?;
+        // limbs = limbs * radix + digit, carrying from the least significant limb up
+        let mut carry = digit as u128;
+        for limb in limbs.iter_mut() {
+            let product = *limb as u128 * radix as u128 + carry;
+            *limb = product as u64;
+            carry = product >> 64;
+        }
+        if carry != 0 {
+            return Err(ParseError::Overflow);
+        }
+    }
+    Ok(BinaryValue::new(U256::new(limbs)))
+}
+
+/**
+ * Compute the checksum of packed board bytes
+ *
+ * @param packed - packed board bytes
+ * @return - XOR of every byte
+ */
+fn packed_checksum(packed: &[u8; PACKED_BYTES]) -> u8 {
+    packed.iter().fold(0, |checksum, byte| checksum ^ byte)
+}
 
 /**
  * Binary element with converstion functionality
@@ -32,6 +131,7 @@ impl BinaryValue {
     }
 
     // returns the u128 from first half of U256 in LE
+    // truncates: bits 128..256 are silently dropped (see checked_lower_u128)
     pub fn lower_u128(self) -> u128 {
         u128::from_le_bytes(
             self.value.into_inner()[0..2]
@@ -44,8 +144,123 @@ impl BinaryValue {
         )
     }
 
+    /**
+     * Return the lower 128 bits only if no information is lost doing so
+     *
+     * @return - the same value as lower_u128, or None if any bit >= 128 is set
+     */
+    pub fn checked_lower_u128(self) -> Option<u128> {
+        match self.value[128..].any() {
+            true => None,
+            false => Some(self.lower_u128()),
+        }
+    }
+
+    /**
+     * Serialize the value in the little endian byte encoding of a field element's repr
+     * @dev Fp::from_repr(value.to_field_bytes()) is Fp::from_u128(value.lower_u128()) for any
+     *      value below 2^128 (ex: a board state), so the bytes match the circuit's preimage
+     *
+     * @return - 32 bytes in little endian order
+     */
+    pub fn to_field_bytes(self) -> [u8; 32] {
+        self.value
+            .into_inner()
+            .iter()
+            .flat_map(|limb| limb.to_le_bytes())
+            .collect::<Vec<u8>>()
+            .try_into()
+            .unwrap()
+    }
+
+    /**
+     * Construct a binary value from the little endian byte encoding of a field element's repr
+     * @dev inverse of to_field_bytes, ex: BinaryValue::from_field_bytes(fp.to_repr())
+     *
+     * @param bytes - 32 bytes in little endian order
+     * @return - BinaryValue holding the same 256 bits
+     */
+    pub fn from_field_bytes(bytes: [u8; 32]) -> BinaryValue {
+        let mut limbs = [0u64; 4];
+        for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks(8)) {
+            *limb = u64::from_le_bytes(chunk.try_into().unwrap());
+        }
+        BinaryValue::new(U256::new(limbs))
+    }
+
+    /**
+     * Pack the board bits into the smallest whole number of bytes for off-circuit storage
+     * @dev only the lower BOARD_SIZE bits are packed (little endian); the 4 high bits of the last
+     *      byte are 0. the checksum is the XOR of all packed bytes, catching any single corrupted byte
+     *
+     * @return - (13 packed bytes, checksum byte)
+     */
+    pub fn to_packed(&self) -> ([u8; PACKED_BYTES], u8) {
+        let mut packed = [0u8; PACKED_BYTES];
+        let bits = packed.view_bits_mut::<Lsb0>();
+        for i in self.value[0..BOARD_SIZE].iter_ones() {
+            bits.set(i, true);
+        }
+        (packed, packed_checksum(&packed))
+    }
+
+    /**
+     * Unpack board bits stored with to_packed
+     *
+     * @param packed - 13 packed bytes in little endian order
+     * @param checksum - XOR of the packed bytes recorded when packing
+     * @return - BinaryValue holding the board bits, or why the packed bytes were rejected
+     */
+    pub fn from_packed(packed: [u8; PACKED_BYTES], checksum: u8) -> Result<BinaryValue, PackError> {
+        let computed = packed_checksum(&packed);
+        if computed != checksum {
+            return Err(PackError::Checksum {
+                expected: checksum,
+                computed,
+            });
+        }
+        let bits = packed.view_bits::<Lsb0>();
+        if bits[BOARD_SIZE..].any() {
+            return Err(PackError::Padding);
+        }
+        let mut value = U256::ZERO;
+        for i in bits.iter_ones() {
+            value.set(i, true);
+        }
+        Ok(BinaryValue::new(value))
+    }
+
+    /**
+     * Parse a hexadecimal string received from a client (ex: a board state sent over the wire)
+     * @dev accepts an optional 0x prefix and either letter case. leading zeros are allowed as long
+     *      as the value fits in 256 bits; signs and whitespace are rejected
+     *
+     * @param hex - the hexadecimal string to parse
+     * @return - BinaryValue holding the parsed number, or why the string was rejected
+     */
+    pub fn from_hex(hex: &str) -> Result<BinaryValue, ParseError> {
+        match hex.strip_prefix("0x") {
+            Some(digits) => parse_limbs(digits, 16, 2),
+            None => parse_limbs(hex, 16, 0),
+        }
+    }
+
+    /**
+     * Parse a decimal string received from a client (ex: a field element printed by a contract)
+     * @dev leading zeros are allowed as long as the value fits in 256 bits; signs and whitespace
+     *      are rejected
+     *
+     * @param decimal - the decimal string to parse
+     * @return - BinaryValue holding the parsed number, or why the string was rejected
+     */
+    pub fn from_decimal_str(decimal: &str) -> Result<BinaryValue, ParseError> {
+        parse_limbs(decimal, 10, 0)
+    }
+
     /**
      * Convert the binary value to an array of bits on a given prime field
+     * @dev little endian (index i is bit i): the order num2bits, the placement gadget and the
+     *      board commitment all use
      *
      * @param F: the prime field to wrap bits in
      * @param S: the number of bits in the field
@@ -60,6 +275,54 @@ impl BinaryValue {
             .unwrap()
     }
 
+    /**
+     * Convert the lower S bits of the binary value to big endian bits on a given prime field
+     * @dev index 0 is bit S - 1, for interop with schemes that chunk bits most significant first.
+     *      not consumed by any circuit in this crate (see bitfield)
+     *
+     * @param F: the prime field to wrap bits in
+     * @param S: the number of bits in the field
+     * @return - bitfield::<F, S>() in reverse order
+     */
+    pub fn bitfield_be<F: FieldExt, const S: usize>(self) -> [F; S] {
+        let mut bits = self.bitfield::<F, S>();
+        bits.reverse();
+        bits
+    }
+
+    /**
+     * Construct a board-shaped BinaryValue from a 10x10 grid
+     * @dev row-major: grid[y][x] lands at bit y * 10 + x (Lsb0, so bit 0 is cell (0, 0))
+     *
+     * @param grid - 10 rows of 10 cells where true means the cell is set
+     * @return - BinaryValue with the corresponding board bits set
+     */
+    pub fn from_grid(grid: &[[bool; 10]; 10]) -> BinaryValue {
+        let mut value = U256::ZERO;
+        for (y, row) in grid.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                value.set(y * 10 + x, *cell);
+            }
+        }
+        BinaryValue::new(value)
+    }
+
+    /**
+     * Export the first 100 bits as a 10x10 grid
+     * @dev inverse of from_grid: grid[y][x] is bit y * 10 + x
+     *
+     * @return - 10 rows of 10 cells where true means the cell is set
+     */
+    pub fn to_grid(self) -> [[bool; 10]; 10] {
+        let mut grid = [[false; 10]; 10];
+        for (y, row) in grid.iter_mut().enumerate() {
+            for (x, cell) in row.iter_mut().enumerate() {
+                *cell = self.value[y * 10 + x];
+            }
+        }
+        grid
+    }
+
     /**
      * Zip together bits
      * @dev fails if both bits set when trying to zip
@@ -68,15 +331,548 @@ impl BinaryValue {
      * @return - BinaryValue where bits are zipped from two inputs
      */
     pub fn zip(self, to: BinaryValue) -> BinaryValue {
+        match self.zip_checked(to) {
+            Ok(zipped) => zipped,
+            Err(i) => panic!("Cannot zip together bit #{}", i),
+        }
+    }
+
+    /**
+     * Zip together bits without aborting on a collision
+     * @dev scans all 100 bits regardless of if/ where a collision occurs so work is constant
+     *
+     * @param to - the BinaryValue to zip with this value
+     * @return - BinaryValue where bits are zipped from two inputs, or index of first bit set in both
+     */
+    pub fn zip_checked(self, to: BinaryValue) -> Result<BinaryValue, usize> {
         let mut zipped = U256::ZERO;
+        // BOARD_SIZE used as sentinel for "no collision found"
+        let mut conflict = BOARD_SIZE;
         for i in 0..BOARD_SIZE {
             // only zip 100 bits
-            if self.value[i] && to.value[i] {
-                panic!("Cannot zip together bit #{}", i);
+            let collision = self.value[i] & to.value[i];
+            // record only the first collision without branching on bit values
+            let first = (collision & (conflict == BOARD_SIZE)) as usize;
+            conflict = conflict * (1 - first) + i * first;
+            zipped.set(i, self.value[i] | to.value[i]);
+        }
+        if conflict == BOARD_SIZE {
+            Ok(BinaryValue::new(zipped))
+        } else {
+            Err(conflict)
+        }
+    }
+
+    /**
+     * Check the value is a board holding exactly the given fleet
+     * @dev the pure analog of the board circuit: set bits must split into disjoint horizontal or
+     *      vertical runs, one per fleet length, with no bit left over. ships may touch
+     *
+     * @param fleet_lengths - length of every ship in the fleet (ex: [5, 4, 3, 3, 2])
+     * @return - true if the set bits are covered exactly by the fleet placed on the board
+     */
+    pub fn is_legal_board(&self, fleet_lengths: &[usize]) -> bool {
+        if self.value[BOARD_SIZE..].any()
+            || self.value.count_ones() != fleet_lengths.iter().sum::<usize>()
+        {
+            return false;
+        }
+        let mut occupied = [false; BOARD_SIZE];
+        for i in self.value.iter_ones() {
+            occupied[i] = true;
+        }
+        let mut lengths = fleet_lengths.to_vec();
+        lengths.sort_unstable();
+        cover_runs(&mut occupied, &mut lengths)
+    }
+
+    /**
+     * List the board cells that differ between two board states
+     * @dev built on XOR, so only the lower BOARD_SIZE bits are compared
+     *
+     * @param other - the board state to compare against (ex: the state after a transition)
+     * @return - (x, y, value in other) for every differing cell, in ascending cell order
+     */
+    pub fn diff(&self, other: &BinaryValue) -> Vec<(usize, usize, bool)> {
+        (*self ^ *other)
+            .value
+            .iter_ones()
+            .map(|cell| (cell % 10, cell / 10, other.value[cell]))
+            .collect()
+    }
+}
+
+/**
+ * Search for a way to cover the occupied cells with one straight run per remaining length
+ * @dev the lowest occupied cell must start a run in either orientation
+ *
+ * @param occupied - cells not yet covered by a run
+ * @param lengths - sorted lengths of the runs not yet placed
+ * @return - true if the remaining runs exactly cover the occupied cells
+ */
+fn cover_runs(occupied: &mut [bool; BOARD_SIZE], lengths: &mut Vec<usize>) -> bool {
+    let head = match occupied.iter().position(|cell| *cell) {
+        Some(head) => head,
+        None => return lengths.is_empty(),
+    };
+    for i in 0..lengths.len() {
+        // equal lengths are interchangeable so only try the first of each
+        if i > 0 && lengths[i] == lengths[i - 1] {
+            continue;
+        }
+        let length = lengths[i];
+        for step in [1, 10] {
+            // a run of 1 is the same cell in both orientations
+            if length == 1 && step == 10 {
+                continue;
+            }
+            let fits = match step {
+                1 => head % 10 + length <= 10,
+                _ => head / 10 + length <= 10,
             };
-            let bit = self.value[i] || to.value[i];
-            zipped.set(i, bit);
+            let cells = (0..length).map(|j| head + j * step).collect::<Vec<usize>>();
+            if !fits || !cells.iter().all(|cell| occupied[*cell]) {
+                continue;
+            }
+            cells.iter().for_each(|cell| occupied[*cell] = false);
+            lengths.remove(i);
+            let covered = cover_runs(occupied, lengths);
+            lengths.insert(i, length);
+            cells.iter().for_each(|cell| occupied[*cell] = true);
+            if covered {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/**
+ * Apply a bitwise operation to the board bits of two BinaryValues
+ * @dev only the lower BOARD_SIZE bits are operated on; upper bits of the result are always 0
+ *
+ * @param lhs - left operand
+ * @param rhs - right operand
+ * @param op - operation applied to each pair of bits
+ * @return - BinaryValue with bit i set to op(lhs[i], rhs[i]) for i < BOARD_SIZE
+ */
+fn board_bitwise(lhs: BinaryValue, rhs: BinaryValue, op: fn(bool, bool) -> bool) -> BinaryValue {
+    let mut value = U256::ZERO;
+    for i in 0..BOARD_SIZE {
+        value.set(i, op(lhs.value[i], rhs.value[i]));
+    }
+    BinaryValue::new(value)
+}
+
+// intersection of board bits (ex: board & shots = hits)
+impl BitAnd for BinaryValue {
+    type Output = BinaryValue;
+
+    fn bitand(self, rhs: BinaryValue) -> BinaryValue {
+        board_bitwise(self, rhs, |a, b| a & b)
+    }
+}
+
+// union of board bits (unlike zip, overlapping bits are allowed)
+impl BitOr for BinaryValue {
+    type Output = BinaryValue;
+
+    fn bitor(self, rhs: BinaryValue) -> BinaryValue {
+        board_bitwise(self, rhs, |a, b| a | b)
+    }
+}
+
+// symmetric difference of board bits (ex: board ^ (board & shots) = un-hit ship cells)
+impl BitXor for BinaryValue {
+    type Output = BinaryValue;
+
+    fn bitxor(self, rhs: BinaryValue) -> BinaryValue {
+        board_bitwise(self, rhs, |a, b| a ^ b)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        crate::utils::{
+            board::Board,
+            deck::Deck,
+            ship::{Ship, ShipType, DEFAULT_WITNESS_OPTIONS},
+        },
+        halo2_proofs::pasta::{
+            group::ff::{PrimeField, PrimeFieldBits},
+            Fp,
+        },
+        proptest::prelude::*,
+    };
+
+    /**
+     * Strategy producing any ship type placed fully on the board in either orientation
+     */
+    fn arb_ship() -> impl Strategy<Value = Ship> {
+        let ship_types = [
+            ShipType::Carrier,
+            ShipType::Battleship,
+            ShipType::Cruiser,
+            ShipType::Submarine,
+            ShipType::Destroyer,
+        ];
+        (0..ship_types.len(), any::<bool>()).prop_flat_map(move |(i, z)| {
+            let ship_type = ship_types[i];
+            let length = ship_type.length() as u8;
+            (0..=10 - length, 0..10u8).prop_map(move |(offset, lane)| {
+                let (x, y) = if z { (lane, offset) } else { (offset, lane) };
+                Ship::new(ship_type, x, y, z)
+            })
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn bitfield_matches_ship_coordinates(ship in arb_ship(), transpose in any::<bool>()) {
+            let bits = ship.bits(transpose).bitfield::<Fp, BOARD_SIZE>();
+            let coordinates = ship.coordinates(transpose);
+            for (i, bit) in bits.iter().enumerate() {
+                let expected = Fp::from(coordinates.contains(&i));
+                prop_assert_eq!(*bit, expected, "bit #{} of {:?}", i, ship);
+            }
+        }
+
+        #[test]
+        fn grid_round_trip(ship in arb_ship()) {
+            let board = ship.bits(false);
+            prop_assert_eq!(BinaryValue::from_grid(&board.to_grid()), board);
+            // cells reported by the ship are the set cells of the grid
+            let grid = board.to_grid();
+            for (x, y) in ship.cells() {
+                prop_assert!(grid[y as usize][x as usize]);
+            }
+        }
+
+        #[test]
+        fn bitfield_recomposes_to_commitment(ship in arb_ship(), transpose in any::<bool>()) {
+            let binary = ship.bits(transpose);
+            let commitment = Fp::from_u128(binary.lower_u128());
+            // recompose bits via weighted sum
+            let bits = binary.bitfield::<Fp, BOARD_SIZE>();
+            let mut recomposed = Fp::zero();
+            let mut power = Fp::one();
+            for bit in bits {
+                recomposed += bit * power;
+                power = power.double();
+            }
+            prop_assert_eq!(recomposed, commitment);
+            // field element little endian decomposition matches bitfield
+            let le_bits = commitment.to_le_bits();
+            for i in 0..BOARD_SIZE {
+                prop_assert_eq!(Fp::from(le_bits[i]), bits[i]);
+            }
         }
-        BinaryValue::new(zipped)
+    }
+
+    proptest! {
+        #[test]
+        fn parse_round_trip(value in any::<u128>()) {
+            let expected = BinaryValue::new(U256::new([value as u64, (value >> 64) as u64, 0, 0]));
+            prop_assert_eq!(BinaryValue::from_hex(&format!("{:x}", value)), Ok(expected));
+            prop_assert_eq!(BinaryValue::from_hex(&format!("0x{:X}", value)), Ok(expected));
+            prop_assert_eq!(BinaryValue::from_decimal_str(&value.to_string()), Ok(expected));
+        }
+
+        #[test]
+        fn parse_garbage_never_panics(input in ".{0,100}") {
+            // any unicode input is either parsed or rejected without panicking
+            for parsed in [BinaryValue::from_hex(&input), BinaryValue::from_decimal_str(&input)] {
+                if let Err(ParseError::InvalidDigit { index }) = parsed {
+                    prop_assert!(input.is_char_boundary(index));
+                }
+            }
+        }
+
+        #[test]
+        fn parse_over_long_digits(hex in "[1-9a-f][0-9a-f]{64,100}", decimal in "[1-9][0-9]{78,100}") {
+            // 65+ significant hex digits or 79+ significant decimal digits exceed 256 bits
+            prop_assert_eq!(BinaryValue::from_hex(&hex), Err(ParseError::Overflow));
+            prop_assert_eq!(BinaryValue::from_decimal_str(&decimal), Err(ParseError::Overflow));
+        }
+    }
+
+    #[test]
+    fn parse_seeds() {
+        assert_eq!(BinaryValue::from_hex(""), Err(ParseError::Empty));
+        assert_eq!(BinaryValue::from_hex("0x"), Err(ParseError::Empty));
+        assert_eq!(BinaryValue::from_decimal_str(""), Err(ParseError::Empty));
+        assert_eq!(
+            BinaryValue::from_hex("0x12g4"),
+            Err(ParseError::InvalidDigit { index: 4 })
+        );
+        assert_eq!(
+            BinaryValue::from_decimal_str("12a"),
+            Err(ParseError::InvalidDigit { index: 2 })
+        );
+        for signed in ["-1", "+1", " 1", "1 "] {
+            assert!(BinaryValue::from_decimal_str(signed).is_err());
+            assert!(BinaryValue::from_hex(signed).is_err());
+        }
+        // 2^256 - 1 is the largest value that fits, with any number of leading zeros
+        let max = BinaryValue::new(U256::new([u64::MAX; 4]));
+        assert_eq!(BinaryValue::from_hex(&"f".repeat(64)), Ok(max));
+        assert_eq!(
+            BinaryValue::from_hex(&format!("0x{}{}", "0".repeat(100), "f".repeat(64))),
+            Ok(max)
+        );
+        assert_eq!(
+            BinaryValue::from_decimal_str(
+                "115792089237316195423570985008687907853269984665640564039457584007913129639935"
+            ),
+            Ok(max)
+        );
+        assert_eq!(
+            BinaryValue::from_decimal_str(
+                "115792089237316195423570985008687907853269984665640564039457584007913129639936"
+            ),
+            Err(ParseError::Overflow)
+        );
+        // the default fleet state parses back from its printed forms
+        let state = Board::from(&Deck::default()).state(DEFAULT_WITNESS_OPTIONS);
+        let printed = state.lower_u128();
+        assert_eq!(BinaryValue::from_hex(&format!("{:#x}", printed)), Ok(state));
+        assert_eq!(
+            BinaryValue::from_decimal_str(&printed.to_string()),
+            Ok(state)
+        );
+    }
+
+    #[test]
+    fn bitfield_be_reverses_bits() {
+        let state = Board::from(&Deck::default()).state(DEFAULT_WITNESS_OPTIONS);
+        let le = state.bitfield::<Fp, BOARD_SIZE>();
+        let be = state.bitfield_be::<Fp, BOARD_SIZE>();
+        // recomposing with little endian weights gives the value with its 100 bits reversed
+        let recompose = |bits: [Fp; BOARD_SIZE]| {
+            bits.iter()
+                .zip(pow2_table::<Fp>())
+                .fold(Fp::zero(), |sum, (bit, power)| sum + bit * power)
+        };
+        assert_eq!(recompose(le), Fp::from_u128(state.lower_u128()));
+        let reversed = state.lower_u128().reverse_bits() >> (128 - BOARD_SIZE);
+        assert_eq!(recompose(be), Fp::from_u128(reversed));
+        // cell (0, 0) is the first little endian bit and the last big endian bit
+        assert_eq!((le[0], be[BOARD_SIZE - 1]), (Fp::one(), Fp::one()));
+    }
+
+    #[test]
+    fn from_grid_indexing() {
+        // cell (x: 3, y: 7) lands at bit 73
+        let mut grid = [[false; 10]; 10];
+        grid[7][3] = true;
+        let value = BinaryValue::from_grid(&grid);
+        assert!(value.value[73]);
+        assert_eq!(value.value.count_ones(), 1);
+        assert_eq!(value.to_grid(), grid);
+    }
+
+    #[test]
+    fn checked_lower_u128_high_bit() {
+        let mut value = U256::ZERO;
+        value.set(99, true);
+        assert_eq!(
+            BinaryValue::new(value).checked_lower_u128(),
+            Some(1u128 << 99)
+        );
+        // bit 200 is dropped by lower_u128 but rejected by checked_lower_u128
+        value.set(200, true);
+        assert_eq!(BinaryValue::new(value).lower_u128(), 1u128 << 99);
+        assert_eq!(BinaryValue::new(value).checked_lower_u128(), None);
+    }
+
+    #[test]
+    fn zip_checked_disjoint() {
+        // bits 3 and 64 do not collide
+        let mut a = U256::ZERO;
+        a.set(3, true);
+        let mut b = U256::ZERO;
+        b.set(64, true);
+        let zipped = BinaryValue::new(a).zip_checked(BinaryValue::new(b));
+        let mut expected = U256::ZERO;
+        expected.set(3, true);
+        expected.set(64, true);
+        assert_eq!(zipped, Ok(BinaryValue::new(expected)));
+    }
+
+    #[test]
+    fn zip_checked_conflict_index() {
+        // bits 17 and 42 collide; first collision is reported
+        let mut a = U256::ZERO;
+        a.set(5, true);
+        a.set(17, true);
+        a.set(42, true);
+        let mut b = U256::ZERO;
+        b.set(17, true);
+        b.set(42, true);
+        b.set(99, true);
+        let zipped = BinaryValue::new(a).zip_checked(BinaryValue::new(b));
+        assert_eq!(zipped, Err(17));
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot zip together bit #42")]
+    fn zip_conflict_panics() {
+        let mut a = U256::ZERO;
+        a.set(42, true);
+        _ = BinaryValue::new(a).zip(BinaryValue::new(a));
+    }
+
+    /**
+     * Return the state of the default fleet and shots at (0, 0), (4, 0) and (9, 9)
+     * @dev only the shot at (9, 9) misses
+     */
+    fn board_and_shots() -> (BinaryValue, BinaryValue) {
+        let board = Board::from(&Deck::default()).state(DEFAULT_WITNESS_OPTIONS);
+        let mut shots = U256::ZERO;
+        for i in [0, 4, 99] {
+            shots.set(i, true);
+        }
+        (board, BinaryValue::new(shots))
+    }
+
+    #[test]
+    fn bitand_hits() {
+        let (board, shots) = board_and_shots();
+        let hits = board & shots;
+        assert_eq!(hits.value.iter_ones().collect::<Vec<usize>>(), vec![0, 4]);
+    }
+
+    #[test]
+    fn bitor_union() {
+        let (board, shots) = board_and_shots();
+        let union = board | shots;
+        assert_eq!(union.value.count_ones(), Deck::expected_tonnage() + 1);
+        assert!(union.value[99]);
+        // or is idempotent where zip would panic
+        assert_eq!(board | board, board);
+    }
+
+    #[test]
+    fn bitxor_unhit_cells() {
+        let (board, shots) = board_and_shots();
+        let unhit = board ^ (board & shots);
+        assert_eq!(unhit.value.count_ones(), Deck::expected_tonnage() - 2);
+        assert!(!unhit.value[0] && !unhit.value[4]);
+        assert!(unhit.value[1]);
+    }
+
+    #[test]
+    fn bitwise_ignores_upper_bits() {
+        let mut a = U256::ZERO;
+        a.set(5, true);
+        a.set(200, true);
+        let a = BinaryValue::new(a);
+        assert_eq!((a | a).value.iter_ones().collect::<Vec<usize>>(), vec![5]);
+        assert_eq!((a & a).value.iter_ones().collect::<Vec<usize>>(), vec![5]);
+        assert_eq!((a ^ BinaryValue::empty()).value.count_ones(), 1);
+    }
+
+    #[test]
+    fn pow2_table_powers() {
+        let table = pow2_table::<Fp>();
+        for (i, power) in table.iter().enumerate() {
+            assert_eq!(*power, Fp::from(2).pow(&[i as u64, 0, 0, 0]));
+        }
+        // later calls reuse the cached table
+        assert!(std::ptr::eq(table, pow2_table::<Fp>()));
+    }
+
+    #[test]
+    fn field_bytes_round_trip() {
+        let state = Board::from(&Deck::default()).state(DEFAULT_WITNESS_OPTIONS);
+        // the preimage the board circuit hashes into the public commitment
+        let expected_commitment = Fp::from_u128(state.lower_u128());
+        let bytes = state.to_field_bytes();
+        assert_eq!(Fp::from_repr(bytes).unwrap(), expected_commitment);
+        assert_eq!(
+            BinaryValue::from_field_bytes(expected_commitment.to_repr()),
+            state
+        );
+        assert_eq!(BinaryValue::from_field_bytes(bytes), state);
+    }
+
+    #[test]
+    fn diff_single_shot() {
+        let before = Board::from(&Deck::default()).state(DEFAULT_WITNESS_OPTIONS);
+        assert_eq!(before.diff(&before), vec![]);
+        // firing at (7, 5) flips exactly one cell
+        let mut after = before;
+        after.value.set(57, true);
+        assert_eq!(before.diff(&after), vec![(7, 5, true)]);
+        assert_eq!(after.diff(&before), vec![(7, 5, false)]);
+    }
+
+    #[test]
+    fn legal_board() {
+        let fleet = [5, 4, 3, 3, 2];
+        let state = Board::from(&Deck::from([
+            Some((3, 3, true)),
+            Some((5, 4, false)),
+            Some((0, 1, false)),
+            Some((0, 5, true)),
+            Some((6, 1, false)),
+        ]))
+        .state(DEFAULT_WITNESS_OPTIONS);
+        assert!(state.is_legal_board(&fleet));
+        // ships packed side by side still split into the fleet
+        assert!(Board::from(&Deck::default())
+            .state(DEFAULT_WITNESS_OPTIONS)
+            .is_legal_board(&fleet));
+        // a stray bit cannot be covered by any ship
+        let mut stray = state;
+        stray.value.set(99, true);
+        assert!(!stray.is_legal_board(&fleet));
+        // the right number of cells, but the carrier bends at (3, 7)
+        let mut bent = state;
+        bent.value.set(73, false);
+        bent.value.set(74, true);
+        assert!(!bent.is_legal_board(&fleet));
+        assert!(!state.is_legal_board(&[5, 4, 3, 3]));
+    }
+
+    #[test]
+    fn packed_round_trip() {
+        let state = Board::from(&Deck::default()).state(DEFAULT_WITNESS_OPTIONS);
+        let (packed, checksum) = state.to_packed();
+        assert_eq!(BinaryValue::from_packed(packed, checksum), Ok(state));
+        // the last board bit lands in the low nibble of the final byte
+        let mut corner = BinaryValue::empty();
+        corner.value.set(BOARD_SIZE - 1, true);
+        assert_eq!(
+            corner.to_packed(),
+            ([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x08], 0x08)
+        );
+    }
+
+    #[test]
+    fn packed_corruption() {
+        let state = Board::from(&Deck::default()).state(DEFAULT_WITNESS_OPTIONS);
+        let (mut packed, checksum) = state.to_packed();
+        assert_eq!(
+            BinaryValue::from_packed(packed, checksum ^ 1),
+            Err(PackError::Checksum {
+                expected: checksum ^ 1,
+                computed: checksum
+            })
+        );
+        packed[3] ^= 0x40;
+        assert!(matches!(
+            BinaryValue::from_packed(packed, checksum),
+            Err(PackError::Checksum { .. })
+        ));
+        // padding bits set with a consistent checksum
+        let (mut packed, checksum) = state.to_packed();
+        packed[PACKED_BYTES - 1] |= 0x10;
+        assert_eq!(
+            BinaryValue::from_packed(packed, checksum ^ 0x10),
+            Err(PackError::Padding)
+        );
     }
 }
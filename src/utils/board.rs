@@ -4,12 +4,14 @@ use {
     crate::utils::{
         binary::BinaryValue,
         deck::Deck,
-        ship::{Ship, WitnessOption, DEFAULT_WITNESS_OPTIONS},
+        ship::{Ship, ShipType, WitnessOption, DEFAULT_WITNESS_OPTIONS},
     },
     bitvec::prelude::*,
+    rand_core::RngCore,
 };
 
 pub const BOARD_SIZE: usize = 100;
+pub const MAX_PLACEMENT_ATTEMPTS: usize = 1000; // random draws allowed per ship before giving up
 
 // Definition of all state data needed to create board commitments
 #[derive(Clone, Copy, Debug)]
@@ -146,3 +148,668 @@ impl Board {
         }
     }
 }
+
+/**
+ * Reasons a ship cannot be added to a board being built ship by ship
+ *
+ * @param Duplicate - a ship of the same type has already been added
+ * @param OffBoard - the ship extends past the edge of the board
+ * @param Overlap - the ship shares a cell with the already added ship of the given type
+ * @param Incomplete - the board was finished before every ship was added (holds the missing types)
+ */
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PlacementError {
+    Duplicate(ShipType),
+    OffBoard(ShipType),
+    Overlap(ShipType),
+    Incomplete(Vec<ShipType>),
+}
+
+/**
+ * Build a board one ship at a time, rejecting each invalid ship as it is added
+ * @dev every ship added is valid against the ships before it, so a complete builder always holds
+ *      a board the board circuit accepts
+ *
+ * @param board - the ships accepted so far
+ */
+#[derive(Clone, Copy, Debug)]
+pub struct BoardBuilder {
+    board: Board,
+}
+
+impl BoardBuilder {
+    pub fn new() -> Self {
+        BoardBuilder {
+            board: Board::new(),
+        }
+    }
+
+    /**
+     * Add a ship to the board if it is legal alongside the ships already added
+     *
+     * @param ship - the ship to add
+     * @return - Ok if the ship was added, otherwise why it was rejected (the builder is unchanged)
+     */
+    pub fn add_ship(&mut self, ship: Ship) -> Result<(), PlacementError> {
+        if self.board.ships[ship.ship_type].is_some() {
+            return Err(PlacementError::Duplicate(ship.ship_type));
+        }
+        if ship.cells().iter().any(|(x, y)| *x >= 10 || *y >= 10) {
+            return Err(PlacementError::OffBoard(ship.ship_type));
+        }
+        if let Some(placed) = self
+            .board
+            .ships
+            .ships()
+            .into_iter()
+            .find(|placed| placed.overlaps(ship))
+        {
+            return Err(PlacementError::Overlap(placed.ship_type));
+        }
+        self.board.ships.add(ship);
+        Ok(())
+    }
+
+    /**
+     * Return the ship types that have not been added yet
+     *
+     * @return - missing ship types in deck order
+     */
+    pub fn missing(&self) -> Vec<ShipType> {
+        [
+            ShipType::Carrier,
+            ShipType::Battleship,
+            ShipType::Cruiser,
+            ShipType::Submarine,
+            ShipType::Destroyer,
+        ]
+        .into_iter()
+        .filter(|ship_type| self.board.ships[*ship_type].is_none())
+        .collect()
+    }
+
+    /**
+     * Finish building the board
+     *
+     * @return - the board holding every added ship, or the ship types still missing
+     */
+    pub fn build(self) -> Result<Board, PlacementError> {
+        let missing = self.missing();
+        match missing.is_empty() {
+            true => Ok(self.board),
+            false => Err(PlacementError::Incomplete(missing)),
+        }
+    }
+}
+
+impl Default for BoardBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/**
+ * Return the minimal set of shots that sinks every ship on a board
+ * @dev every occupied cell must be hit exactly once, so the set is the occupied cells themselves
+ *
+ * @param board - board state (bit y * 10 + x set if cell (x, y) is occupied)
+ * @return - (x, y) coordinates of every occupied cell in ascending bit order
+ */
+pub fn sink_all_shots(board: BinaryValue) -> Vec<(usize, usize)> {
+    board.value[0..BOARD_SIZE]
+        .iter_ones()
+        .map(|i| (i % 10, i / 10))
+        .collect()
+}
+
+/**
+ * Error returned when a replayed shot is not a cell of the board
+ *
+ * @param shot - index of the first off-board shot in the replay
+ * @param x - horizontal coordinate of the shot
+ * @param y - vertical coordinate of the shot
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct OffBoardShot {
+    pub shot: usize,
+    pub x: u8,
+    pub y: u8,
+}
+
+/**
+ * Rebuild the shots fired at a board from the ordered shots of a game replay
+ * @dev matches the shots value exported publicly by the win chip; repeated shots leave the
+ *      accumulated value unchanged. replayed shots come from untrusted instances, so a coordinate
+ *      of 10 or more is rejected rather than aliased to another cell
+ *
+ * @param shots - (x, y) coordinates of every shot in the order they were proven
+ * @return - 100-bit integer where the (y*10 + x)th bit is flipped for every cell shot, or the
+ *           first shot that is off the board
+ */
+pub fn replay_shots(shots: &[(u8, u8)]) -> Result<BinaryValue, OffBoardShot> {
+    let mut fired = BinaryValue::empty();
+    for (shot, &(x, y)) in shots.iter().enumerate() {
+        if x >= 10 || y >= 10 {
+            return Err(OffBoardShot { shot, x, y });
+        }
+        fired.value.set(y as usize * 10 + x as usize, true);
+    }
+    Ok(fired)
+}
+
+/**
+ * Report which ships are still afloat after a set of shots
+ * @dev off-circuit: a ship is sunk once every cell it covers has been shot
+ *
+ * @param ships - the placed ships to check
+ * @param shots - shots fired at the board (bit y * 10 + x set if cell (x, y) was shot)
+ * @return - true for every ship with at least one un-hit cell, in the order of ships
+ */
+pub fn ships_remaining(ships: &[Ship], shots: BinaryValue) -> Vec<bool> {
+    ships
+        .iter()
+        .map(|ship| {
+            ship.cells()
+                .iter()
+                .any(|(x, y)| !shots.value[*y as usize * 10 + *x as usize])
+        })
+        .collect()
+}
+
+/**
+ * Error returned when ship commitments cannot be combined into a board
+ *
+ * @param ship - index [0-4] of the ship whose commitment collided with the board built so far
+ * @param other - index [0-4] of the earlier ship already covering the bit (ship itself if its own
+ *        H and V commitments collide)
+ * @param bit - the first board bit set by both
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct OverlapError {
+    pub ship: usize,
+    pub other: usize,
+    pub bit: usize,
+}
+
+/**
+ * Combine the H, V commitments of each ship into the board state
+ * @dev matches the board chip: V commitments are transposed before all placements are zipped
+ *
+ * @param commitments - [H, V] commitments for each of the 5 ships in deck order
+ * @return - board state (same as Board::state), or where the first overlapping bit was found
+ */
+pub fn combine_commitments(
+    commitments: &[[BinaryValue; 2]; 5],
+) -> Result<BinaryValue, OverlapError> {
+    let mut board = BinaryValue::empty();
+    let mut placements = Vec::<BinaryValue>::new();
+    for (ship, [horizontal, vertical]) in commitments.iter().enumerate() {
+        let mut transposed = U256::ZERO;
+        for i in vertical.value[0..BOARD_SIZE].iter_ones() {
+            transposed.set(i % 10 * 10 + i / 10, true);
+        }
+        let placement = horizontal
+            .zip_checked(BinaryValue::new(transposed))
+            .map_err(|bit| OverlapError {
+                ship,
+                other: ship,
+                bit,
+            })?;
+        board = board.zip_checked(placement).map_err(|bit| OverlapError {
+            ship,
+            other: placements
+                .iter()
+                .position(|placed| placed.value[bit])
+                .unwrap(),
+            bit,
+        })?;
+        placements.push(placement);
+    }
+    Ok(board)
+}
+
+/**
+ * Reasons a board state cannot be decoded back into a fleet
+ *
+ * @param NoFleet - the occupied cells cannot be split into the 5 ships of the fleet
+ * @param Ambiguous - the occupied cells split into more than one fleet
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DecodeError {
+    NoFleet,
+    Ambiguous,
+}
+
+/**
+ * Reconstruct the fleet placed on a board from the board state alone
+ * @dev the cruiser and submarine share a length so cannot be told apart: the length 3 ship with
+ *      the lower head is always decoded as the cruiser
+ *
+ * @param board - board state (bit y * 10 + x set if cell (x, y) is occupied)
+ * @return - the only deck whose ships exactly cover the occupied cells, or why none could be chosen
+ */
+pub fn decode_board(board: BinaryValue) -> Result<Deck, DecodeError> {
+    let mut occupied = [false; BOARD_SIZE];
+    for i in board.value[0..BOARD_SIZE].iter_ones() {
+        occupied[i] = true;
+    }
+    let mut fleets = Vec::<Deck>::new();
+    segment_fleet(&mut occupied, &mut Deck::new(), &mut fleets);
+    match fleets.len() {
+        0 => Err(DecodeError::NoFleet),
+        1 => Ok(fleets[0]),
+        _ => Err(DecodeError::Ambiguous),
+    }
+}
+
+/**
+ * Search for fleets covering the remaining occupied cells, stopping once two are found
+ * @dev the lowest remaining occupied cell must be the head of a ship in either orientation
+ *
+ * @param occupied - cells not yet covered by a ship in the deck
+ * @param deck - ships placed so far
+ * @param fleets - complete decks found so far
+ */
+fn segment_fleet(occupied: &mut [bool; BOARD_SIZE], deck: &mut Deck, fleets: &mut Vec<Deck>) {
+    let head = match occupied.iter().position(|cell| *cell) {
+        Some(head) => head,
+        None => {
+            if deck.iterator().iter().all(Option::is_some) {
+                fleets.push(*deck);
+            }
+            return;
+        }
+    };
+    for ship_type in [
+        ShipType::Carrier,
+        ShipType::Battleship,
+        ShipType::Cruiser,
+        ShipType::Submarine,
+        ShipType::Destroyer,
+    ] {
+        // only try the submarine after the cruiser so equal length ships are not decoded twice
+        if deck[ship_type].is_some()
+            || (ship_type == ShipType::Submarine && deck[ShipType::Cruiser].is_none())
+        {
+            continue;
+        }
+        for z in [false, true] {
+            let ship = Ship::new(ship_type, (head % 10) as u8, (head / 10) as u8, z);
+            let cells = ship
+                .cells()
+                .into_iter()
+                .map(|(x, y)| (x < 10 && y < 10).then(|| y as usize * 10 + x as usize))
+                .collect::<Option<Vec<usize>>>();
+            let cells = match cells {
+                Some(cells) if cells.iter().all(|i| occupied[*i]) => cells,
+                _ => continue,
+            };
+            cells.iter().for_each(|i| occupied[*i] = false);
+            deck.add(ship);
+            segment_fleet(occupied, deck, fleets);
+            deck.remove(ship_type);
+            cells.iter().for_each(|i| occupied[*i] = true);
+            if fleets.len() > 1 {
+                return;
+            }
+        }
+    }
+}
+
+/**
+ * Place the classic fleet at random positions with no two ships overlapping
+ * @dev heads are only drawn where the whole ship fits on the board, so draws are only retried on
+ *      overlap. if any ship exhausts MAX_PLACEMENT_ATTEMPTS the valid Deck::default() is returned
+ *
+ * @param rng - source of randomness for ship heads and orientations
+ * @return - deck of 5 on-board ships with disjoint cells
+ */
+pub fn random_valid_fleet(rng: &mut impl RngCore) -> Deck {
+    let mut deck = Deck::new();
+    let mut placed = Vec::<Ship>::new();
+    for ship_type in [
+        ShipType::Carrier,
+        ShipType::Battleship,
+        ShipType::Cruiser,
+        ShipType::Submarine,
+        ShipType::Destroyer,
+    ] {
+        let span = (11 - ship_type.length()) as u32;
+        let ship = (0..MAX_PLACEMENT_ATTEMPTS)
+            .map(|_| {
+                let z = rng.next_u32() % 2 == 1;
+                let (along, across) = ((rng.next_u32() % span) as u8, (rng.next_u32() % 10) as u8);
+                match z {
+                    true => Ship::new(ship_type, across, along, z),
+                    false => Ship::new(ship_type, along, across, z),
+                }
+            })
+            .find(|ship| placed.iter().all(|other| !ship.overlaps(*other)));
+        match ship {
+            Some(ship) => {
+                deck.add(ship);
+                placed.push(ship);
+            }
+            None => return Deck::default(),
+        }
+    }
+    deck
+}
+
+/**
+ * Generators for malicious fleets that each violate a single board rule
+ * @dev fleets start from Deck::default() (ships stacked horizontally in rows 0-4 from x = 0)
+ *      and move the destroyer so that only the targeted rule is broken
+ */
+pub mod adversarial {
+    use crate::utils::{
+        deck::Deck,
+        ship::{Ship, ShipType},
+    };
+
+    // coordinate field of a ship placement
+    #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    pub enum Field {
+        X,
+        Y,
+    }
+
+    /**
+     * Return a fleet where the destroyer overlaps the carrier
+     * @dev caught by the transpose chip's collision constraint at cells (1, 0) and (2, 0)
+     *
+     * @return - deck with two ships sharing board cells
+     */
+    pub fn overlapping_fleet() -> Deck {
+        let mut deck = Deck::default();
+        deck.add(Ship::new(ShipType::Destroyer, 1, 0, false));
+        deck
+    }
+
+    /**
+     * Return a fleet where the destroyer runs off the edge of the board and wraps to the next row/ column
+     * @dev caught by the destroyer placement chip's full bit window constraint for the given orientation
+     *
+     * @param orientation - if true the destroyer overflows its column, otherwise its row
+     * @return - deck with one ship hanging off the board
+     */
+    pub fn off_board_fleet(orientation: bool) -> Deck {
+        let mut deck = Deck::default();
+        let (x, y) = if orientation { (6, 9) } else { (9, 6) };
+        deck.add(Ship::new(ShipType::Destroyer, x, y, orientation));
+        deck
+    }
+
+    /**
+     * Return a fleet where the destroyer's head coordinate is outside [0, 9]
+     * @dev the head is placed in the last row/ column so its cells serialize past the board to
+     *      bits 100 and 101 instead of aliasing an in-range cell, and are caught by the 100 bit
+     *      num2bits decomposition of the destroyer commitment:
+     *        - Field::X: horizontal destroyer at (10, 9)
+     *        - Field::Y: vertical destroyer at (9, 10)
+     *
+     * @param field - the coordinate of the destroyer set out of range
+     * @return - deck with one ship whose head coordinate is 10
+     */
+    pub fn out_of_range_fleet(field: Field) -> Deck {
+        let mut deck = Deck::default();
+        let ship = match field {
+            Field::X => Ship::new(ShipType::Destroyer, 10, 9, false),
+            Field::Y => Ship::new(ShipType::Destroyer, 9, 10, true),
+        };
+        deck.add(ship);
+        deck
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        proptest::test_runner::{RngAlgorithm, TestRng},
+    };
+
+    #[test]
+    fn sink_all_shots_covers_board() {
+        let boards = [
+            Board::from(&Deck::default()),
+            Board::from(&Deck::from([
+                Some((3, 3, true)),
+                Some((5, 4, false)),
+                Some((0, 1, false)),
+                Some((0, 5, true)),
+                Some((6, 1, false)),
+            ])),
+        ];
+        for board in boards {
+            let state = board.state(DEFAULT_WITNESS_OPTIONS);
+            let shots = sink_all_shots(state);
+            assert_eq!(shots.len(), state.value[0..BOARD_SIZE].count_ones());
+            assert_eq!(shots.len(), Deck::expected_tonnage());
+            for (x, y) in shots {
+                assert!(state.value[y * 10 + x]);
+            }
+        }
+    }
+
+    #[test]
+    fn sink_all_shots_empty_board() {
+        assert_eq!(
+            sink_all_shots(Board::new().state(DEFAULT_WITNESS_OPTIONS)),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn replay_shots_accumulates() {
+        let shots = [(3, 3), (9, 9), (0, 0), (3, 3)];
+        let fired = replay_shots(&shots).unwrap();
+        assert_eq!(fired, crate::utils::shot::serialize_cells(&shots));
+        assert_eq!(fired.value[0..BOARD_SIZE].count_ones(), 3);
+        assert_eq!(replay_shots(&[]), Ok(BinaryValue::empty()));
+    }
+
+    #[test]
+    fn replay_shots_rejects_off_board() {
+        // (10, 0) would alias cell (0, 1)
+        assert_eq!(
+            replay_shots(&[(3, 3), (10, 0)]),
+            Err(OffBoardShot {
+                shot: 1,
+                x: 10,
+                y: 0
+            })
+        );
+        // 26 * 10 overflows u8
+        assert_eq!(
+            replay_shots(&[(0, 26), (3, 3)]),
+            Err(OffBoardShot {
+                shot: 0,
+                x: 0,
+                y: 26
+            })
+        );
+    }
+
+    #[test]
+    fn ships_remaining_after_shots() {
+        let ships = [
+            Ship::new(ShipType::Cruiser, 0, 1, false),
+            Ship::new(ShipType::Destroyer, 6, 1, true),
+        ];
+        assert_eq!(
+            ships_remaining(&ships, BinaryValue::empty()),
+            vec![true, true]
+        );
+        // every cruiser cell hit, one of two destroyer cells hit
+        let shots = replay_shots(&[(0, 1), (1, 1), (2, 1), (6, 2)]).unwrap();
+        assert_eq!(ships_remaining(&ships, shots), vec![false, true]);
+        let shots = shots | replay_shots(&[(6, 1)]).unwrap();
+        assert_eq!(ships_remaining(&ships, shots), vec![false, false]);
+    }
+
+    #[test]
+    fn builder_rejects_invalid_ships() {
+        let mut builder = BoardBuilder::new();
+        builder
+            .add_ship(Ship::new(ShipType::Carrier, 3, 3, true))
+            .unwrap();
+        // battleship crosses the carrier at (3, 5)
+        assert_eq!(
+            builder.add_ship(Ship::new(ShipType::Battleship, 1, 5, false)),
+            Err(PlacementError::Overlap(ShipType::Carrier))
+        );
+        assert_eq!(
+            builder.add_ship(Ship::new(ShipType::Carrier, 0, 0, false)),
+            Err(PlacementError::Duplicate(ShipType::Carrier))
+        );
+        assert_eq!(
+            builder.add_ship(Ship::new(ShipType::Destroyer, 9, 0, false)),
+            Err(PlacementError::OffBoard(ShipType::Destroyer))
+        );
+        // rejected ships leave the builder unchanged
+        assert_eq!(
+            builder.build().unwrap_err(),
+            PlacementError::Incomplete(vec![
+                ShipType::Battleship,
+                ShipType::Cruiser,
+                ShipType::Submarine,
+                ShipType::Destroyer,
+            ])
+        );
+    }
+
+    #[test]
+    fn random_valid_fleet_disjoint_on_board() {
+        // deterministic seed so any invalid fleet found is reproducible
+        let mut rng = TestRng::deterministic_rng(RngAlgorithm::ChaCha);
+        for _ in 0..1000 {
+            let deck = random_valid_fleet(&mut rng);
+            let ships = deck.iterator().map(Option::unwrap);
+            for ship in ships {
+                assert!(ship.cells().iter().all(|&(x, y)| x < 10 && y < 10));
+            }
+            // ships are disjoint iff their commitments combine without collision
+            let board = Board::from(&deck);
+            assert!(combine_commitments(&commitments(board)).is_ok());
+            assert_eq!(
+                board.state(DEFAULT_WITNESS_OPTIONS).value.count_ones(),
+                Deck::expected_tonnage()
+            );
+        }
+    }
+
+    /**
+     * Split a board witness into [H, V] commitments per ship
+     *
+     * @param board - the board to witness with default witness options
+     * @return - [H, V] commitments for each of the 5 ships
+     */
+    fn commitments(board: Board) -> [[BinaryValue; 2]; 5] {
+        let witness = board.witness(DEFAULT_WITNESS_OPTIONS);
+        [0, 1, 2, 3, 4].map(|i| [witness[i * 2], witness[i * 2 + 1]])
+    }
+
+    #[test]
+    fn combine_commitments_matches_board() {
+        for deck in [
+            Deck::default(),
+            Deck::from([
+                Some((3, 3, true)),
+                Some((5, 4, false)),
+                Some((0, 1, false)),
+                Some((0, 5, true)),
+                Some((6, 1, false)),
+            ]),
+            Deck::from([None, Some((9, 6, true)), None, Some((0, 6, false)), None]),
+        ] {
+            let board = Board::from(&deck);
+            assert_eq!(
+                combine_commitments(&commitments(board)).unwrap().value,
+                board.state(DEFAULT_WITNESS_OPTIONS).value
+            );
+        }
+    }
+
+    #[test]
+    fn combine_commitments_overlap() {
+        // destroyer at (1, 0) first collides with the carrier at (1, 0)
+        let board = Board::from(&adversarial::overlapping_fleet());
+        assert_eq!(
+            combine_commitments(&commitments(board)),
+            Err(OverlapError {
+                ship: 4,
+                other: 0,
+                bit: 1
+            })
+        );
+    }
+
+    #[test]
+    fn combine_commitments_identical_ships() {
+        // cruiser and submarine share length 3, so identical coordinates fully overlap
+        let board = Board::from(&Deck::from([
+            Some((3, 3, true)),
+            Some((5, 4, false)),
+            Some((0, 1, false)),
+            Some((0, 1, false)),
+            Some((6, 1, false)),
+        ]));
+        assert_eq!(
+            combine_commitments(&commitments(board)),
+            Err(OverlapError {
+                ship: 3,
+                other: 2,
+                bit: 10
+            })
+        );
+    }
+
+    #[test]
+    fn decode_board_round_trip() {
+        for deck in [
+            Deck::from([
+                Some((3, 3, true)),
+                Some((5, 4, false)),
+                Some((0, 1, false)),
+                Some((0, 5, true)),
+                Some((6, 1, false)),
+            ]),
+            Deck::from([
+                Some((3, 4, false)),
+                Some((9, 6, true)),
+                Some((0, 0, false)),
+                Some((0, 6, false)),
+                Some((6, 1, true)),
+            ]),
+        ] {
+            let state = Board::from(&deck).state(DEFAULT_WITNESS_OPTIONS);
+            assert_eq!(decode_board(state).unwrap().iterator(), deck.iterator());
+        }
+    }
+
+    #[test]
+    fn decode_board_ambiguous() {
+        // destroyer and two length 3 ships tile a 4 x 2 block with the destroyer on either end
+        let deck = Deck::from([
+            Some((5, 5, false)),
+            Some((0, 7, false)),
+            Some((1, 0, false)),
+            Some((1, 1, false)),
+            Some((0, 0, true)),
+        ]);
+        let state = Board::from(&deck).state(DEFAULT_WITNESS_OPTIONS);
+        assert_eq!(decode_board(state).unwrap_err(), DecodeError::Ambiguous);
+    }
+
+    #[test]
+    fn decode_board_no_fleet() {
+        assert_eq!(
+            decode_board(Board::new().state(DEFAULT_WITNESS_OPTIONS)).unwrap_err(),
+            DecodeError::NoFleet
+        );
+        // overlapping ships leave too few cells for the fleet
+        let state = Board::from(&adversarial::overlapping_fleet()).state(DEFAULT_WITNESS_OPTIONS);
+        assert_eq!(decode_board(state).unwrap_err(), DecodeError::NoFleet);
+    }
+}
@@ -0,0 +1,52 @@
+use {
+    crate::utils::layout::LayoutRecorder,
+    halo2_proofs::{
+        arithmetic::FieldExt,
+        plonk::{Advice, Circuit, Column, Error, Fixed},
+    },
+};
+
+/**
+ * Extract the advice values a circuit assigns in a region as a matrix for printing
+ * @dev synthesizes the circuit with its own floor planner instead of inspecting a MockProver
+ *      (halo2 does not expose a MockProver's assignments). only the first region with the
+ *      given name is recorded; cells the region leaves unassigned read as 0
+ *
+ * @param circuit - the circuit to synthesize, with witnesses assigned
+ * @param config - config returned by configuring the circuit
+ * @param constants - fixed columns the circuit enabled for constants
+ * @param region - name of the region to extract
+ * @param columns - advice columns to extract, in output column order
+ * @return - one row per region row (from its first assigned row) holding one value per column
+ */
+pub fn advice_grid<F: FieldExt, C: Circuit<F>>(
+    circuit: &C,
+    config: C::Config,
+    constants: Vec<Column<Fixed>>,
+    region: &str,
+    columns: &[Column<Advice>],
+) -> Result<Vec<Vec<F>>, Error> {
+    let mut layout = LayoutRecorder::with_values();
+    layout.synthesize(circuit, config, constants)?;
+    let cells = match layout
+        .regions
+        .iter()
+        .find(|recorded| recorded.name == region)
+    {
+        Some(recorded) => &recorded.advice,
+        None => return Ok(vec![]),
+    };
+    let rows = cells.keys().map(|(_, row)| *row);
+    let (first, last) = match (rows.clone().min(), rows.max()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return Ok(vec![]),
+    };
+    Ok((first..=last)
+        .map(|row| {
+            columns
+                .iter()
+                .map(|column| *cells.get(&(*column, row)).unwrap_or(&F::zero()))
+                .collect()
+        })
+        .collect())
+}
@@ -1,8 +1,24 @@
 use {
-    crate::utils::ship::{Ship, ShipType},
+    crate::utils::ship::{get_ship_length, Ship, ShipType},
     std::ops::{Index, IndexMut},
 };
 
+/**
+ * Reasons a fleet notation string cannot be parsed into a deck
+ *
+ * @param Malformed - token is not <column A-J><row 1-10><H|V><length>
+ * @param OffBoard - token places a ship extending past the edge of the board
+ * @param Length - token's length is not left in the classic fleet (5, 4, 3, 3, 2)
+ * @param ShipCount - string does not contain exactly 5 tokens (holds the number found)
+ */
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum NotationError {
+    Malformed(String),
+    OffBoard(String),
+    Length(String),
+    ShipCount(usize),
+}
+
 // contains all 5 ship commitments
 #[derive(Clone, Copy, Debug)]
 pub struct Deck {
@@ -44,6 +60,21 @@ impl Deck {
         deck
     }
 
+    /**
+     * Return the total number of board cells occupied by a full fleet (17 for the classic fleet)
+     *
+     * @return - sum of the lengths of all 5 ships
+     */
+    pub const fn expected_tonnage() -> usize {
+        let mut tonnage = 0;
+        let mut i = 0;
+        while i < 5 {
+            tonnage += get_ship_length(i);
+            i += 1;
+        }
+        tonnage
+    }
+
     /**
      * Selectively place ships onto the board (instead of all 5)
      * @dev index corresponds to [carrier, battleship, cruiser, submarine, destroyer]
@@ -70,6 +101,58 @@ impl Deck {
         deck
     }
 
+    /**
+     * Parse a fleet from human readable notation
+     * @dev each token is <column A-J><row 1-10><H|V><length>, ex: "A1H5" is a horizontal carrier
+     *      with its head at (0, 0). the first length 3 token is the cruiser, the second the submarine
+     *
+     * @param notation - 5 whitespace or comma separated tokens, ex: "A1H5 B3V4 D4H3 H7V3 J1V2"
+     * @return - deck with all 5 ships placed, or the error naming the first offending token
+     */
+    pub fn from_notation(notation: &str) -> Result<Self, NotationError> {
+        let tokens = notation
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|token| !token.is_empty())
+            .collect::<Vec<&str>>();
+        if tokens.len() != 5 {
+            return Err(NotationError::ShipCount(tokens.len()));
+        }
+        let mut deck = Deck::new();
+        for token in tokens {
+            let malformed = || NotationError::Malformed(String::from(token));
+            let mut chars = token.chars();
+            let x = match chars.next() {
+                Some(column @ 'A'..='J') => column as u8 - b'A',
+                _ => return Err(malformed()),
+            };
+            let rest = chars.as_str();
+            let split = rest.find(['H', 'V']).ok_or_else(malformed)?;
+            let y = match rest[..split].parse::<u8>() {
+                Ok(row @ 1..=10) => row - 1,
+                _ => return Err(malformed()),
+            };
+            let z = &rest[split..split + 1] == "V";
+            let length = rest[split + 1..]
+                .parse::<usize>()
+                .map_err(|_| malformed())?;
+            let ship_type = [
+                ShipType::Carrier,
+                ShipType::Battleship,
+                ShipType::Cruiser,
+                ShipType::Submarine,
+                ShipType::Destroyer,
+            ]
+            .into_iter()
+            .find(|ship_type| ship_type.length() == length && deck[*ship_type].is_none())
+            .ok_or_else(|| NotationError::Length(String::from(token)))?;
+            if (if z { y } else { x }) as usize + length > 10 {
+                return Err(NotationError::OffBoard(String::from(token)));
+            }
+            deck.add(Ship::new(ship_type, x, y, z));
+        }
+        Ok(deck)
+    }
+
     /**
      * Return an iterable reference to ships in enum order
      *
@@ -85,6 +168,19 @@ impl Deck {
         ]
     }
 
+    /**
+     * Return the placed ships in canonical order
+     * @dev sorted by (ship_type, x, y, z) so decks holding the same ships list them identically
+     *      no matter the order they were added in
+     *
+     * @return - vector of every ship in the deck, sorted
+     */
+    pub fn ships(&self) -> Vec<Ship> {
+        let mut ships = self.iterator().into_iter().flatten().collect::<Vec<Ship>>();
+        ships.sort();
+        ships
+    }
+
     /**
      * Add a new ship of given type, or overwrite current ship of given type
      *
@@ -128,3 +224,112 @@ impl IndexMut<ShipType> for Deck {
         }
     }
 }
+
+impl TryFrom<&str> for Deck {
+    type Error = NotationError;
+    fn try_from(notation: &str) -> Result<Self, NotationError> {
+        Deck::from_notation(notation)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        crate::utils::{board::Board, ship::DEFAULT_WITNESS_OPTIONS},
+    };
+
+    #[test]
+    fn from_notation_valid() {
+        // board pattern 1 written in notation, mixing whitespace and comma separators
+        let deck = Deck::try_from("D4V5, F5H4 A2H3\tA6V3 ,G2H2").unwrap();
+        let expected = Deck::from([
+            Some((3, 3, true)),
+            Some((5, 4, false)),
+            Some((0, 1, false)),
+            Some((0, 5, true)),
+            Some((6, 1, false)),
+        ]);
+        assert_eq!(deck.iterator(), expected.iterator());
+    }
+
+    #[test]
+    fn from_notation_malformed() {
+        let cases = [
+            ("A1H5 A2H4 A3H3 A4H3", NotationError::ShipCount(4)),
+            ("A1H5 A2H4 A3H3 A4H3 A5H2 A6H2", NotationError::ShipCount(6)),
+            (
+                "A1H5 A2H4 A3H3 A4H3 K5H2",
+                NotationError::Malformed(String::from("K5H2")),
+            ),
+            (
+                "A1H5 A2H4 A3H3 A4H3 A11H2",
+                NotationError::Malformed(String::from("A11H2")),
+            ),
+            (
+                "A1H5 A2H4 A3H3 A4H3 A0H2",
+                NotationError::Malformed(String::from("A0H2")),
+            ),
+            (
+                "A1H5 A2D4 A3H3 A4H3 A5H2",
+                NotationError::Malformed(String::from("A2D4")),
+            ),
+            (
+                "A1H5 A2H4 A3H3 A4H3 A5H",
+                NotationError::Malformed(String::from("A5H")),
+            ),
+            (
+                "A1H5 A2H4 A3H3 A4H3 A5H4",
+                NotationError::Length(String::from("A5H4")),
+            ),
+            (
+                "A1H5 A2H4 A3H3 A4H3 A5H3",
+                NotationError::Length(String::from("A5H3")),
+            ),
+            (
+                "G1H5 A2H4 A3H3 A4H3 A5H2",
+                NotationError::OffBoard(String::from("G1H5")),
+            ),
+            (
+                "A1H5 A2H4 A3H3 A4H3 A10V2",
+                NotationError::OffBoard(String::from("A10V2")),
+            ),
+        ];
+        for (notation, error) in cases {
+            assert_eq!(Deck::from_notation(notation).unwrap_err(), error);
+        }
+    }
+
+    #[test]
+    fn ships_canonical_order() {
+        let ships = [
+            Ship::new(ShipType::Destroyer, 6, 1, false),
+            Ship::new(ShipType::Carrier, 3, 3, true),
+            Ship::new(ShipType::Submarine, 0, 5, true),
+            Ship::new(ShipType::Battleship, 5, 4, false),
+            Ship::new(ShipType::Cruiser, 0, 1, false),
+        ];
+        // ship type dominates the ordering before any coordinate
+        assert!(ships[1] < ships[3]);
+        assert!(
+            Ship::new(ShipType::Carrier, 9, 9, true) < Ship::new(ShipType::Battleship, 0, 0, false)
+        );
+        // same ships added in two different orders
+        let mut forward = Deck::new();
+        let mut reverse = Deck::new();
+        for ship in ships {
+            forward.add(ship);
+        }
+        for ship in ships.iter().rev() {
+            reverse.add(*ship);
+        }
+        let mut sorted = ships.to_vec();
+        sorted.sort();
+        assert_eq!(forward.ships(), sorted);
+        assert_eq!(reverse.ships(), sorted);
+        assert_eq!(
+            Board::from(&forward).state(DEFAULT_WITNESS_OPTIONS),
+            Board::from(&reverse).state(DEFAULT_WITNESS_OPTIONS)
+        );
+    }
+}
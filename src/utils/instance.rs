@@ -0,0 +1,274 @@
+/**
+ * Public values exported to the instance column by the game circuits
+ *
+ * @param Digest - poseidon hash of the private board state
+ * @param Shot - serialized shot coordinates
+ * @param Hit - asserted hit (1) or miss (0) of the shot
+ * @param Nonce - turn the shot is fired on
+ * @param Shots - serialized board of every shot fired
+ * @param Win - 1 if every ship cell was hit by the shots fired, 0 otherwise (computed in circuit)
+ * @param Region - serialized cells of the region proven to be empty
+ * @param Board - serialized board state revealed at the end of the game
+ * @param Hits - number of shots fired that hit the board
+ * @param Root - Merkle root of an allowlist of board commitments
+ * @param GameId - identifier of the game a salted board commitment is bound to
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PublicInput {
+    Digest,
+    Shot,
+    Hit,
+    Nonce,
+    Shots,
+    Win,
+    Region,
+    Board,
+    Hits,
+    Root,
+    GameId,
+}
+
+/**
+ * Canonical order of public inputs in the instance column of each circuit
+ * @dev the authoritative reference for both chips exporting values and verifiers assembling
+ *      instances: a value's row is its index in inputs()
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InstanceLayout {
+    Board,
+    Shot,
+    RegionMiss,
+    Membership,
+    Win,
+    Reveal,
+    Tally,
+    MerkleMembership,
+    SaltedBoard,
+    SaltedShot,
+    SaltedRegionMiss,
+    SaltedWin,
+    SaltedTally,
+    SaltedMembership,
+    SaltedReveal,
+}
+
+impl InstanceLayout {
+    /**
+     * Return the public inputs of the circuit in instance row order
+     *
+     * @return - public inputs where the input at index i is exported to instance row i
+     */
+    pub const fn inputs(self) -> &'static [PublicInput] {
+        match self {
+            InstanceLayout::Board => &[PublicInput::Digest],
+            InstanceLayout::Shot => &[
+                PublicInput::Digest,
+                PublicInput::Shot,
+                PublicInput::Hit,
+                PublicInput::Nonce,
+            ],
+            InstanceLayout::RegionMiss => &[PublicInput::Digest, PublicInput::Region],
+            InstanceLayout::Membership => &[PublicInput::Digest],
+            InstanceLayout::Win => &[PublicInput::Digest, PublicInput::Shots, PublicInput::Win],
+            InstanceLayout::Reveal => &[PublicInput::Digest, PublicInput::Board],
+            InstanceLayout::Tally => &[PublicInput::Digest, PublicInput::Shots, PublicInput::Hits],
+            InstanceLayout::MerkleMembership => {
+                &[PublicInput::Digest, PublicInput::GameId, PublicInput::Root]
+            }
+            InstanceLayout::SaltedBoard => &[PublicInput::Digest, PublicInput::GameId],
+            // salted variants append the game id so every other row matches the unsalted layout
+            InstanceLayout::SaltedShot => &[
+                PublicInput::Digest,
+                PublicInput::Shot,
+                PublicInput::Hit,
+                PublicInput::Nonce,
+                PublicInput::GameId,
+            ],
+            InstanceLayout::SaltedRegionMiss => &[
+                PublicInput::Digest,
+                PublicInput::Region,
+                PublicInput::GameId,
+            ],
+            InstanceLayout::SaltedWin => &[
+                PublicInput::Digest,
+                PublicInput::Shots,
+                PublicInput::Win,
+                PublicInput::GameId,
+            ],
+            InstanceLayout::SaltedTally => &[
+                PublicInput::Digest,
+                PublicInput::Shots,
+                PublicInput::Hits,
+                PublicInput::GameId,
+            ],
+            InstanceLayout::SaltedMembership => &[PublicInput::Digest, PublicInput::GameId],
+            InstanceLayout::SaltedReveal => {
+                &[PublicInput::Digest, PublicInput::Board, PublicInput::GameId]
+            }
+        }
+    }
+
+    /**
+     * Return the layout of the circuit when its board commitment is salted for one game
+     * @dev panics if the circuit has no salted variant
+     *
+     * @return - the layout exporting the salted digest and the game id
+     */
+    pub fn salted(self) -> InstanceLayout {
+        match self {
+            InstanceLayout::Board => InstanceLayout::SaltedBoard,
+            InstanceLayout::Shot => InstanceLayout::SaltedShot,
+            InstanceLayout::RegionMiss => InstanceLayout::SaltedRegionMiss,
+            InstanceLayout::Win => InstanceLayout::SaltedWin,
+            InstanceLayout::Tally => InstanceLayout::SaltedTally,
+            InstanceLayout::Membership => InstanceLayout::SaltedMembership,
+            InstanceLayout::Reveal => InstanceLayout::SaltedReveal,
+            _ => panic!("the {:?} circuit has no salted variant", self),
+        }
+    }
+
+    /**
+     * Return the instance row a public input is exported to
+     * @dev panics if the circuit does not export the input
+     *
+     * @param input - the public input to locate
+     * @return - row of the instance column holding the input
+     */
+    pub fn row(self, input: PublicInput) -> usize {
+        match self.inputs().iter().position(|i| *i == input) {
+            Some(row) => row,
+            None => panic!(
+                "{:?} is not a public input of the {:?} circuit",
+                input, self
+            ),
+        }
+    }
+
+    /**
+     * Return the instance row a public input of one of several stacked copies of the circuit is
+     * exported to
+     * @dev copies share one instance column, copy i following copies 0..i in canonical order
+     *
+     * @param copy - index of the copy exporting the input
+     * @param input - the public input to locate
+     * @return - row of the instance column holding the copy's input
+     */
+    pub fn stacked_row(self, copy: usize, input: PublicInput) -> usize {
+        copy * self.inputs().len() + self.row(input)
+    }
+
+    /**
+     * Assemble the instance column of the circuit from its public values
+     *
+     * @param value - returns the value of each public input
+     * @return - instance column values in canonical row order
+     */
+    pub fn assemble<F>(self, value: impl FnMut(PublicInput) -> F) -> Vec<F> {
+        self.inputs().iter().copied().map(value).collect()
+    }
+
+    /**
+     * Assemble the instance column shared by several stacked copies of the circuit
+     *
+     * @param copies - number of copies stacked in the instance column
+     * @param value - returns the value of each public input of each copy
+     * @return - instance column values, each copy in canonical row order (see stacked_row)
+     */
+    pub fn assemble_stacked<F>(
+        self,
+        copies: usize,
+        mut value: impl FnMut(usize, PublicInput) -> F,
+    ) -> Vec<F> {
+        (0..copies)
+            .flat_map(|copy| self.assemble(|input| value(copy, input)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        crate::{
+            circuits::shot::ShotCircuit,
+            utils::{
+                binary::BinaryValue, board::Board, deck::Deck, ship::DEFAULT_WITNESS_OPTIONS,
+                shot::serialize,
+            },
+        },
+        halo2_gadgets::poseidon::primitives::{ConstantLength, Hash as Poseidon, P128Pow5T3},
+        halo2_proofs::{arithmetic::FieldExt, dev::MockProver, pasta::Fp},
+    };
+
+    #[test]
+    fn canonical_rows() {
+        // pinned: reordering any of these breaks every deployed verifier
+        assert_eq!(InstanceLayout::Board.row(PublicInput::Digest), 0);
+        for (input, row) in [
+            (PublicInput::Digest, 0),
+            (PublicInput::Shot, 1),
+            (PublicInput::Hit, 2),
+            (PublicInput::Nonce, 3),
+        ] {
+            assert_eq!(InstanceLayout::Shot.row(input), row);
+        }
+        assert_eq!(InstanceLayout::RegionMiss.row(PublicInput::Region), 1);
+        assert_eq!(InstanceLayout::Membership.row(PublicInput::Digest), 0);
+        assert_eq!(InstanceLayout::Win.row(PublicInput::Shots), 1);
+        assert_eq!(InstanceLayout::Win.row(PublicInput::Win), 2);
+        assert_eq!(InstanceLayout::Reveal.row(PublicInput::Board), 1);
+        assert_eq!(InstanceLayout::Tally.row(PublicInput::Shots), 1);
+        assert_eq!(InstanceLayout::Tally.row(PublicInput::Hits), 2);
+        assert_eq!(InstanceLayout::MerkleMembership.row(PublicInput::Root), 2);
+        assert_eq!(InstanceLayout::SaltedBoard.row(PublicInput::GameId), 1);
+        // stacked copies follow each other
+        assert_eq!(InstanceLayout::Board.stacked_row(2, PublicInput::Digest), 2);
+        assert_eq!(InstanceLayout::Shot.stacked_row(1, PublicInput::Hit), 6);
+        // salting only appends the game id
+        for layout in [
+            InstanceLayout::Board,
+            InstanceLayout::Shot,
+            InstanceLayout::RegionMiss,
+            InstanceLayout::Win,
+            InstanceLayout::Tally,
+            InstanceLayout::Membership,
+            InstanceLayout::Reveal,
+        ] {
+            let inputs = layout.inputs();
+            assert_eq!(&layout.salted().inputs()[..inputs.len()], inputs);
+            assert_eq!(layout.salted().row(PublicInput::GameId), inputs.len());
+        }
+    }
+
+    #[test]
+    fn shot_instance_order() {
+        let board = Board::from(&Deck::default()).state(DEFAULT_WITNESS_OPTIONS);
+        let shot = serialize::<1>([1], [0]);
+        let hit = BinaryValue::from_u8(1);
+        let digest = Poseidon::<_, P128Pow5T3, ConstantLength<1>, 3, 2>::init()
+            .hash([Fp::from_u128(board.lower_u128())]);
+        let value = |input| match input {
+            PublicInput::Digest => digest,
+            PublicInput::Shot => Fp::from_u128(shot.lower_u128()),
+            PublicInput::Hit => Fp::from_u128(hit.lower_u128()),
+            PublicInput::Nonce => Fp::from(7),
+            _ => unreachable!(),
+        };
+        let circuit = ShotCircuit::<P128Pow5T3, Fp>::new(board, shot, hit, Fp::from(7));
+        // the chip exports values in the layout's order
+        let instance = InstanceLayout::Shot.assemble(value);
+        let prover = MockProver::run(9, &circuit, vec![instance.clone()]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+        // any other order is rejected
+        let mut swapped = instance;
+        swapped.swap(2, 3);
+        let prover = MockProver::run(9, &circuit, vec![swapped]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "Hit is not a public input of the Board circuit")]
+    fn missing_input_row() {
+        InstanceLayout::Board.row(PublicInput::Hit);
+    }
+}
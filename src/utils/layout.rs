@@ -0,0 +1,244 @@
+use {
+    halo2_proofs::{
+        arithmetic::FieldExt,
+        circuit::Value,
+        dev::metadata,
+        plonk::{
+            Advice, Any, Assigned, Assignment, Circuit, Column, ConstraintSystem, Error, Fixed,
+            FloorPlanner, Instance, Selector,
+        },
+    },
+    std::collections::HashMap,
+};
+
+/**
+ * A region entered while laying out a circuit
+ *
+ * @param name - the name the region was assigned with
+ * @param namespace - names of the layouter namespaces enclosing the region, outermost first
+ * @param advice - (column, absolute row) -> value of each advice cell assigned in the region
+ *                 (only recorded by LayoutRecorder::with_values)
+ */
+#[derive(Clone, Debug)]
+pub struct RecordedRegion<F: FieldExt> {
+    pub name: String,
+    pub namespace: Vec<String>,
+    pub advice: HashMap<(Column<Advice>, usize), F>,
+}
+
+/**
+ * Assignment backend that records a circuit's layout without a MockProver
+ * @dev witness values are only computed by with_values, so a layout-only pass is cheap at any
+ *      circuit size. halo2 numbers regions in the order they are entered, so regions[i] is the
+ *      region a MockProver reports as region i
+ *
+ * @param rows - one past the highest row assigned, enabled or copied so far
+ * @param selectors - selector -> number of rows it is enabled on
+ * @param regions - every region in the order it was entered
+ */
+#[derive(Clone, Debug)]
+pub struct LayoutRecorder<F: FieldExt> {
+    pub rows: usize,
+    pub selectors: HashMap<Selector, usize>,
+    pub regions: Vec<RecordedRegion<F>>,
+    values: bool,
+    namespace: Vec<String>,
+    current: Option<usize>,
+}
+
+impl<F: FieldExt> Default for LayoutRecorder<F> {
+    fn default() -> Self {
+        LayoutRecorder {
+            rows: 0,
+            selectors: HashMap::new(),
+            regions: vec![],
+            values: false,
+            namespace: vec![],
+            current: None,
+        }
+    }
+}
+
+impl<F: FieldExt> LayoutRecorder<F> {
+    /**
+     * Construct a recorder that also evaluates and records assigned advice values
+     *
+     * @return - empty recorder with value recording on
+     */
+    pub fn with_values() -> Self {
+        LayoutRecorder {
+            values: true,
+            ..Default::default()
+        }
+    }
+
+    /**
+     * Lay out a circuit with its own floor planner, recording into this recorder
+     *
+     * @param circuit - the circuit to lay out (witness values are only needed with_values)
+     * @param config - config returned by configuring the circuit
+     * @param constants - the fixed columns the circuit's configure enabled for constants
+     * @return - Ok if the circuit synthesized
+     */
+    pub fn synthesize<C: Circuit<F>>(
+        &mut self,
+        circuit: &C,
+        config: C::Config,
+        constants: Vec<Column<Fixed>>,
+    ) -> Result<(), Error> {
+        C::FloorPlanner::synthesize(self, circuit, config, constants)
+    }
+
+    /**
+     * Find the recorded region a MockProver failure location refers to
+     * @dev halo2 keeps region metadata private, so regions are matched by equality
+     *
+     * @param region - region metadata reported in a VerifyFailure
+     * @return - the recorded region, or None if it was not laid out by this recorder
+     */
+    pub fn region(&self, region: &metadata::Region) -> Option<&RecordedRegion<F>> {
+        self.regions
+            .iter()
+            .enumerate()
+            .find(|(index, recorded)| {
+                *region == metadata::Region::from((*index, recorded.name.as_str()))
+            })
+            .map(|(_, recorded)| recorded)
+    }
+
+    fn touch(&mut self, row: usize) {
+        self.rows = self.rows.max(row + 1);
+    }
+}
+
+impl<F: FieldExt> Assignment<F> for LayoutRecorder<F> {
+    fn enter_region<NR, N>(&mut self, name: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+        self.current = Some(self.regions.len());
+        self.regions.push(RecordedRegion {
+            name: name().into(),
+            namespace: self.namespace.clone(),
+            advice: HashMap::new(),
+        });
+    }
+
+    fn exit_region(&mut self) {
+        self.current = None;
+    }
+
+    fn enable_selector<A, AR>(&mut self, _: A, selector: &Selector, row: usize) -> Result<(), Error>
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        *self.selectors.entry(*selector).or_insert(0) += 1;
+        self.touch(row);
+        Ok(())
+    }
+
+    fn query_instance(&self, _: Column<Instance>, _: usize) -> Result<Value<F>, Error> {
+        Ok(Value::unknown())
+    }
+
+    fn assign_advice<V, VR, A, AR>(
+        &mut self,
+        _: A,
+        column: Column<Advice>,
+        row: usize,
+        to: V,
+    ) -> Result<(), Error>
+    where
+        V: FnOnce() -> Value<VR>,
+        VR: Into<Assigned<F>>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.touch(row);
+        if !self.values {
+            return Ok(());
+        }
+        // halo2 only learns an assigned cell's value (used by later copies) when it is evaluated
+        let value = to();
+        if let Some(current) = self.current {
+            let advice = &mut self.regions[current].advice;
+            value.map(|value| advice.insert((column, row), value.into().evaluate()));
+        }
+        Ok(())
+    }
+
+    fn assign_fixed<V, VR, A, AR>(
+        &mut self,
+        _: A,
+        _: Column<Fixed>,
+        row: usize,
+        _: V,
+    ) -> Result<(), Error>
+    where
+        V: FnOnce() -> Value<VR>,
+        VR: Into<Assigned<F>>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.touch(row);
+        Ok(())
+    }
+
+    fn copy(
+        &mut self,
+        _: Column<Any>,
+        left_row: usize,
+        _: Column<Any>,
+        right_row: usize,
+    ) -> Result<(), Error> {
+        // copies into the instance column also need their public row to be usable
+        self.touch(left_row.max(right_row));
+        Ok(())
+    }
+
+    fn fill_from_row(
+        &mut self,
+        _: Column<Fixed>,
+        _: usize,
+        _: Value<Assigned<F>>,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn push_namespace<NR, N>(&mut self, name: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+        self.namespace.push(name().into());
+    }
+
+    fn pop_namespace(&mut self, _: Option<String>) {
+        self.namespace.pop();
+    }
+}
+
+/**
+ * Return the smallest k such that a circuit's layout fits in 2^k rows
+ * @dev halo2 reserves the last blinding_factors() + 1 rows of every column, and never proves
+ *      circuits with fewer than minimum_rows() rows, so both are read from the configured
+ *      constraint system rather than estimated
+ *
+ * @param circuit - the circuit to lay out (witness values are not needed)
+ * @param constants - the fixed columns the circuit's configure enabled for constants
+ * @return - minimum k to prove the circuit with, or the error synthesizing its layout
+ */
+pub fn min_k<F: FieldExt, C: Circuit<F>>(
+    circuit: &C,
+    constants: impl FnOnce(&C::Config) -> Vec<Column<Fixed>>,
+) -> Result<u32, Error> {
+    let mut meta = ConstraintSystem::<F>::default();
+    let config = C::configure(&mut meta);
+    let constants = constants(&config);
+    let mut layout = LayoutRecorder::default();
+    layout.synthesize(circuit, config, constants)?;
+    let rows = (layout.rows + meta.blinding_factors() + 1).max(meta.minimum_rows());
+    Ok(rows.next_power_of_two().trailing_zeros())
+}
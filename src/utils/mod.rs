@@ -2,4 +2,11 @@ pub mod ship;
 pub mod deck;
 pub mod board;
 pub mod binary;
-pub mod shot;
\ No newline at end of file
+pub mod shot;
+pub mod region;
+pub mod instance;
+pub mod layout;
+#[cfg(feature = "debug-advice")]
+pub mod debug;
+#[cfg(feature = "profiling")]
+pub mod profile;
\ No newline at end of file
@@ -0,0 +1,88 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/**
+ * Global allocator wrapping the system allocator to track live and peak heap usage
+ * @dev install in a binary with #[global_allocator]; used to budget the memory of the prover
+ *      where heap is constrained (ex: wasm in the browser)
+ *
+ * @param current - bytes currently allocated
+ * @param peak - most bytes allocated at once since construction or the last reset
+ * @param total - bytes allocated since construction or the last reset, ignoring frees
+ */
+pub struct PeakAllocator {
+    current: AtomicUsize,
+    peak: AtomicUsize,
+    total: AtomicUsize,
+}
+
+/**
+ * Heap usage recorded by a PeakAllocator over one profiled section
+ *
+ * @param peak - most bytes allocated at once during the section
+ * @param total - bytes allocated during the section, ignoring frees
+ * @param retained - bytes still allocated at the end of the section that were not before it
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HeapStats {
+    pub peak: usize,
+    pub total: usize,
+    pub retained: isize,
+}
+
+impl PeakAllocator {
+    pub const fn new() -> Self {
+        PeakAllocator {
+            current: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+            total: AtomicUsize::new(0),
+        }
+    }
+
+    /**
+     * Run a closure and record the heap usage of everything it allocates
+     * @dev peak is measured relative to the bytes live when the closure starts; sections should
+     *      not be profiled concurrently from multiple threads
+     *
+     * @param f - the section to profile
+     * @return - the closure's output and the heap usage it recorded
+     */
+    pub fn profile<T>(&self, f: impl FnOnce() -> T) -> (T, HeapStats) {
+        let start = self.current.load(Ordering::SeqCst);
+        self.peak.store(start, Ordering::SeqCst);
+        self.total.store(0, Ordering::SeqCst);
+        let output = f();
+        let end = self.current.load(Ordering::SeqCst);
+        let stats = HeapStats {
+            peak: self.peak.load(Ordering::SeqCst) - start,
+            total: self.total.load(Ordering::SeqCst),
+            retained: end as isize - start as isize,
+        };
+        (output, stats)
+    }
+}
+
+impl Default for PeakAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+unsafe impl GlobalAlloc for PeakAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let current = self.current.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+            self.peak.fetch_max(current, Ordering::SeqCst);
+            self.total.fetch_add(layout.size(), Ordering::SeqCst);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        self.current.fetch_sub(layout.size(), Ordering::SeqCst);
+    }
+}
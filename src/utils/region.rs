@@ -0,0 +1,35 @@
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{AssignedCell, Region},
+    plonk::{Advice, Column, Error},
+};
+
+/**
+ * Copy a run of assigned cells into consecutive rows of a column, constraining each copy equal
+ * to its source
+ *
+ * @param region - the region to assign the copies in
+ * @param cells - the assigned cells to copy in order
+ * @param column - the advice column to copy the cells into
+ * @param start_offset - the region offset of the first copy
+ * @return - the new assignments, in the same order as cells
+ */
+pub fn copy_cells<F: FieldExt>(
+    region: &mut Region<F>,
+    cells: &[AssignedCell<F, F>],
+    column: Column<Advice>,
+    start_offset: usize,
+) -> Result<Vec<AssignedCell<F, F>>, Error> {
+    cells
+        .iter()
+        .enumerate()
+        .map(|(i, cell)| {
+            cell.copy_advice(
+                || format!("copy cell {}", i),
+                region,
+                column,
+                start_offset + i,
+            )
+        })
+        .collect()
+}
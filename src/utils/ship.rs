@@ -3,7 +3,7 @@ use {
     bitvec::prelude::*,
 };
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 
 pub enum ShipType {
     Carrier,
@@ -79,8 +79,10 @@ impl ShipType {
 
 /**
  * Definition of a ship's placement on a board
+ * @dev two ships are equal (and hash the same) iff they share ship_type, x, y, and z
+ *      ships are totally ordered by (ship_type, x, y, z) where ship types follow deck order
  */
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Ship {
     pub ship_type: ShipType,
     pub x: u8, // [0, 9]
@@ -135,26 +137,103 @@ impl Ship {
 
 // use in battleship game
 impl Ship {
+    /**
+     * Return the (x, y) cells on the game board this ship covers
+     * @dev ordering is guaranteed: cells start at the ship head (x, y) and extend outward one
+     *      cell at a time along the orientation axis (+x if horizontal, +y if vertical)
+     *      ex: vertical cruiser at (3, 2) => [(3, 2), (3, 3), (3, 4)]
+     *
+     * @return - vector of ship_type.length() size containing (x, y) cells in order from the head
+     */
+    pub fn cells(self) -> Vec<(u8, u8)> {
+        (0..self.ship_type.length() as u8)
+            .map(|i| match self.z {
+                true => (self.x, self.y + i),
+                false => (self.x + i, self.y),
+            })
+            .collect()
+    }
+
+    /**
+     * Return the smallest box containing every cell this ship covers
+     *
+     * @return - ((min x, min y), (max x, max y)) of the cells the ship covers
+     */
+    pub fn bounding_box(self) -> ((u8, u8), (u8, u8)) {
+        let length = self.ship_type.length() as u8 - 1;
+        match self.z {
+            true => ((self.x, self.y), (self.x, self.y + length)),
+            false => ((self.x, self.y), (self.x + length, self.y)),
+        }
+    }
+
+    /**
+     * Return the ring of cells surrounding this ship, including diagonals
+     * @dev clipped to the board, so ships on an edge or in a corner have fewer adjacent cells.
+     *      a ship touches another (see NoTouchChip) if any of these cells is in the other's cells()
+     *
+     * @return - (x, y) cells next to the ship but not covered by it, in ascending y then x order
+     */
+    pub fn adjacent_cells(self) -> Vec<(u8, u8)> {
+        let ((min_x, min_y), (max_x, max_y)) = self.bounding_box();
+        let cells = self.cells();
+        (min_y.saturating_sub(1)..=(max_y + 1).min(9))
+            .flat_map(|y| (min_x.saturating_sub(1)..=(max_x + 1).min(9)).map(move |x| (x, y)))
+            .filter(|cell| !cells.contains(cell))
+            .collect()
+    }
+
+    /**
+     * Return true if this ship covers any cell also covered by another ship
+     * @dev compares cells() directly so no board state has to be built to detect a collision
+     *      ships whose bounding boxes are disjoint are rejected before any cell is compared
+     *
+     * @param other - the ship to check for shared cells against
+     * @return - true if the ships share at least one (x, y) cell
+     */
+    pub fn overlaps(self, other: Ship) -> bool {
+        let ((min_x, min_y), (max_x, max_y)) = self.bounding_box();
+        let ((other_min_x, other_min_y), (other_max_x, other_max_y)) = other.bounding_box();
+        if max_x < other_min_x || other_max_x < min_x || max_y < other_min_y || other_max_y < min_y
+        {
+            return false;
+        }
+        let cells = other.cells();
+        self.cells().iter().any(|cell| cells.contains(cell))
+    }
+
+    /**
+     * Rotate the ship in place about its head
+     * @dev only the orientation changes: a ship at (x, y) still has its head at (x, y)
+     *
+     * @return - the ship with z flipped, or None if the rotated ship would leave the board
+     */
+    pub fn rotate(self) -> Option<Ship> {
+        let rotated = Ship::new(self.ship_type, self.x, self.y, !self.z);
+        match rotated.cells().iter().all(|(x, y)| *x < 10 && *y < 10) {
+            true => Some(rotated),
+            false => None,
+        }
+    }
+
     /**
      * Return a vector of the coordinates on the game board this ship covers
+     * @dev follows the same ordering as cells()
      *
      * @param transpose - if true, apply vertical transposition rule
      * @return - vector of ship_type.length() size containing assigned coordinates
      */
     pub fn coordinates(self, transpose: bool) -> Vec<usize> {
         // if transpose is toggled, serialze vertical ships differently
-        let mut coordinates = Vec::<usize>::new();
-        for i in 0..self.ship_type.length() {
-            // compute coordinate point with index offset
-            let x_i = if self.z { self.x } else { self.x + i as u8 };
-            let y_i = if self.z { self.y + i as u8 } else { self.y };
-            // serialize coordinate point
-            let x = if transpose && self.z { x_i * 10 } else { x_i };
-            let y = if transpose && self.z { y_i } else { y_i * 10 };
-            // combine and store
-            coordinates.push((x + y) as usize);
-        }
-        coordinates
+        self.cells()
+            .into_iter()
+            .map(|(x_i, y_i)| {
+                // serialize coordinate point
+                let x = if transpose && self.z { x_i * 10 } else { x_i };
+                let y = if transpose && self.z { y_i } else { y_i * 10 };
+                (x + y) as usize
+            })
+            .collect()
     }
 
     /**
@@ -176,6 +255,17 @@ impl Ship {
             0,
         ]))
     }
+
+    /**
+     * Export the board cells this ship occupies, independent of orientation
+     * @dev never transposed: bit y * 10 + x is set for every cell in cells()
+     *      inverse of PlacementGadget::from_mask
+     *
+     * @return - mask of occupied board cells
+     */
+    pub fn to_mask(self) -> BinaryValue {
+        self.bits(false)
+    }
 }
 
 // use in a halo 2 proof
@@ -314,7 +404,7 @@ impl Ship {
 }
 
 // Defines possible options for witness generation for a given ship placement
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
 pub enum WitnessOption {
     // place the ship as a normal player would
     Default,
@@ -339,3 +429,115 @@ pub const DEFAULT_WITNESS_OPTIONS: [WitnessOption; 5] = [
     WitnessOption::Default,
     WitnessOption::Default,
 ];
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn adjacent_cells_clipped() {
+        // 7 x 3 ring around a centered carrier
+        let center = Ship::new(ShipType::Carrier, 2, 4, false);
+        assert_eq!(center.adjacent_cells().len(), 7 * 3 - 5);
+        // only the cells right of and below a carrier in the corner remain
+        let corner = Ship::new(ShipType::Carrier, 0, 0, false);
+        assert_eq!(
+            corner.adjacent_cells(),
+            vec![(5, 0), (0, 1), (1, 1), (2, 1), (3, 1), (4, 1), (5, 1)]
+        );
+        let ship = Ship::new(ShipType::Destroyer, 9, 8, true);
+        assert_eq!(ship.adjacent_cells(), vec![(8, 7), (9, 7), (8, 8), (8, 9)]);
+    }
+
+    #[test]
+    fn cells_vertical_from_head() {
+        let ship = Ship::new(ShipType::Cruiser, 3, 2, true);
+        assert_eq!(ship.cells(), vec![(3, 2), (3, 3), (3, 4)]);
+    }
+
+    #[test]
+    fn cells_horizontal_from_head() {
+        let ship = Ship::new(ShipType::Battleship, 5, 7, false);
+        assert_eq!(ship.cells(), vec![(5, 7), (6, 7), (7, 7), (8, 7)]);
+    }
+
+    #[test]
+    fn coordinates_follow_cells() {
+        let ship = Ship::new(ShipType::Cruiser, 3, 2, true);
+        assert_eq!(ship.coordinates(false), vec![23, 33, 43]);
+        assert_eq!(ship.coordinates(true), vec![32, 33, 34]);
+    }
+
+    #[test]
+    fn rotate_about_head() {
+        // horizontal battleship at (2, 3) rotates to vertical covering (2, 3) to (2, 6)
+        let ship = Ship::new(ShipType::Battleship, 2, 3, false);
+        let rotated = ship.rotate().unwrap();
+        assert_eq!(rotated, Ship::new(ShipType::Battleship, 2, 3, true));
+        assert_eq!(rotated.cells(), vec![(2, 3), (2, 4), (2, 5), (2, 6)]);
+        assert_eq!(rotated.rotate(), Some(ship));
+    }
+
+    #[test]
+    fn rotate_off_board() {
+        // horizontal carrier on row 7 would extend to y: 11 if rotated
+        let ship = Ship::new(ShipType::Carrier, 0, 7, false);
+        assert_eq!(ship.rotate(), None);
+        // vertical destroyer at the right edge cannot lie horizontally
+        let ship = Ship::new(ShipType::Destroyer, 9, 0, true);
+        assert_eq!(ship.rotate(), None);
+    }
+
+    #[test]
+    fn bounding_box_orientations() {
+        let horizontal = Ship::new(ShipType::Battleship, 2, 7, false);
+        assert_eq!(horizontal.bounding_box(), ((2, 7), (5, 7)));
+        let vertical = Ship::new(ShipType::Cruiser, 9, 1, true);
+        assert_eq!(vertical.bounding_box(), ((9, 1), (9, 3)));
+        // the box spans exactly the first and last cell
+        for ship in [horizontal, vertical] {
+            let cells = ship.cells();
+            assert_eq!(ship.bounding_box(), (cells[0], cells[cells.len() - 1]));
+        }
+    }
+
+    #[test]
+    fn overlaps_disjoint_bounding_boxes() {
+        // boxes are separated along x, then along y
+        let carrier = Ship::new(ShipType::Carrier, 0, 0, true);
+        let destroyer = Ship::new(ShipType::Destroyer, 1, 0, true);
+        assert!(!carrier.overlaps(destroyer));
+        assert!(!destroyer.overlaps(carrier));
+        let destroyer = Ship::new(ShipType::Destroyer, 0, 5, false);
+        assert!(!carrier.overlaps(destroyer));
+        assert!(!destroyer.overlaps(carrier));
+    }
+
+    #[test]
+    fn overlaps_adjacent_ships() {
+        // destroyer sits directly below the cruiser without sharing a cell
+        let cruiser = Ship::new(ShipType::Cruiser, 3, 2, false);
+        let destroyer = Ship::new(ShipType::Destroyer, 3, 3, false);
+        assert!(!cruiser.overlaps(destroyer));
+        assert!(!destroyer.overlaps(cruiser));
+    }
+
+    #[test]
+    fn overlaps_crossing_ships() {
+        // vertical battleship crosses the horizontal carrier at (4, 5)
+        let carrier = Ship::new(ShipType::Carrier, 2, 5, false);
+        let battleship = Ship::new(ShipType::Battleship, 4, 3, true);
+        assert!(carrier.overlaps(battleship));
+        assert!(battleship.overlaps(carrier));
+    }
+
+    #[test]
+    fn overlaps_shared_endpoint() {
+        // submarine's head is the cruiser's tail at (5, 2)
+        let cruiser = Ship::new(ShipType::Cruiser, 3, 2, false);
+        let submarine = Ship::new(ShipType::Submarine, 5, 2, true);
+        assert!(cruiser.overlaps(submarine));
+        assert!(submarine.overlaps(cruiser));
+    }
+}
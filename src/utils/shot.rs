@@ -19,4 +19,46 @@ pub fn serialize<const S: usize>(x: [u8; S], y: [u8; S]) -> BinaryValue {
         value.set(index, true);
     }
     BinaryValue::new(value)
+}
+
+/**
+ * Serializes a list of X, Y cells into a single binary value
+ * @dev panics if a coordinate is off the 10x10 board, which would otherwise alias another cell
+ *
+ * @param cells - (x, y) coordinates of each cell to include
+ * @return - 100-bit integer where the (y*10 + x)th bit is flipped for every cell
+ */
+pub fn serialize_cells(cells: &[(u8, u8)]) -> BinaryValue {
+    let mut value: U256 = U256::new([0, 0, 0, 0]);
+    for &(x, y) in cells {
+        assert!(x < 10 && y < 10, "cell ({}, {}) is off the board", x, y);
+        value.set(y as usize * 10 + x as usize, true);
+    }
+    BinaryValue::new(value)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn serialize_cells_corners() {
+        let value = serialize_cells(&[(0, 0), (9, 0), (0, 9), (9, 9)]);
+        let flipped = value.value.iter_ones().collect::<Vec<usize>>();
+        assert_eq!(flipped, vec![0, 9, 90, 99]);
+    }
+
+    #[test]
+    #[should_panic(expected = "cell (10, 0) is off the board")]
+    fn serialize_cells_off_board() {
+        // (10, 0) would otherwise alias (0, 1)
+        serialize_cells(&[(10, 0)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "cell (0, 26) is off the board")]
+    fn serialize_cells_overflow() {
+        // 26 * 10 overflows u8
+        serialize_cells(&[(0, 26)]);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,153 @@
+use {
+    battlezips_v2::{
+        circuits::{
+            board::{BoardCircuit, SaltedBoardCircuit},
+            shot::ShotCircuit,
+            win::WinCircuit,
+        },
+        prover::{commit_board, mock_prove},
+        utils::{
+            binary::BinaryValue,
+            board::{sink_all_shots, Board},
+            deck::Deck,
+            instance::{InstanceLayout, PublicInput},
+            ship::{ShipType, DEFAULT_WITNESS_OPTIONS},
+            shot::serialize,
+        },
+    },
+    halo2_gadgets::poseidon::primitives::{ConstantLength, Hash as Poseidon, P128Pow5T3},
+    halo2_proofs::{arithmetic::FieldExt, pasta::Fp},
+};
+
+/**
+ * Mock prove a shot against a board and return whether the proof verified
+ *
+ * @param board - the private board state the shot is fired at
+ * @param commitment - the public board commitment the shot proof must match
+ * @param x - horizontal coordinate of the shot
+ * @param y - vertical coordinate of the shot
+ * @param hit - the public hit/ miss assertion made by the board owner
+ * @param turn - the turn nonce the shot is fired on
+ * @return - true if the shot proof is satisfied, false otherwise
+ */
+fn prove_shot(board: BinaryValue, commitment: Fp, x: u8, y: u8, hit: bool, turn: u64) -> bool {
+    let shot = serialize::<1>([x], [y]);
+    let hit = BinaryValue::from_u8(hit as u8);
+    let public_inputs = vec![
+        commitment,
+        Fp::from_u128(shot.lower_u128()),
+        Fp::from_u128(hit.lower_u128()),
+        Fp::from(turn),
+    ];
+    let circuit = ShotCircuit::<P128Pow5T3, Fp>::new(board, shot, hit, Fp::from(turn));
+    mock_prove(&circuit, 9, vec![public_inputs]).is_ok()
+}
+
+#[test]
+fn game_flow() {
+    // player places a valid board
+    let board = Board::from(&Deck::from([
+        Some((3, 3, true)),
+        Some((5, 4, false)),
+        Some((0, 1, false)),
+        Some((0, 5, true)),
+        Some((6, 1, false)),
+    ]));
+    let state = board.state(DEFAULT_WITNESS_OPTIONS);
+
+    // player commits to the board and proves it is valid
+    let commitment = Poseidon::<_, P128Pow5T3, ConstantLength<1>, 3, 2>::init()
+        .hash([Fp::from_u128(state.lower_u128())]);
+    let circuit =
+        BoardCircuit::<P128Pow5T3, Fp>::new(board.witness(DEFAULT_WITNESS_OPTIONS), state);
+    assert_eq!(mock_prove(&circuit, 12, vec![vec![commitment]]), Ok(()));
+
+    // opponent fires a sequence of shots, each proven against the same board commitment
+    let shots: [(u8, u8); 6] = [(0, 0), (3, 5), (9, 9), (5, 4), (4, 3), (6, 1)];
+    let mut turn = 0;
+    for (x, y) in shots {
+        let hit = state.value[(y * 10 + x) as usize];
+        assert!(prove_shot(state, commitment, x, y, hit, turn));
+        // the board owner cannot lie about the outcome of any shot
+        assert!(!prove_shot(state, commitment, x, y, !hit, turn));
+        turn += 1;
+    }
+
+    // opponent sinks the carrier by proving a hit on every cell it covers
+    let carrier = board.ships[ShipType::Carrier].unwrap();
+    let cells = carrier.coordinates(false);
+    assert_eq!(cells.len(), ShipType::Carrier.length());
+    for cell in cells {
+        let (x, y) = ((cell % 10) as u8, (cell / 10) as u8);
+        assert!(prove_shot(state, commitment, x, y, true, turn));
+        turn += 1;
+    }
+
+    // a shot proof made against a different board commitment does not verify
+    let other = Board::from(&Deck::default()).state(DEFAULT_WITNESS_OPTIONS);
+    let other_commitment = Poseidon::<_, P128Pow5T3, ConstantLength<1>, 3, 2>::init()
+        .hash([Fp::from_u128(other.lower_u128())]);
+    assert!(!prove_shot(other, commitment, 3, 5, false, turn));
+    assert!(prove_shot(other, other_commitment, 3, 5, false, turn));
+}
+
+#[test]
+fn salted_game_flow() {
+    let board = Board::from(&Deck::from([
+        Some((3, 3, true)),
+        Some((5, 4, false)),
+        Some((0, 1, false)),
+        Some((0, 5, true)),
+        Some((6, 1, false)),
+    ]));
+    let state = board.state(DEFAULT_WITNESS_OPTIONS);
+    let (secret, game_id) = (Fp::from(0xbee5), Fp::from(42));
+
+    // player commits to the board under the game's salt and proves it is valid
+    let commitment = commit_board(state, secret, game_id);
+    let circuit = SaltedBoardCircuit::<P128Pow5T3, Fp>::new(
+        board.witness(DEFAULT_WITNESS_OPTIONS),
+        state,
+        secret,
+        game_id,
+    );
+    assert_eq!(
+        mock_prove(&circuit, 12, vec![vec![commitment, game_id]]),
+        Ok(())
+    );
+
+    // shots are proven against the same salted commitment for the same game
+    let shot_proof = |x: u8, y: u8, hit: bool, digest: Fp, game: Fp| {
+        let shot = serialize::<1>([x], [y]);
+        let hit = BinaryValue::from_u8(hit as u8);
+        let instance = InstanceLayout::SaltedShot.assemble(|input| match input {
+            PublicInput::Digest => digest,
+            PublicInput::Shot => Fp::from_u128(shot.lower_u128()),
+            PublicInput::Hit => Fp::from_u128(hit.lower_u128()),
+            PublicInput::GameId => game,
+            _ => Fp::zero(),
+        });
+        let circuit =
+            ShotCircuit::<P128Pow5T3, Fp>::new(state, shot, hit, Fp::zero()).salted(secret, game);
+        mock_prove(&circuit, 9, vec![instance]).is_ok()
+    };
+    assert!(shot_proof(3, 5, true, commitment, game_id));
+    assert!(shot_proof(0, 0, false, commitment, game_id));
+    assert!(!shot_proof(0, 0, true, commitment, game_id));
+    // the salted commitment of one game does not verify shots of another
+    assert!(!shot_proof(3, 5, true, commitment, Fp::from(43)));
+
+    // the win is proven against the salted commitment as well
+    let mut fired = BinaryValue::empty();
+    for (x, y) in sink_all_shots(state) {
+        fired.value.set(y * 10 + x, true);
+    }
+    let circuit = WinCircuit::<P128Pow5T3, Fp>::new(state, fired).salted(secret, game_id);
+    let instance = InstanceLayout::SaltedWin.assemble(|input| match input {
+        PublicInput::Digest => commitment,
+        PublicInput::Shots => Fp::from_u128(fired.lower_u128()),
+        PublicInput::Win => Fp::one(),
+        _ => game_id,
+    });
+    assert_eq!(mock_prove(&circuit, 9, vec![instance]), Ok(()));
+}